@@ -0,0 +1,265 @@
+//! Printing throughput of a large unit.
+//!
+//! The unit is generated: copies of one ERC20 contract, so the numbers only compare runs of this
+//! bench against each other.
+#![feature(test)]
+
+extern crate test;
+
+use std::io;
+
+use ast_parser::{
+    ast_parse::parse,
+    ast_serialize::{AstSerializer, PrintContext},
+    ast_specs::SourceUnit,
+};
+use test::{Bencher, black_box};
+
+/// Copies of the contract in the fixture.
+const CONTRACTS: usize = 80;
+
+const HEADER: &str = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+error ERC20InsufficientBalance(address sender, uint256 balance, uint256 needed);
+error ERC20InvalidSender(address sender);
+error ERC20InvalidReceiver(address receiver);
+error ERC20InsufficientAllowance(address spender, uint256 allowance, uint256 needed);
+error ERC20InvalidApprover(address approver);
+error ERC20InvalidSpender(address spender);
+"#;
+
+/// An ERC20 in the shape of OpenZeppelin's, `CONTRACT` replaced by a name of its own per copy.
+const ERC20: &str = r#"
+/// @title ERC20 token
+/// @notice Implementation of the ERC20 standard
+abstract contract CONTRACT {
+    struct Checkpoint {
+        uint48 key;
+        uint208 value;
+    }
+
+    enum Rounding {
+        Floor,
+        Ceil,
+        Trunc,
+        Expand
+    }
+
+    event Transfer(address indexed from, address indexed to, uint256 value);
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+
+    mapping(address account => uint256) private _balances;
+    mapping(address account => mapping(address spender => uint256)) private _allowances;
+    mapping(address account => Checkpoint[]) private _checkpoints;
+    uint256 private _totalSupply;
+    string private _name;
+    string private _symbol;
+    uint256 public constant MAX_SUPPLY = 1_000_000 ether;
+
+    modifier onlyHolder(address account) {
+        if (_balances[account] == 0) {
+            revert ERC20InvalidSender(account);
+        }
+        _;
+    }
+
+    constructor(string memory name_, string memory symbol_) {
+        _name = name_;
+        _symbol = symbol_;
+    }
+
+    function name() public view virtual returns (string memory) {
+        return _name;
+    }
+
+    function symbol() public view virtual returns (string memory) {
+        return _symbol;
+    }
+
+    function decimals() public view virtual returns (uint8) {
+        return 18;
+    }
+
+    function totalSupply() public view virtual returns (uint256) {
+        return _totalSupply;
+    }
+
+    function balanceOf(address account) public view virtual returns (uint256) {
+        return _balances[account];
+    }
+
+    function transfer(address to, uint256 value) public virtual returns (bool) {
+        address owner = msg.sender;
+        _transfer(owner, to, value);
+        return true;
+    }
+
+    function allowance(address owner, address spender) public view virtual returns (uint256) {
+        return _allowances[owner][spender];
+    }
+
+    function approve(address spender, uint256 value) public virtual returns (bool) {
+        address owner = msg.sender;
+        _approve(owner, spender, value, true);
+        return true;
+    }
+
+    function transferFrom(address from, address to, uint256 value) public virtual returns (bool) {
+        address spender = msg.sender;
+        _spendAllowance(from, spender, value);
+        _transfer(from, to, value);
+        return true;
+    }
+
+    function _transfer(address from, address to, uint256 value) internal {
+        if (from == address(0)) {
+            revert ERC20InvalidSender(address(0));
+        }
+        if (to == address(0)) {
+            revert ERC20InvalidReceiver(address(0));
+        }
+        _update(from, to, value);
+    }
+
+    function _update(address from, address to, uint256 value) internal virtual {
+        if (from == address(0)) {
+            _totalSupply += value;
+        } else {
+            uint256 fromBalance = _balances[from];
+            if (fromBalance < value) {
+                revert ERC20InsufficientBalance(from, fromBalance, value);
+            }
+            unchecked {
+                _balances[from] = fromBalance - value;
+            }
+        }
+
+        if (to == address(0)) {
+            unchecked {
+                _totalSupply -= value;
+            }
+        } else {
+            unchecked {
+                _balances[to] += value;
+            }
+        }
+
+        emit Transfer(from, to, value);
+    }
+
+    function _mint(address account, uint256 value) internal {
+        if (account == address(0)) {
+            revert ERC20InvalidReceiver(address(0));
+        }
+        _update(address(0), account, value);
+    }
+
+    function _burn(address account, uint256 value) internal onlyHolder(account) {
+        if (account == address(0)) {
+            revert ERC20InvalidSender(address(0));
+        }
+        _update(account, address(0), value);
+    }
+
+    function _approve(address owner, address spender, uint256 value, bool emitEvent) internal virtual {
+        if (owner == address(0)) {
+            revert ERC20InvalidApprover(address(0));
+        }
+        if (spender == address(0)) {
+            revert ERC20InvalidSpender(address(0));
+        }
+        _allowances[owner][spender] = value;
+        if (emitEvent) {
+            emit Approval(owner, spender, value);
+        }
+    }
+
+    function _spendAllowance(address owner, address spender, uint256 value) internal virtual {
+        uint256 currentAllowance = allowance(owner, spender);
+        if (currentAllowance < type(uint256).max) {
+            if (currentAllowance < value) {
+                revert ERC20InsufficientAllowance(spender, currentAllowance, value);
+            }
+            unchecked {
+                _approve(owner, spender, currentAllowance - value, false);
+            }
+        }
+    }
+
+    function _push(address account, uint48 key, uint208 value) internal returns (uint208, uint208) {
+        Checkpoint[] storage checkpoints = _checkpoints[account];
+        uint256 length = checkpoints.length;
+        if (length > 0) {
+            Checkpoint storage last = checkpoints[length - 1];
+            uint208 lastValue = last.value;
+            if (last.key == key) {
+                last.value = value;
+            } else {
+                checkpoints.push(Checkpoint({key: key, value: value}));
+            }
+            return (lastValue, value);
+        }
+        checkpoints.push(Checkpoint({key: key, value: value}));
+        return (0, value);
+    }
+
+    function _upperLookup(address account, uint48 key) internal view returns (uint208) {
+        Checkpoint[] storage checkpoints = _checkpoints[account];
+        uint256 low = 0;
+        uint256 high = checkpoints.length;
+        while (low < high) {
+            uint256 mid = _average(low, high);
+            if (checkpoints[mid].key > key) {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        return high == 0 ? 0 : checkpoints[high - 1].value;
+    }
+
+    function _average(uint256 a, uint256 b) internal pure returns (uint256) {
+        return (a & b) + (a ^ b) / 2;
+    }
+
+    function _mulDiv(uint256 x, uint256 y, uint256 denominator, Rounding rounding) internal pure returns (uint256 result) {
+        result = x * y / denominator;
+        if (rounding == Rounding.Ceil && mulmod(x, y, denominator) > 0) {
+            result += 1;
+        }
+    }
+
+    function _sum(uint256[] memory values) internal pure returns (uint256 total) {
+        for (uint256 i = 0; i < values.length; i++) {
+            total += values[i];
+        }
+    }
+}
+"#;
+
+fn fixture() -> SourceUnit {
+    let mut source = HEADER.to_owned();
+    for i in 0..CONTRACTS {
+        source.push_str(&ERC20.replace("CONTRACT", &format!("ERC20_{i}")));
+    }
+    parse(&source).expect("The fixture is valid Solidity")
+}
+
+#[bench]
+fn to_sol_string(b: &mut Bencher) {
+    let unit = fixture();
+    b.bytes = unit.to_sol_string().len() as u64;
+    b.iter(|| black_box(&unit).to_sol_string());
+}
+
+#[bench]
+fn write_sol_to_sink(b: &mut Bencher) {
+    let unit = fixture();
+    b.bytes = unit.to_sol_string().len() as u64;
+    b.iter(|| {
+        black_box(&unit)
+            .write_sol(&mut io::sink(), &mut PrintContext::default())
+            .expect("Writing to a sink never fails")
+    });
+}
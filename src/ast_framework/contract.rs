@@ -0,0 +1,273 @@
+use std::collections::BTreeMap;
+
+use getters::Getters;
+use serde::{Deserialize, Deserializer, de::Error as _};
+
+/// Per-contract compiler output (`contracts[path][name]` in standard-JSON).
+#[derive(Deserialize, Debug, Clone, Default, Getters, PartialEq)]
+pub struct Contract {
+    #[serde(default, deserialize_with = "abi_or_string")]
+    #[return_type = "&[AbiItem]"]
+    abi: Vec<AbiItem>,
+    #[use_as_ref]
+    #[return_type = "Option<&Evm>"]
+    evm: Option<Evm>,
+    #[serde(rename = "storageLayout")]
+    #[use_as_ref]
+    #[return_type = "Option<&StorageLayout>"]
+    storage_layout: Option<StorageLayout>,
+    #[serde(default, deserialize_with = "metadata_or_string")]
+    #[use_as_ref]
+    #[return_type = "Option<&Metadata>"]
+    metadata: Option<Metadata>,
+}
+
+/// Contract entry of `solc --combined-json`, keyed by `path:Name`.
+#[derive(Deserialize, Debug)]
+pub(super) struct CombinedContract {
+    #[serde(default, deserialize_with = "abi_or_string")]
+    abi: Vec<AbiItem>,
+    bin: Option<String>,
+    #[serde(rename = "bin-runtime")]
+    bin_runtime: Option<String>,
+    srcmap: Option<String>,
+    #[serde(rename = "srcmap-runtime")]
+    srcmap_runtime: Option<String>,
+    #[serde(rename = "storage-layout")]
+    storage_layout: Option<StorageLayout>,
+    #[serde(default, deserialize_with = "metadata_or_string")]
+    metadata: Option<Metadata>,
+}
+
+impl From<CombinedContract> for Contract {
+    fn from(value: CombinedContract) -> Self {
+        let bytecode = |object: Option<String>, source_map: Option<String>| {
+            object.map(|object| Bytecode {
+                object,
+                source_map,
+                ..Default::default()
+            })
+        };
+        let bytecode_present = value.bin.is_some() || value.bin_runtime.is_some();
+
+        Self {
+            abi: value.abi,
+            evm: bytecode_present.then(|| Evm {
+                bytecode: bytecode(value.bin, value.srcmap),
+                deployed_bytecode: bytecode(value.bin_runtime, value.srcmap_runtime),
+                ..Default::default()
+            }),
+            storage_layout: value.storage_layout,
+            metadata: value.metadata,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Getters)]
+pub struct AbiItem {
+    #[serde(rename = "type")]
+    #[copy]
+    kind: AbiItemKind,
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    name: Option<String>,
+    #[serde(default)]
+    #[return_type = "&[AbiParam]"]
+    inputs: Vec<AbiParam>,
+    #[serde(default)]
+    #[return_type = "&[AbiParam]"]
+    outputs: Vec<AbiParam>,
+    #[serde(rename = "stateMutability")]
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    state_mutability: Option<String>,
+    #[copy]
+    anonymous: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AbiItemKind {
+    Function,
+    Constructor,
+    Receive,
+    Fallback,
+    Event,
+    Error,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Getters)]
+pub struct AbiParam {
+    #[serde(default)]
+    #[return_type = "&str"]
+    name: String,
+    #[serde(rename = "type")]
+    #[return_type = "&str"]
+    kind: String,
+    #[serde(rename = "internalType")]
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    internal_type: Option<String>,
+    #[serde(default)]
+    #[return_type = "&[AbiParam]"]
+    components: Vec<AbiParam>,
+    #[copy]
+    indexed: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Getters)]
+pub struct Evm {
+    #[use_as_ref]
+    #[return_type = "Option<&Bytecode>"]
+    bytecode: Option<Bytecode>,
+    #[serde(rename = "deployedBytecode")]
+    #[use_as_ref]
+    #[return_type = "Option<&Bytecode>"]
+    deployed_bytecode: Option<Bytecode>,
+    #[serde(rename = "methodIdentifiers", default)]
+    method_identifiers: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq, Getters)]
+pub struct Bytecode {
+    #[serde(default)]
+    #[return_type = "&str"]
+    object: String,
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    opcodes: Option<String>,
+    #[serde(rename = "sourceMap")]
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    source_map: Option<String>,
+    #[serde(rename = "linkReferences", default)]
+    link_references: BTreeMap<String, BTreeMap<String, Vec<ByteRange>>>,
+    /// Only present on `deployedBytecode`, keyed by the AST id of the immutable.
+    #[serde(rename = "immutableReferences", default)]
+    immutable_references: BTreeMap<String, Vec<ByteRange>>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Getters)]
+pub struct ByteRange {
+    #[copy]
+    start: usize,
+    #[copy]
+    length: usize,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq, Getters)]
+pub struct StorageLayout {
+    #[serde(default)]
+    #[return_type = "&[StorageSlot]"]
+    storage: Vec<StorageSlot>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    types: BTreeMap<String, StorageType>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Getters)]
+pub struct StorageSlot {
+    #[serde(rename = "astId")]
+    #[copy]
+    ast_id: isize,
+    #[return_type = "&str"]
+    contract: String,
+    #[return_type = "&str"]
+    label: String,
+    #[copy]
+    offset: usize,
+    /// Decimal slot number, kept as a string since it may exceed 64 bits.
+    #[return_type = "&str"]
+    slot: String,
+    #[serde(rename = "type")]
+    #[return_type = "&str"]
+    kind: String,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Getters)]
+pub struct StorageType {
+    #[return_type = "&str"]
+    encoding: String,
+    #[return_type = "&str"]
+    label: String,
+    #[serde(rename = "numberOfBytes")]
+    #[return_type = "&str"]
+    number_of_bytes: String,
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    base: Option<String>,
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    key: Option<String>,
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    value: Option<String>,
+    #[use_as_deref]
+    #[return_type = "Option<&[StorageSlot]>"]
+    members: Option<Vec<StorageSlot>>,
+}
+
+/// Contract metadata, emitted by solc as an embedded JSON string.
+#[derive(Deserialize, Debug, Clone, PartialEq, Getters)]
+pub struct Metadata {
+    compiler: MetadataCompiler,
+    #[return_type = "&str"]
+    language: String,
+    #[serde(default)]
+    output: serde_json::Value,
+    #[serde(default)]
+    settings: serde_json::Value,
+    #[serde(default)]
+    sources: BTreeMap<String, MetadataSource>,
+    #[copy]
+    version: usize,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Getters)]
+pub struct MetadataCompiler {
+    #[return_type = "&str"]
+    version: String,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Getters)]
+pub struct MetadataSource {
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    keccak256: Option<String>,
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    license: Option<String>,
+    #[serde(default)]
+    #[return_type = "&[String]"]
+    urls: Vec<String>,
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    content: Option<String>,
+}
+
+/// Older `--combined-json` output encodes the ABI as a JSON string.
+fn abi_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<AbiItem>, D::Error> {
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::Null => Ok(Vec::new()),
+        serde_json::Value::String(raw) => serde_json::from_str(&raw).map_err(D::Error::custom),
+        value => serde_json::from_value(value).map_err(D::Error::custom),
+    }
+}
+
+fn metadata_or_string<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Metadata>, D::Error> {
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::Null => Ok(None),
+        serde_json::Value::String(raw) if raw.is_empty() => Ok(None),
+        serde_json::Value::String(raw) => serde_json::from_str(&raw).map_err(D::Error::custom),
+        value => serde_json::from_value(value).map_err(D::Error::custom),
+    }
+}
+
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
@@ -0,0 +1,86 @@
+use std::fmt::Display;
+
+use getters::Getters;
+use serde::Deserialize;
+
+/// One entry of the `errors` array of solc standard-JSON output.
+#[derive(Deserialize, Debug, Clone, Getters, PartialEq, Eq)]
+pub struct SolcDiagnostic {
+    #[copy]
+    severity: Severity,
+    /// `ParserError`, `TypeError`, `Warning`, ...
+    #[serde(rename = "type")]
+    #[return_type = "&str"]
+    kind: String,
+    #[return_type = "&str"]
+    component: String,
+    #[serde(rename = "errorCode")]
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    error_code: Option<String>,
+    #[return_type = "&str"]
+    message: String,
+    #[serde(rename = "formattedMessage")]
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    formatted_message: Option<String>,
+    #[serde(rename = "sourceLocation")]
+    #[use_as_ref]
+    #[return_type = "Option<&DiagnosticLocation>"]
+    source_location: Option<DiagnosticLocation>,
+    #[serde(rename = "secondarySourceLocations", default)]
+    #[return_type = "&[DiagnosticLocation]"]
+    secondary_source_locations: Vec<DiagnosticLocation>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// Byte range of a diagnostic; `start` and `end` are `-1` when unknown.
+#[derive(Deserialize, Debug, Clone, Getters, PartialEq, Eq)]
+pub struct DiagnosticLocation {
+    #[return_type = "&str"]
+    file: String,
+    #[copy]
+    start: isize,
+    #[copy]
+    end: isize,
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    message: Option<String>,
+}
+
+impl SolcDiagnostic {
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Info => write!(f, "info"),
+        }
+    }
+}
+
+impl Display for SolcDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.kind, self.message)?;
+        if let Some(location) = &self.source_location {
+            write!(
+                f,
+                " --> {}:{}:{}",
+                location.file, location.start, location.end
+            )?;
+        }
+        Ok(())
+    }
+}
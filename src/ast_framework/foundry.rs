@@ -1,14 +1,53 @@
-use serde::Deserialize;
+use std::{io::Read, path::Path};
 
-use crate::ast_specs::SourceUnit;
+use serde::{Deserialize, Deserializer};
+
+use crate::{AstParserResult, ast_normalize, ast_specs::SourceUnit, utils::json_locator};
 
 #[derive(Deserialize, Debug)]
 pub struct FoundryOutput {
     pub ast: SourceUnit,
+    #[serde(rename = "metadata", default, deserialize_with = "compiler_version")]
+    compiler_version: Option<String>,
 }
 
 impl FoundryOutput {
+    pub fn from_reader<R: Read>(reader: R) -> AstParserResult<Self> {
+        json_locator::from_reader_normalized(reader, ast_normalize::normalize_artifact)
+            .map(Self::record_compiler_version)
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P) -> AstParserResult<Self> {
+        json_locator::from_path_normalized(path, ast_normalize::normalize_artifact)
+            .map(Self::record_compiler_version)
+    }
+
     pub fn ast(&self) -> &SourceUnit {
         &self.ast
     }
+
+    pub fn compiler_version(&self) -> Option<&str> {
+        self.compiler_version.as_deref()
+    }
+
+    fn record_compiler_version(mut self) -> Self {
+        self.ast.set_compiler_version(self.compiler_version.clone());
+        self
+    }
+}
+
+/// `metadata.compiler.version` of an artifact, with `metadata` either inline or a JSON string.
+pub(super) fn compiler_version<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    let metadata = serde_json::Value::deserialize(deserializer)?;
+    let metadata = match metadata {
+        serde_json::Value::String(raw) => serde_json::from_str(&raw).unwrap_or_default(),
+        metadata => metadata,
+    };
+
+    Ok(metadata
+        .pointer("/compiler/version")
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_owned))
 }
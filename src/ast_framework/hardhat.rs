@@ -0,0 +1,97 @@
+use std::{collections::BTreeMap, fs, io::Read, path::Path};
+
+use getters::Getters;
+use serde::Deserialize;
+
+use crate::{AstParserResult, ast_normalize, ast_specs::SourceUnit, utils::json_locator};
+
+use super::{solc::SolcOutput, standard_json::StandardJsonInput};
+
+/// A Hardhat `artifacts/build-info/*.json` file.
+///
+/// One build-info holds the complete compiler input and output of a single
+/// solc invocation, so it covers every source of the project compiled with
+/// that compiler version.
+#[derive(Deserialize, Debug, Getters)]
+pub struct HardhatBuildInfo {
+    #[return_type = "&str"]
+    id: String,
+    #[serde(rename = "_format")]
+    #[return_type = "&str"]
+    format: String,
+    #[serde(rename = "solcVersion")]
+    #[return_type = "&str"]
+    solc_version: String,
+    #[serde(rename = "solcLongVersion")]
+    #[return_type = "&str"]
+    solc_long_version: String,
+    input: StandardJsonInput,
+    output: SolcOutput,
+}
+
+impl HardhatBuildInfo {
+    pub fn from_reader<R: Read>(reader: R) -> AstParserResult<Self> {
+        json_locator::from_reader_normalized(reader, normalize_output)
+            .map(Self::record_compiler_version)
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P) -> AstParserResult<Self> {
+        json_locator::from_path_normalized(path, normalize_output)
+            .map(Self::record_compiler_version)
+    }
+
+    /// Loads every `*.json` file of a `build-info` directory, ordered by file name.
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> AstParserResult<Vec<Self>> {
+        let mut paths = fs::read_dir(dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        paths.retain(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "json"));
+        paths.sort();
+
+        paths.into_iter().map(Self::from_path).collect()
+    }
+
+    /// All source units of the build keyed by their `absolutePath`.
+    pub fn source_units(&self) -> BTreeMap<&str, &SourceUnit> {
+        self.output
+            .source_units()
+            .into_values()
+            .map(|unit| (unit.absolute_path(), unit))
+            .collect()
+    }
+
+    /// Like [`Self::source_units`], with the embedded source text attached to each unit.
+    pub fn into_source_units(self) -> BTreeMap<String, SourceUnit> {
+        let Self { input, output, .. } = self;
+
+        output
+            .into_source_units()
+            .into_iter()
+            .map(|(path, mut unit)| {
+                if let Some(content) = input.sources().get(&path).and_then(|s| s.content()) {
+                    unit.set_source(content.to_owned());
+                }
+                (unit.absolute_path().to_owned(), unit)
+            })
+            .collect()
+    }
+
+    fn record_compiler_version(mut self) -> Self {
+        self.output.set_compiler_version(&self.solc_long_version);
+        self
+    }
+
+    /// Source text passed to the compiler for `path`, if it was embedded in the input.
+    pub fn source_content(&self, path: &str) -> Option<&str> {
+        self.input
+            .sources()
+            .get(path)
+            .and_then(|source| source.content())
+    }
+}
+
+fn normalize_output(build_info: &mut serde_json::Value) {
+    if let Some(output) = build_info.get_mut("output") {
+        ast_normalize::normalize_solc_output(output);
+    }
+}
@@ -1,5 +1,18 @@
+mod contract;
+mod diagnostic;
 mod foundry;
-mod solc;
+mod hardhat;
+pub mod solc;
+mod standard_json;
+mod workspace;
 
+pub use contract::{
+    AbiItem, AbiItemKind, AbiParam, ByteRange, Bytecode, Contract, Evm, Metadata, MetadataCompiler,
+    MetadataSource, StorageLayout, StorageSlot, StorageType,
+};
+pub use diagnostic::{DiagnosticLocation, Severity, SolcDiagnostic};
 pub use foundry::FoundryOutput;
-pub use solc::SolcOutput;
+pub use hardhat::HardhatBuildInfo;
+pub use solc::{SolcConfig, SolcOutput, SolcSource, compile, compile_input};
+pub use standard_json::{OptimizerSettings, Settings, SourceInput, StandardJsonInput};
+pub use workspace::Workspace;
@@ -1,24 +1,309 @@
-use std::collections::HashMap;
+use std::{
+    collections::BTreeMap,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
 
-use serde::Deserialize;
+use getters::Getters;
+use serde::{Deserialize, Deserializer, de::Error as _};
 
-use crate::ast_specs::SourceUnit;
+use crate::{
+    AstParserResult, ErrorKind, ast_normalize, ast_specs::SourceUnit, utils::json_locator,
+};
 
-#[derive(Deserialize, Debug)]
+use super::{
+    contract::{CombinedContract, Contract},
+    diagnostic::SolcDiagnostic,
+    standard_json::{Settings, StandardJsonInput},
+};
+
+/// Output of solc, either standard-JSON or `--combined-json`.
+///
+/// Every map is ordered by path so iteration is deterministic.
+#[derive(Deserialize, Debug, Default, Getters)]
 pub struct SolcOutput {
-    // contracts: HashMap<String, serde_json::Value>,
-    // #[serde(rename = "sourceList")]
-    // source_list: Vec<String>,
-    sources: HashMap<String, HashMap<String, SourceUnit>>,
-    // version: String,
+    /// `contracts[path][name]`; combined-json `path:Name` keys are split accordingly.
+    #[serde(default, deserialize_with = "nested_or_combined")]
+    contracts: BTreeMap<String, BTreeMap<String, Contract>>,
+    #[serde(rename = "sourceList", default)]
+    #[return_type = "&[String]"]
+    source_list: Vec<String>,
+    #[serde(default)]
+    sources: BTreeMap<String, SolcSource>,
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    version: Option<String>,
+    /// Errors, warnings and infos reported by the compiler.
+    #[serde(default)]
+    #[return_type = "&[SolcDiagnostic]"]
+    errors: Vec<SolcDiagnostic>,
+}
+
+#[derive(Deserialize, Debug, Getters)]
+pub struct SolcSource {
+    #[copy]
+    id: Option<isize>,
+    #[serde(alias = "AST")]
+    ast: SourceUnit,
 }
 
 impl SolcOutput {
-    pub fn ast(&self) -> &SourceUnit {
-        let middle = self
-            .sources
-            .get(self.sources.keys().last().unwrap().as_str())
-            .unwrap();
-        middle.get(middle.keys().last().unwrap().as_str()).unwrap()
+    pub fn from_reader<R: Read>(reader: R) -> AstParserResult<Self> {
+        json_locator::from_reader_normalized(reader, ast_normalize::normalize_solc_output)
+            .map(Self::record_compiler_version)
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P) -> AstParserResult<Self> {
+        json_locator::from_path_normalized(path, ast_normalize::normalize_solc_output)
+            .map(Self::record_compiler_version)
+    }
+
+    /// Records `version` as the output's and every unit's compiler version.
+    pub(super) fn set_compiler_version(&mut self, version: &str) {
+        self.version = Some(version.to_owned());
+        for source in self.sources.values_mut() {
+            source.ast.set_compiler_version(Some(version.to_owned()));
+        }
+    }
+
+    fn record_compiler_version(mut self) -> Self {
+        if let Some(version) = self.version.clone() {
+            self.set_compiler_version(&version);
+        }
+        self
+    }
+
+    /// Last source unit in path order, for single-file outputs. `None` when no source was
+    /// compiled.
+    pub fn ast(&self) -> Option<&SourceUnit> {
+        self.sources.values().last().map(|source| &source.ast)
+    }
+
+    /// All source units keyed by path.
+    pub fn source_units(&self) -> BTreeMap<&str, &SourceUnit> {
+        self.sources
+            .iter()
+            .map(|(path, source)| (path.as_str(), &source.ast))
+            .collect()
+    }
+
+    pub fn into_source_units(self) -> BTreeMap<String, SourceUnit> {
+        self.sources
+            .into_iter()
+            .map(|(path, source)| (path, source.ast))
+            .collect()
+    }
+
+    pub fn source_unit(&self, path: &str) -> Option<&SourceUnit> {
+        self.sources.get(path).map(|source| &source.ast)
+    }
+
+    pub fn contract(&self, path: &str, name: &str) -> Option<&Contract> {
+        self.contracts.get(path)?.get(name)
+    }
+
+    /// Compiler output of the contracts declared in `path`, keyed by name.
+    pub fn contracts_in(&self, path: &str) -> Option<&BTreeMap<String, Contract>> {
+        self.contracts.get(path)
+    }
+
+    /// `compiler.version` in the metadata of the first contract that has it.
+    fn metadata_compiler_version(&self) -> Option<String> {
+        self.contracts
+            .values()
+            .flat_map(BTreeMap::values)
+            .find_map(|contract| Some(contract.metadata()?.compiler().version().to_owned()))
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors.iter().any(SolcDiagnostic::is_error)
+    }
+}
+
+/// How [`compile`] invokes solc.
+#[derive(Debug, Clone, Getters)]
+pub struct SolcConfig {
+    /// Binary to run, looked up in `PATH` unless it is a path.
+    #[return_type = "&Path"]
+    solc: PathBuf,
+    settings: Settings,
+    #[use_as_deref]
+    #[return_type = "Option<&Path>"]
+    base_path: Option<PathBuf>,
+    #[return_type = "&[PathBuf]"]
+    include_paths: Vec<PathBuf>,
+    #[return_type = "&[PathBuf]"]
+    allow_paths: Vec<PathBuf>,
+}
+
+impl Default for SolcConfig {
+    fn default() -> Self {
+        Self::new("solc")
+    }
+}
+
+impl SolcConfig {
+    pub fn new<P: Into<PathBuf>>(solc: P) -> Self {
+        Self {
+            solc: solc.into(),
+            settings: Settings::new(),
+            base_path: None,
+            include_paths: vec![],
+            allow_paths: vec![],
+        }
     }
+
+    pub fn with_settings(mut self, settings: Settings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Shorthand for replacing the remappings of the current settings.
+    pub fn with_remappings<I: IntoIterator<Item = S>, S: Into<String>>(
+        mut self,
+        remappings: I,
+    ) -> Self {
+        self.settings = self.settings.with_remappings(remappings);
+        self
+    }
+
+    pub fn with_base_path<P: Into<PathBuf>>(mut self, base_path: P) -> Self {
+        self.base_path = Some(base_path.into());
+        self
+    }
+
+    pub fn with_include_path<P: Into<PathBuf>>(mut self, include_path: P) -> Self {
+        self.include_paths.push(include_path.into());
+        self
+    }
+
+    pub fn with_allow_path<P: Into<PathBuf>>(mut self, allow_path: P) -> Self {
+        self.allow_paths.push(allow_path.into());
+        self
+    }
+
+    fn command(&self) -> Command {
+        let mut command = Command::new(&self.solc);
+        command.arg("--standard-json");
+        if let Some(base_path) = &self.base_path {
+            command.arg("--base-path").arg(base_path);
+        }
+        for include_path in &self.include_paths {
+            command.arg("--include-path").arg(include_path);
+        }
+        if !self.allow_paths.is_empty() {
+            let allow_paths = self
+                .allow_paths
+                .iter()
+                .map(|path| path.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(",");
+            command.arg("--allow-paths").arg(allow_paths);
+        }
+        command
+    }
+}
+
+/// Compiles `paths` with a local solc in standard-JSON mode.
+///
+/// Sources are keyed by the paths as given. Imports are resolved by solc
+/// itself, relative to the configured base and include paths. Fails with
+/// [`ErrorKind::Compilation`] if solc reports any error; warnings stay in
+/// [`SolcOutput::errors`].
+///
+/// solc runs once. The compiler version comes from the contract metadata, which the default
+/// [`Settings`] select; without it the units get no version.
+pub fn compile<P: AsRef<Path>>(
+    paths: impl IntoIterator<Item = P>,
+    config: &SolcConfig,
+) -> AstParserResult<SolcOutput> {
+    let input = StandardJsonInput::from_paths(paths, config.settings.clone())?;
+    compile_input(&input, config)
+}
+
+/// Runs solc on a prepared standard-JSON input.
+pub fn compile_input(
+    input: &StandardJsonInput,
+    config: &SolcConfig,
+) -> AstParserResult<SolcOutput> {
+    let input = serde_json::to_vec(input).expect("standard-JSON input is always serializable");
+
+    let mut child = config
+        .command()
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    // Written from another thread so a large input can't deadlock against a full stdout pipe
+    let output = std::thread::scope(|scope| {
+        scope.spawn(move || {
+            // solc reports its own errors if the input is cut short
+            let _ = stdin.write_all(&input);
+        });
+        child.wait_with_output()
+    })?;
+
+    if !output.status.success() && output.stdout.is_empty() {
+        return Err(ErrorKind::Solc {
+            status: output.status.code(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        }
+        .into());
+    }
+
+    // The rest of the output may be incomplete when compilation failed
+    #[derive(Deserialize)]
+    struct Errors {
+        #[serde(default)]
+        errors: Vec<SolcDiagnostic>,
+    }
+    let Errors { errors } = json_locator::from_slice(&output.stdout)?;
+    if errors.iter().any(SolcDiagnostic::is_error) {
+        return Err(ErrorKind::Compilation(errors).into());
+    }
+
+    let mut output: SolcOutput =
+        json_locator::from_slice_normalized(&output.stdout, ast_normalize::normalize_solc_output)?;
+    if let Some(version) = output.metadata_compiler_version() {
+        output.set_compiler_version(&version);
+    }
+    Ok(output)
+}
+
+fn nested_or_combined<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<String, BTreeMap<String, Contract>>, D::Error> {
+    let raw = BTreeMap::<String, serde_json::Value>::deserialize(deserializer)?;
+    let mut contracts: BTreeMap<String, BTreeMap<String, Contract>> = BTreeMap::new();
+
+    for (key, value) in raw {
+        match key.rsplit_once(':').filter(|(_, name)| is_identifier(name)) {
+            Some((path, name)) => {
+                let contract: CombinedContract =
+                    serde_json::from_value(value).map_err(D::Error::custom)?;
+                contracts
+                    .entry(path.to_owned())
+                    .or_default()
+                    .insert(name.to_owned(), contract.into());
+            }
+            None => {
+                let by_name: BTreeMap<String, Contract> =
+                    serde_json::from_value(value).map_err(D::Error::custom)?;
+                contracts.entry(key).or_default().extend(by_name);
+            }
+        }
+    }
+
+    Ok(contracts)
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
 }
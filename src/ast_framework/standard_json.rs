@@ -0,0 +1,178 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use getters::Getters;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::AstParserResult;
+
+/// Compiler input in the solc standard-JSON format.
+#[derive(Serialize, Deserialize, Debug, Clone, Getters, PartialEq)]
+pub struct StandardJsonInput {
+    #[return_type = "&str"]
+    language: String,
+    sources: BTreeMap<String, SourceInput>,
+    settings: Settings,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Getters, PartialEq, Eq)]
+pub struct SourceInput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    keccak256: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[use_as_deref]
+    #[return_type = "Option<&[String]>"]
+    urls: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Getters, PartialEq)]
+pub struct Settings {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[return_type = "&[String]"]
+    remappings: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[use_as_ref]
+    #[return_type = "Option<&OptimizerSettings>"]
+    optimizer: Option<OptimizerSettings>,
+    #[serde(rename = "evmVersion", skip_serializing_if = "Option::is_none")]
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    evm_version: Option<String>,
+    #[serde(rename = "viaIR", skip_serializing_if = "Option::is_none")]
+    #[copy]
+    via_ir: Option<bool>,
+    #[serde(
+        rename = "outputSelection",
+        default,
+        skip_serializing_if = "serde_json::Value::is_null"
+    )]
+    output_selection: serde_json::Value,
+    /// Every other setting (`metadata`, `libraries`, `debug`, ...) kept as is.
+    #[serde(flatten)]
+    other: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, Getters, PartialEq, Eq)]
+pub struct OptimizerSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[copy]
+    enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[copy]
+    runs: Option<usize>,
+}
+
+impl StandardJsonInput {
+    pub fn new(sources: BTreeMap<String, SourceInput>, settings: Settings) -> Self {
+        Self {
+            language: "Solidity".to_owned(),
+            sources,
+            settings,
+        }
+    }
+
+    /// Reads every file in `paths`, keyed by the path as given.
+    pub fn from_paths<P: AsRef<Path>>(
+        paths: impl IntoIterator<Item = P>,
+        settings: Settings,
+    ) -> AstParserResult<Self> {
+        let sources = paths
+            .into_iter()
+            .map(|path| {
+                let path = path.as_ref();
+                let content = fs::read_to_string(path)?;
+                Ok((
+                    path.to_string_lossy().into_owned(),
+                    SourceInput::new(content),
+                ))
+            })
+            .collect::<AstParserResult<_>>()?;
+
+        Ok(Self::new(sources, settings))
+    }
+}
+
+impl SourceInput {
+    pub fn new(content: String) -> Self {
+        Self {
+            content: Some(content),
+            ..Default::default()
+        }
+    }
+}
+
+impl Settings {
+    /// Settings selecting the AST of every source and the typed contract output.
+    pub fn new() -> Self {
+        Self {
+            output_selection: json!({
+                "*": {
+                    "": ["ast"],
+                    "*": [
+                        "abi",
+                        "evm.bytecode",
+                        "evm.deployedBytecode",
+                        "evm.methodIdentifiers",
+                        "storageLayout",
+                        "metadata"
+                    ]
+                }
+            }),
+            remappings: vec![],
+            optimizer: None,
+            evm_version: None,
+            via_ir: None,
+            other: serde_json::Map::new(),
+        }
+    }
+
+    pub fn with_remappings<I: IntoIterator<Item = S>, S: Into<String>>(
+        mut self,
+        remappings: I,
+    ) -> Self {
+        self.remappings = remappings.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_optimizer(mut self, optimizer: OptimizerSettings) -> Self {
+        self.optimizer = Some(optimizer);
+        self
+    }
+
+    pub fn with_evm_version<S: Into<String>>(mut self, evm_version: S) -> Self {
+        self.evm_version = Some(evm_version.into());
+        self
+    }
+
+    pub fn with_via_ir(mut self, via_ir: bool) -> Self {
+        self.via_ir = Some(via_ir);
+        self
+    }
+
+    pub fn with_output_selection(mut self, output_selection: serde_json::Value) -> Self {
+        self.output_selection = output_selection;
+        self
+    }
+}
+
+/// Same as [`Settings::new`], selecting the AST.
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OptimizerSettings {
+    pub fn new(enabled: bool, runs: usize) -> Self {
+        Self {
+            enabled: Some(enabled),
+            runs: Some(runs),
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use std::{
+    collections::{BTreeMap, HashMap, hash_map::Entry},
+    fs, io,
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{
+    AstParserResult, ast_normalize,
+    ast_source::{LineIndex, SourceProvider},
+    ast_specs::{NodeType, NodeTypeInternal, NodeTypeInternalRef, SourceUnit},
+    ast_visitor::{AstRefVisitor, AstVisitor, Visit, Walk},
+    utils::json_locator,
+};
+
+use super::{HardhatBuildInfo, SolcOutput, foundry::compiler_version};
+
+type UnitKey = (String, isize);
+
+/// Set of deduplicated source units with a node id index spanning all of them.
+///
+/// Foundry writes the AST of a source file into every artifact of every
+/// contract that depends on it; the workspace keeps one copy per
+/// `(absolutePath, id)` pair.
+///
+/// Units of separate compilations may reuse node ids. Such ids are reported by
+/// [`Workspace::conflicts`] and resolve to no unit in [`Workspace::unit_of`].
+#[derive(Debug, Default, Clone)]
+pub struct Workspace {
+    units: BTreeMap<UnitKey, SourceUnit>,
+    node_index: HashMap<isize, UnitKey>,
+    /// Units other than the indexed one that contain the id.
+    conflicts: BTreeMap<isize, Vec<UnitKey>>,
+}
+
+#[derive(Deserialize)]
+struct FoundryArtifact {
+    ast: Option<SourceUnit>,
+    #[serde(rename = "metadata", default, deserialize_with = "compiler_version")]
+    compiler_version: Option<String>,
+}
+
+impl Workspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Walks a Foundry `out/` directory, skipping `build-info` and artifacts without an AST.
+    pub fn from_foundry_out<P: AsRef<Path>>(out: P) -> AstParserResult<Self> {
+        let mut artifacts = vec![];
+        collect_artifacts(out.as_ref(), &mut artifacts)?;
+        artifacts.sort();
+
+        let mut workspace = Self::new();
+        for artifact in artifacts {
+            let artifact: FoundryArtifact =
+                json_locator::from_path_normalized(artifact, ast_normalize::normalize_artifact)?;
+            if let Some(mut ast) = artifact.ast {
+                ast.set_compiler_version(artifact.compiler_version);
+                workspace.add_unit(ast);
+            }
+        }
+
+        Ok(workspace)
+    }
+
+    /// Loads every build-info file of a Hardhat `artifacts/build-info` directory.
+    pub fn from_hardhat_build_info<P: AsRef<Path>>(dir: P) -> AstParserResult<Self> {
+        Ok(HardhatBuildInfo::from_dir(dir)?
+            .into_iter()
+            .flat_map(|build_info| build_info.into_source_units().into_values())
+            .collect())
+    }
+
+    pub fn from_solc_output(output: SolcOutput) -> Self {
+        output.into_source_units().into_values().collect()
+    }
+
+    /// Adds `unit` unless a unit with the same `absolutePath` and `id` is already present.
+    /// Returns whether the unit was added.
+    pub fn add_unit(&mut self, unit: SourceUnit) -> bool {
+        let key = (unit.absolute_path().to_owned(), unit.id());
+        if self.units.contains_key(&key) {
+            return false;
+        }
+
+        for id in unit.childrens_id() {
+            match self.node_index.entry(id) {
+                Entry::Vacant(entry) => {
+                    entry.insert(key.clone());
+                }
+                Entry::Occupied(entry) if *entry.get() != key => {
+                    self.conflicts.entry(id).or_default().push(key.clone());
+                }
+                Entry::Occupied(_) => {}
+            }
+        }
+        self.units.insert(key, unit);
+
+        true
+    }
+
+    /// Source units ordered by `absolutePath`, then `id`.
+    pub fn units(&self) -> impl Iterator<Item = &SourceUnit> {
+        self.units.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.units.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.units.is_empty()
+    }
+
+    /// First unit compiled from `absolute_path`.
+    pub fn unit(&self, absolute_path: &str) -> Option<&SourceUnit> {
+        self.units
+            .range((absolute_path.to_owned(), isize::MIN)..)
+            .next()
+            .filter(|((path, _), _)| path == absolute_path)
+            .map(|(_, unit)| unit)
+    }
+
+    /// Unit that contains the node with `id`, `None` if several units do.
+    pub fn unit_of(&self, id: isize) -> Option<&SourceUnit> {
+        if self.conflicts.contains_key(&id) {
+            return None;
+        }
+        self.node_index.get(&id).and_then(|key| self.units.get(key))
+    }
+
+    /// Every unit that contains a node with `id`, in the order they were added.
+    pub fn units_of(&self, id: isize) -> impl Iterator<Item = &SourceUnit> {
+        self.node_index
+            .get(&id)
+            .into_iter()
+            .chain(self.conflicts.get(&id).into_iter().flatten())
+            .filter_map(|key| self.units.get(key))
+    }
+
+    /// Ids found in more than one unit, in ascending order.
+    pub fn conflicts(&self) -> impl Iterator<Item = isize> + '_ {
+        self.conflicts.keys().copied()
+    }
+
+    /// Unit with a source compiled from the file at `file_index`, if only one file is.
+    fn unit_of_file(&self, file_index: usize) -> Option<&SourceUnit> {
+        let units: Vec<_> = self
+            .units()
+            .filter(|unit| unit.file_index() == Some(file_index))
+            .collect();
+        let first = units.first()?;
+        if units
+            .iter()
+            .any(|unit| unit.absolute_path() != first.absolute_path())
+        {
+            return None;
+        }
+        units.into_iter().find(|unit| unit.source().is_some())
+    }
+
+    /// Attaches `source` to every unit compiled from `absolute_path`.
+    pub fn set_source(&mut self, absolute_path: &str, source: String) {
+        for ((path, _), unit) in self.units.iter_mut() {
+            if path == absolute_path {
+                unit.set_source(source.clone());
+            }
+        }
+    }
+
+    /// Reads the content of every unit from disk, resolving `absolutePath` against `root`.
+    pub fn load_sources<P: AsRef<Path>>(&mut self, root: P) -> AstParserResult<()> {
+        for unit in self.units.values_mut() {
+            unit.load_source(root.as_ref())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Separate compilations may give different files the same `fileIndex`, whose source is then
+/// `None` like the ids of [`Workspace::conflicts`].
+impl SourceProvider for Workspace {
+    fn source(&self, file_index: usize) -> Option<&str> {
+        self.unit_of_file(file_index)?.source()
+    }
+
+    fn line_index(&self, file_index: usize) -> Option<LineIndex<'_>> {
+        self.unit_of_file(file_index)?.line_index(file_index)
+    }
+}
+
+impl FromIterator<SourceUnit> for Workspace {
+    fn from_iter<T: IntoIterator<Item = SourceUnit>>(iter: T) -> Self {
+        let mut workspace = Self::new();
+        workspace.extend(iter);
+        workspace
+    }
+}
+
+impl Extend<SourceUnit> for Workspace {
+    fn extend<T: IntoIterator<Item = SourceUnit>>(&mut self, iter: T) {
+        for unit in iter {
+            self.add_unit(unit);
+        }
+    }
+}
+
+fn collect_artifacts(dir: &Path, artifacts: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if path.file_name().is_some_and(|name| name != "build-info") {
+                collect_artifacts(&path, artifacts)?;
+            }
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            artifacts.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+impl<'ast> Walk<'ast> for Workspace {
+    fn walk<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) -> ControlFlow<()> {
+        self.units().try_for_each(|unit| unit.walk(visitor))
+    }
+}
+
+impl AstVisitor for Workspace {
+    fn filter_by_node_type<N: Into<NodeType>>(&self, node_type: N) -> Vec<NodeTypeInternal> {
+        let node_type: NodeType = node_type.into();
+
+        self.units()
+            .flat_map(|unit| unit.filter_by_node_type(node_type))
+            .collect()
+    }
+
+    fn filter_ref_by_node_type<'a, N: Into<NodeType>>(
+        &'a self,
+        node_type: N,
+    ) -> Vec<NodeTypeInternalRef<'a>> {
+        self.refs_by_node_type(node_type)
+    }
+
+    fn filter_by_reference_id(&self, id: isize) -> Vec<NodeTypeInternal> {
+        self.units()
+            .flat_map(|unit| unit.filter_by_reference_id(id))
+            .collect()
+    }
+
+    fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {
+        self.refs_by_reference_id(id)
+    }
+
+    fn filter_by_id(&self, id: isize) -> Option<NodeTypeInternal> {
+        self.unit_of(id)?.filter_by_id(id)
+    }
+
+    fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {
+        self.unit_of(id)?.ref_by_id(id)
+    }
+
+    fn childrens_id(&self) -> Vec<isize> {
+        self.units().flat_map(|unit| unit.childrens_id()).collect()
+    }
+
+    fn references(&self) -> Vec<isize> {
+        self.units().flat_map(|unit| unit.references()).collect()
+    }
+
+    fn step_back<'a, 'b, N: Into<NodeTypeInternalRef<'a>> + Copy>(
+        &'b self,
+        target: N,
+    ) -> Option<NodeTypeInternalRef<'b>> {
+        self.units().find_map(|unit| unit.step_back(target))
+    }
+
+    fn step_back_until<'a, 'b, N: Into<NodeTypeInternalRef<'a>> + Copy>(
+        &'b self,
+        from: N,
+        to: NodeType,
+    ) -> Option<NodeTypeInternalRef<'b>> {
+        self.units().find_map(|unit| unit.step_back_until(from, to))
+    }
+
+    fn is_node<'a, N: Into<NodeTypeInternalRef<'a>> + Copy>(&self, target: N) -> bool {
+        self.units().any(|unit| unit.is_node(target))
+    }
+}
@@ -0,0 +1,304 @@
+//! Fields that older compact ASTs lack or spell differently, filled in the way
+//! current solc reports them.
+
+use serde_json::{Map, Value, json};
+
+use super::SyntheticIds;
+
+const UNKNOWN_SRC: &str = "-1:-1:-1";
+
+pub(super) fn fix(node: &mut Map<String, Value>, ids: &mut SyntheticIds) {
+    let Some(node_type) = node.get("nodeType").and_then(Value::as_str) else {
+        return;
+    };
+
+    match node_type {
+        "SourceUnit" => {
+            default(node, "exportedSymbols", json!({}));
+            default(node, "nodes", json!([]));
+        }
+        "ImportDirective" => {
+            if let Some(source_unit) = node.remove("SourceUnit") {
+                default(node, "sourceUnit", source_unit);
+            }
+            default(node, "unitAlias", json!(""));
+            default(node, "symbolAliases", json!([]));
+            fix_symbol_aliases(node, ids);
+        }
+        "ContractDefinition" => {
+            if !node.contains_key("contractKind") {
+                let kind = match node.get("isLibrary").and_then(Value::as_bool) {
+                    Some(true) => "library",
+                    _ => "contract",
+                };
+                node.insert("contractKind".to_owned(), kind.into());
+            }
+            let id = node.get("id").cloned().unwrap_or(Value::Null);
+            default(node, "linearizedBaseContracts", json!([id]));
+            default(node, "baseContracts", json!([]));
+            default(node, "contractDependencies", json!([]));
+            default(node, "fullyImplemented", json!(true));
+            default(node, "nodes", json!([]));
+            structured_documentation(node, ids);
+        }
+        "FunctionDefinition" => {
+            state_mutability(node);
+            if !node.contains_key("kind") {
+                let kind = if node.get("isConstructor").and_then(Value::as_bool) == Some(true) {
+                    "constructor"
+                } else if node.get("name").and_then(Value::as_str) == Some("") {
+                    "fallback"
+                } else {
+                    "function"
+                };
+                node.insert("kind".to_owned(), kind.into());
+            }
+            let implemented = node.get("body").is_some_and(|body| !body.is_null());
+            default(node, "implemented", json!(implemented));
+            default(node, "modifiers", json!([]));
+            // Public was the implicit default before 0.5
+            default(node, "visibility", json!("public"));
+            structured_documentation(node, ids);
+        }
+        "ModifierDefinition" => {
+            default(node, "visibility", json!("internal"));
+            structured_documentation(node, ids);
+        }
+        "EventDefinition" => {
+            default(node, "anonymous", json!(false));
+            structured_documentation(node, ids);
+        }
+        "VariableDeclaration" => {
+            let constant = node.get("constant").and_then(Value::as_bool);
+            let mutability = node.get("mutability").and_then(Value::as_str);
+            match (constant, mutability) {
+                (Some(constant), None) => {
+                    let mutability = if constant { "constant" } else { "mutable" };
+                    node.insert("mutability".to_owned(), mutability.into());
+                }
+                (None, mutability) => {
+                    let constant = mutability == Some("constant");
+                    node.insert("constant".to_owned(), constant.into());
+                }
+                _ => {}
+            }
+            default(node, "stateVariable", json!(false));
+            default(node, "storageLocation", json!("default"));
+            default(node, "visibility", json!("internal"));
+            type_descriptions(node);
+        }
+        "StructDefinition" => {
+            if let Some(name) = node.get("name").cloned() {
+                default(node, "canonicalName", name);
+            }
+            default(node, "visibility", json!("public"));
+            default(node, "members", json!([]));
+        }
+        "EnumDefinition" => default(node, "members", json!([])),
+        "ParameterList" => default(node, "parameters", json!([])),
+        "VariableDeclarationStatement" if !node.contains_key("assignments") => {
+            let assignments: Vec<Value> = node
+                .get("declarations")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .map(|declaration| declaration.get("id").cloned().unwrap_or(Value::Null))
+                .collect();
+            node.insert("assignments".to_owned(), assignments.into());
+        }
+        "InlineAssembly" => external_references(node),
+        "ElementaryTypeName" | "UserDefinedTypeName" | "Mapping" | "ArrayTypeName" => {
+            type_descriptions(node)
+        }
+        "FunctionTypeName" => {
+            state_mutability(node);
+            default(node, "visibility", json!("internal"));
+            type_descriptions(node);
+        }
+        "Identifier" => {
+            default(node, "overloadedDeclarations", json!([]));
+            type_descriptions(node);
+        }
+        "Literal" => {
+            if !node.contains_key("hexValue") {
+                let hex_value = node
+                    .get("value")
+                    .and_then(Value::as_str)
+                    .map(|value| {
+                        value
+                            .bytes()
+                            .map(|b| format!("{b:02x}"))
+                            .collect::<String>()
+                    })
+                    .unwrap_or_default();
+                node.insert("hexValue".to_owned(), hex_value.into());
+            }
+            expression(node);
+        }
+        "FunctionCall" => {
+            default(node, "kind", json!("functionCall"));
+            default(node, "names", json!([]));
+            expression(node);
+        }
+        "TupleExpression" => {
+            default(node, "isInlineArray", json!(false));
+            default(node, "components", json!([]));
+            expression(node);
+        }
+        "BinaryOperation" => {
+            if let Some(common_type) = node.get("typeDescriptions").cloned() {
+                default(node, "commonType", common_type);
+            }
+            expression(node);
+        }
+        "Assignment"
+        | "Conditional"
+        | "ElementaryTypeNameExpression"
+        | "FunctionCallOptions"
+        | "IndexAccess"
+        | "IndexRangeAccess"
+        | "MemberAccess"
+        | "NewExpression"
+        | "UnaryOperation" => expression(node),
+        _ => {}
+    }
+
+    if matches!(
+        node.get("nodeType").and_then(Value::as_str),
+        Some(
+            "ContractDefinition"
+                | "FunctionDefinition"
+                | "ModifierDefinition"
+                | "EventDefinition"
+                | "ErrorDefinition"
+                | "EnumDefinition"
+                | "EnumValue"
+                | "StructDefinition"
+                | "UserDefinedValueTypeDefinition"
+                | "VariableDeclaration"
+        )
+    ) {
+        default(node, "nameLocation", json!(UNKNOWN_SRC));
+    }
+}
+
+fn default(node: &mut Map<String, Value>, key: &str, value: Value) {
+    node.entry(key).or_insert(value);
+}
+
+fn expression(node: &mut Map<String, Value>) {
+    for flag in ["isConstant", "isLValue", "isPure", "lValueRequested"] {
+        default(node, flag, json!(false));
+    }
+    type_descriptions(node);
+}
+
+fn type_descriptions(node: &mut Map<String, Value>) {
+    default(
+        node,
+        "typeDescriptions",
+        json!({ "typeIdentifier": null, "typeString": null }),
+    );
+}
+
+/// `constant` and `payable` flags predate `stateMutability` (0.4.16).
+fn state_mutability(node: &mut Map<String, Value>) {
+    if node.contains_key("stateMutability") {
+        return;
+    }
+
+    let flag = |key| node.get(key).and_then(Value::as_bool) == Some(true);
+    let state_mutability = if flag("payable") {
+        "payable"
+    } else if flag("constant") {
+        "view"
+    } else {
+        "nonpayable"
+    };
+    node.insert("stateMutability".to_owned(), state_mutability.into());
+}
+
+/// Plain-string `documentation` predates `StructuredDocumentation` (0.6.3).
+fn structured_documentation(node: &mut Map<String, Value>, ids: &mut SyntheticIds) {
+    let Some(Value::String(text)) = node.get("documentation") else {
+        return;
+    };
+
+    let id = ids.id(node, 0);
+    let documentation = json!({
+        "id": id,
+        "nodeType": "StructuredDocumentation",
+        "src": UNKNOWN_SRC,
+        "text": text,
+    });
+    node.insert("documentation".to_owned(), documentation);
+}
+
+/// `foreign` was the referenced declaration id before it became an `Identifier`.
+fn fix_symbol_aliases(node: &mut Map<String, Value>, ids: &mut SyntheticIds) {
+    let Some(Value::Array(aliases)) = node.get("symbolAliases") else {
+        return;
+    };
+
+    let identifiers: Vec<Option<Value>> = aliases
+        .iter()
+        .enumerate()
+        .map(|(i, alias)| {
+            let declaration = alias.get("foreign")?.as_i64()?;
+            Some(json!({
+                "id": ids.id(node, i),
+                "name": "",
+                "nodeType": "Identifier",
+                "overloadedDeclarations": [],
+                "referencedDeclaration": declaration,
+                "src": node.get("src").cloned().unwrap_or(json!(UNKNOWN_SRC)),
+                "typeDescriptions": { "typeIdentifier": null, "typeString": null },
+            }))
+        })
+        .collect();
+
+    if let Some(Value::Array(aliases)) = node.get_mut("symbolAliases") {
+        for (alias, identifier) in aliases.iter_mut().zip(identifiers) {
+            if let (Some(alias), Some(identifier)) = (alias.as_object_mut(), identifier) {
+                alias.insert("foreign".to_owned(), identifier);
+            }
+        }
+    }
+}
+
+/// Flattens the `[{ "name": reference }]` maps of solc before 0.6 into a list of
+/// references, deriving `suffix` from the `isSlot`/`isOffset` flags.
+fn external_references(node: &mut Map<String, Value>) {
+    let references = match node.remove("externalReferences") {
+        Some(Value::Array(references)) => references,
+        _ => vec![],
+    };
+
+    let references: Vec<Value> = references
+        .into_iter()
+        .flat_map(|reference| match reference {
+            Value::Object(map) if !map.contains_key("declaration") => {
+                map.into_iter().map(|(_, reference)| reference).collect()
+            }
+            reference => vec![reference],
+        })
+        .map(|mut reference| {
+            if let Some(reference) = reference.as_object_mut() {
+                let flag = |key| reference.get(key).and_then(Value::as_bool) == Some(true);
+                let suffix = if flag("isSlot") {
+                    json!("slot")
+                } else if flag("isOffset") {
+                    json!("offset")
+                } else {
+                    Value::Null
+                };
+                reference.entry("suffix").or_insert(suffix);
+                reference.entry("isSlot").or_insert(json!(false));
+                reference.entry("isOffset").or_insert(json!(false));
+            }
+            reference
+        })
+        .collect();
+
+    node.insert("externalReferences".to_owned(), references.into());
+}
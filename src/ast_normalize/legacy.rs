@@ -0,0 +1,254 @@
+//! Legacy `--ast-json` layout: `{ name, id, src, attributes, children }`.
+//!
+//! solc produced it from the same attribute list as the compact AST, except that
+//! every node-valued attribute was moved into `children` in declaration order,
+//! while `null`s and empty arrays stayed in `attributes`. Children are therefore
+//! handed back to the compact fields in that order, skipping the fields still
+//! present in `attributes`.
+
+use serde_json::{Map, Value, json};
+
+enum Slot {
+    /// At most one child, if it satisfies the predicate.
+    One(fn(&str) -> bool),
+    /// Consecutive children satisfying the predicate.
+    Many(fn(&str) -> bool),
+    /// Every remaining child.
+    Rest,
+}
+
+use Slot::*;
+
+fn any(_: &str) -> bool {
+    true
+}
+
+fn type_name(node_type: &str) -> bool {
+    matches!(
+        node_type,
+        "ElementaryTypeName"
+            | "UserDefinedTypeName"
+            | "Mapping"
+            | "ArrayTypeName"
+            | "FunctionTypeName"
+    )
+}
+
+fn parameter_list(node_type: &str) -> bool {
+    node_type == "ParameterList"
+}
+
+fn block(node_type: &str) -> bool {
+    node_type == "Block"
+}
+
+fn identifier(node_type: &str) -> bool {
+    node_type == "Identifier"
+}
+
+fn inheritance_specifier(node_type: &str) -> bool {
+    node_type == "InheritanceSpecifier"
+}
+
+fn modifier_invocation(node_type: &str) -> bool {
+    node_type == "ModifierInvocation"
+}
+
+fn variable_declaration(node_type: &str) -> bool {
+    // Skipped tuple components are `null`
+    matches!(node_type, "VariableDeclaration" | "")
+}
+
+fn layout(node_type: &str) -> &'static [(&'static str, Slot)] {
+    match node_type {
+        "SourceUnit" => &[("nodes", Rest)],
+        "ContractDefinition" => &[
+            ("baseContracts", Many(inheritance_specifier)),
+            ("nodes", Rest),
+        ],
+        "InheritanceSpecifier" => &[("baseName", One(any)), ("arguments", Rest)],
+        "UsingForDirective" => &[("libraryName", One(any)), ("typeName", One(type_name))],
+        "StructDefinition" | "EnumDefinition" => &[("members", Rest)],
+        "ParameterList" => &[("parameters", Rest)],
+        "FunctionDefinition" => &[
+            ("parameters", One(parameter_list)),
+            ("returnParameters", One(parameter_list)),
+            ("modifiers", Many(modifier_invocation)),
+            ("body", One(block)),
+        ],
+        "VariableDeclaration" => &[("typeName", One(type_name)), ("value", One(any))],
+        "ModifierDefinition" => &[("parameters", One(parameter_list)), ("body", One(block))],
+        "ModifierInvocation" => &[("modifierName", One(identifier)), ("arguments", Rest)],
+        "EventDefinition" => &[("parameters", One(parameter_list))],
+        "FunctionTypeName" => &[
+            ("parameterTypes", One(parameter_list)),
+            ("returnParameterTypes", One(parameter_list)),
+        ],
+        "Mapping" => &[("keyType", One(type_name)), ("valueType", One(type_name))],
+        "ArrayTypeName" => &[("baseType", One(type_name)), ("length", One(any))],
+        "OverrideSpecifier" => &[("overrides", Rest)],
+        "Block" => &[("statements", Rest)],
+        "IfStatement" => &[
+            ("condition", One(any)),
+            ("trueBody", One(any)),
+            ("falseBody", One(any)),
+        ],
+        "WhileStatement" | "DoWhileStatement" => &[("condition", One(any)), ("body", One(any))],
+        "ForStatement" => &[
+            ("initializationExpression", One(any)),
+            ("condition", One(any)),
+            ("loopExpression", One(any)),
+            ("body", One(any)),
+        ],
+        "Return" => &[("expression", One(any))],
+        // `throw;` of solc < 0.5, a leaf
+        "Throw" => &[],
+        "EmitStatement" => &[("eventCall", One(any))],
+        "ExpressionStatement" => &[("expression", One(any))],
+        "VariableDeclarationStatement" => &[
+            ("declarations", Many(variable_declaration)),
+            ("initialValue", One(any)),
+        ],
+        "TryStatement" => &[("externalCall", One(any)), ("clauses", Rest)],
+        "TryCatchClause" => &[("parameters", One(parameter_list)), ("block", One(block))],
+        "Conditional" => &[
+            ("condition", One(any)),
+            ("trueExpression", One(any)),
+            ("falseExpression", One(any)),
+        ],
+        "Assignment" => &[("leftHandSide", One(any)), ("rightHandSide", One(any))],
+        "TupleExpression" => &[("components", Rest)],
+        "UnaryOperation" => &[("subExpression", One(any))],
+        "BinaryOperation" => &[("leftExpression", One(any)), ("rightExpression", One(any))],
+        "FunctionCall" => &[("expression", One(any)), ("arguments", Rest)],
+        "FunctionCallOptions" => &[("expression", One(any)), ("options", Rest)],
+        "NewExpression" => &[("typeName", One(type_name))],
+        "MemberAccess" => &[("expression", One(any))],
+        "IndexAccess" => &[("baseExpression", One(any)), ("indexExpression", One(any))],
+        "IndexRangeAccess" => &[
+            ("baseExpression", One(any)),
+            ("startExpression", One(any)),
+            ("endExpression", One(any)),
+        ],
+        _ => &[],
+    }
+}
+
+pub(super) fn is_legacy_node(node: &Map<String, Value>) -> bool {
+    !node.contains_key("nodeType")
+        && node.get("name").is_some_and(Value::is_string)
+        && node.contains_key("id")
+        && node.contains_key("src")
+}
+
+/// Rewrites one legacy node into the compact layout. Children are left legacy.
+pub(super) fn convert(node: &mut Map<String, Value>) {
+    let Some(Value::String(node_type)) = node.remove("name") else {
+        return;
+    };
+    let mut attributes = match node.remove("attributes") {
+        Some(Value::Object(attributes)) => attributes,
+        _ => Map::new(),
+    };
+    let mut children = match node.remove("children") {
+        Some(Value::Array(children)) => children.into_iter().peekable(),
+        _ => vec![].into_iter().peekable(),
+    };
+
+    rename_attributes(&node_type, &mut attributes);
+
+    for (field, slot) in layout(&node_type) {
+        if let Some(value) = attributes.remove(*field) {
+            node.insert(field.to_string(), value);
+            continue;
+        }
+
+        let value = match slot {
+            One(accepts) => children
+                .next_if(|child| accepts(child_type(child)))
+                .unwrap_or(Value::Null),
+            Many(accepts) => Value::Array(
+                std::iter::from_fn(|| children.next_if(|child| accepts(child_type(child))))
+                    .collect(),
+            ),
+            Rest => Value::Array(children.by_ref().collect()),
+        };
+        node.insert(field.to_string(), value);
+    }
+
+    node.insert("nodeType".to_owned(), Value::String(node_type));
+    node.extend(attributes);
+
+    let rest: Vec<Value> = children.collect();
+    if !rest.is_empty() {
+        node.insert("children".to_owned(), Value::Array(rest));
+    }
+}
+
+fn child_type(child: &Value) -> &str {
+    child
+        .get("name")
+        .or_else(|| child.get("nodeType"))
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+}
+
+/// Attributes that were spelled differently in the legacy output.
+fn rename_attributes(node_type: &str, attributes: &mut Map<String, Value>) {
+    // Empty arrays were written as `[null]`
+    for value in attributes.values_mut() {
+        if matches!(value.as_array().map(Vec::as_slice), Some([Value::Null])) {
+            *value = Value::Array(vec![]);
+        }
+    }
+
+    if let Some(Value::String(type_string)) = attributes.remove("type") {
+        attributes.insert(
+            "typeDescriptions".to_owned(),
+            json!({ "typeIdentifier": null, "typeString": type_string }),
+        );
+    }
+
+    let rename = |attributes: &mut Map<String, Value>, from: &str, to: &str| {
+        if let Some(value) = attributes.remove(from) {
+            attributes.entry(to).or_insert(value);
+        }
+    };
+
+    match node_type {
+        "Identifier" => rename(attributes, "value", "name"),
+        "ElementaryTypeNameExpression" => rename(attributes, "value", "typeName"),
+        "MemberAccess" => rename(attributes, "member_name", "memberName"),
+        "Literal" => {
+            rename(attributes, "hexvalue", "hexValue");
+            if let Some(token) = attributes.remove("token") {
+                let kind = match token.as_str() {
+                    Some("true" | "false" | "bool") => "bool",
+                    Some("string" | "stringLiteral") => "string",
+                    Some("hexString") => "hexString",
+                    Some("unicodeString") => "unicodeString",
+                    _ => "number",
+                };
+                attributes.entry("kind").or_insert(kind.into());
+            }
+        }
+        "FunctionCall" => {
+            let type_conversion = attributes.remove("type_conversion");
+            if !attributes.contains_key("kind") {
+                let kind = if type_conversion.as_ref().and_then(Value::as_bool) == Some(true) {
+                    "typeConversion"
+                } else if attributes
+                    .get("isStructConstructorCall")
+                    .and_then(Value::as_bool)
+                    == Some(true)
+                {
+                    "structConstructorCall"
+                } else {
+                    "functionCall"
+                };
+                attributes.insert("kind".to_owned(), kind.into());
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,189 @@
+//! Normalization of ASTs from older compilers into the shape of [`crate::ast_specs`].
+//!
+//! Works on the raw JSON before deserialization. Two layers are applied to every node:
+//! the legacy `children`/`attributes` AST (solc < 0.8) is first rewritten into the
+//! compact layout, then fields that older compact ASTs lack are filled in the way
+//! current solc reports them (`mutability` from `constant`, `stateMutability` from
+//! `constant`/`payable`, `kind` from `isConstructor`, a `-1:-1:-1` `nameLocation`,
+//! structured documentation, flattened `ExternalReference` maps, ...).
+//!
+//! Nodes created along the way get negative ids hashed from their parent's id and position,
+//! unique within one normalization and the same across normalizations of the same unit.
+
+mod compact;
+mod legacy;
+
+use std::collections::{HashMap, HashSet};
+
+use serde_json::{Map, Value};
+
+use crate::{AstParserResult, ast_specs::SourceUnit, utils::json_locator};
+
+/// Rewrites a source unit AST of any supported solc version in place.
+pub fn normalize(ast: &mut Value) {
+    normalize_all([ast]);
+}
+
+/// Like [`normalize`], for every unit of one compilation at once, so import aliases
+/// can be resolved to declarations of other units.
+pub fn normalize_all<'a, I: IntoIterator<Item = &'a mut Value>>(asts: I) {
+    let mut asts: Vec<&mut Value> = asts.into_iter().collect();
+    let mut ids = SyntheticIds::default();
+    for ast in asts.iter_mut() {
+        walk(ast, &mut ids);
+    }
+
+    let mut names = HashMap::new();
+    for ast in &asts {
+        collect_names(ast, &mut names);
+    }
+    for ast in asts.iter_mut() {
+        resolve_aliases(ast, &names);
+    }
+}
+
+/// Whether `ast` is in the legacy `children`/`attributes` layout.
+pub fn is_legacy(ast: &Value) -> bool {
+    ast.as_object().is_some_and(legacy::is_legacy_node)
+}
+
+/// Normalizes `ast` and deserializes it, recording the compiler version on the unit.
+pub fn source_unit_from_value(
+    mut ast: Value,
+    compiler_version: Option<&str>,
+) -> AstParserResult<SourceUnit> {
+    normalize(&mut ast);
+    let mut unit: SourceUnit = json_locator::from_value(&ast)?;
+    unit.set_compiler_version(compiler_version.map(str::to_owned));
+    Ok(unit)
+}
+
+/// Normalizes the sources of a solc output document, taking `legacyAST` when
+/// the compiler gave no compact `ast`.
+pub(crate) fn normalize_solc_output(output: &mut Value) {
+    let Some(Value::Object(sources)) = output.get_mut("sources") else {
+        return;
+    };
+
+    let asts = sources.values_mut().filter_map(|source| {
+        let source = source.as_object_mut()?;
+        if let Some(legacy) = source.remove("legacyAST")
+            && !source.contains_key("ast")
+            && !source.contains_key("AST")
+        {
+            source.insert("ast".to_owned(), legacy);
+        }
+        // `AST` in `--combined-json` output
+        if source.contains_key("ast") {
+            source.get_mut("ast")
+        } else {
+            source.get_mut("AST")
+        }
+    });
+    normalize_all(asts);
+}
+
+/// Normalizes the `ast` of a Foundry artifact.
+pub(crate) fn normalize_artifact(artifact: &mut Value) {
+    if let Some(ast) = artifact.get_mut("ast") {
+        normalize(ast);
+    }
+}
+
+fn walk(value: &mut Value, ids: &mut SyntheticIds) {
+    match value {
+        Value::Object(node) => {
+            if legacy::is_legacy_node(node) {
+                legacy::convert(node);
+            }
+            compact::fix(node, ids);
+            node.values_mut().for_each(|child| walk(child, ids));
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| walk(item, ids)),
+        _ => {}
+    }
+}
+
+fn collect_names(value: &Value, names: &mut HashMap<i64, String>) {
+    match value {
+        Value::Object(node) => {
+            if let (Some(_), Some(id), Some(name)) = (
+                node.get("nodeType"),
+                node.get("id").and_then(Value::as_i64),
+                node.get("name").and_then(Value::as_str),
+            ) {
+                names.entry(id).or_insert_with(|| name.to_owned());
+            }
+            node.values().for_each(|child| collect_names(child, names));
+        }
+        Value::Array(items) => items.iter().for_each(|child| collect_names(child, names)),
+        _ => {}
+    }
+}
+
+/// Names the `Identifier`s synthesized for id-only import aliases.
+fn resolve_aliases(value: &mut Value, names: &HashMap<i64, String>) {
+    match value {
+        Value::Object(node) => {
+            if node.get("nodeType").and_then(Value::as_str) == Some("ImportDirective") {
+                for alias in node
+                    .get_mut("symbolAliases")
+                    .and_then(Value::as_array_mut)
+                    .into_iter()
+                    .flatten()
+                {
+                    let Some(foreign) = alias.get_mut("foreign").and_then(Value::as_object_mut)
+                    else {
+                        continue;
+                    };
+                    let declaration = foreign.get("referencedDeclaration").and_then(Value::as_i64);
+                    let name = declaration.and_then(|declaration| names.get(&declaration));
+                    if let (Some(Value::String(current)), Some(name)) =
+                        (foreign.get_mut("name"), name)
+                        && current.is_empty()
+                    {
+                        current.clone_from(name);
+                    }
+                }
+            }
+            node.values_mut()
+                .for_each(|child| resolve_aliases(child, names));
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|child| resolve_aliases(child, names)),
+        _ => {}
+    }
+}
+
+/// Ids handed out to synthesized nodes during one normalization.
+#[derive(Debug, Default)]
+struct SyntheticIds {
+    used: HashSet<i64>,
+}
+
+impl SyntheticIds {
+    /// Negative id below `-1` for the `slot`-th node synthesized under `parent`, hashed from both
+    /// and rehashed until it differs from every id handed out before.
+    fn id(&mut self, parent: &Map<String, Value>, slot: usize) -> i64 {
+        let parent = parent.get("id").and_then(Value::as_i64).unwrap_or_default();
+        let mut salt = 0;
+        loop {
+            let hash = mix(mix(mix(parent as u64) ^ slot as u64) ^ salt);
+            // 62 bits keep the negation and the offset in range
+            let id = -((hash >> 2) as i64) - 2;
+            if self.used.insert(id) {
+                return id;
+            }
+            salt += 1;
+        }
+    }
+}
+
+/// SplitMix64 finalizer, stable across platforms and Rust versions unlike `DefaultHasher`.
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
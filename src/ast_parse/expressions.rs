@@ -0,0 +1,453 @@
+//! Expressions, by precedence climbing.
+
+use serde_json::{Value, json};
+
+use super::{
+    Parser, TokenKind, hex, items::is_elementary_type, lexer::unescape, type_descriptions,
+};
+use crate::AstParserResult;
+
+const ASSIGNMENT_OPERATORS: &[&str] = &[
+    "=", "|=", "^=", "&=", "<<=", ">>=", ">>>=", "+=", "-=", "*=", "/=", "%=",
+];
+
+/// Binary operators from the loosest binding up.
+const BINARY_OPERATORS: &[&[&str]] = &[
+    &["||"],
+    &["&&"],
+    &["==", "!="],
+    &["<", ">", "<=", ">="],
+    &["|"],
+    &["^"],
+    &["&"],
+    &["<<", ">>", ">>>"],
+    &["+", "-"],
+    &["*", "/", "%"],
+    &["**"],
+];
+
+const PREFIX_OPERATORS: &[&str] = &["!", "~", "-", "+", "++", "--", "delete"];
+
+const SUBDENOMINATIONS: &[&str] = &[
+    "wei", "gwei", "ether", "finney", "szabo", "seconds", "minutes", "hours", "days", "weeks",
+];
+
+impl Parser<'_> {
+    pub(super) fn expression(&mut self) -> AstParserResult<Value> {
+        let start = self.peek().start;
+        let left = self.conditional()?;
+
+        let operator = self.peek_text();
+        if self.peek().kind == TokenKind::Punct && ASSIGNMENT_OPERATORS.contains(&operator) {
+            let id = self.id();
+            self.bump();
+            let right = self.expression()?;
+            return Ok(self.expression_node(
+                "Assignment",
+                id,
+                start,
+                json!({
+                    "leftHandSide": left,
+                    "operator": operator,
+                    "rightHandSide": right,
+                }),
+            ));
+        }
+        Ok(left)
+    }
+
+    fn conditional(&mut self) -> AstParserResult<Value> {
+        let start = self.peek().start;
+        let condition = self.binary(0)?;
+        if !self.eat("?") {
+            return Ok(condition);
+        }
+        let id = self.id();
+        let true_expression = self.expression()?;
+        self.expect(":")?;
+        let false_expression = self.expression()?;
+        Ok(self.expression_node(
+            "Conditional",
+            id,
+            start,
+            json!({
+                "condition": condition,
+                "falseExpression": false_expression,
+                "trueExpression": true_expression,
+            }),
+        ))
+    }
+
+    fn binary(&mut self, level: usize) -> AstParserResult<Value> {
+        let Some(operators) = BINARY_OPERATORS.get(level) else {
+            return self.unary();
+        };
+        let start = self.peek().start;
+        let mut left = self.binary(level + 1)?;
+
+        while self.peek().kind == TokenKind::Punct && operators.contains(&self.peek_text()) {
+            let operator = self.bump();
+            let operator = self.text(operator);
+            let id = self.id();
+            // `**` is right associative
+            let right = if operator == "**" {
+                self.binary(level)?
+            } else {
+                self.binary(level + 1)?
+            };
+            left = self.expression_node(
+                "BinaryOperation",
+                id,
+                start,
+                json!({
+                    "commonType": type_descriptions(),
+                    "leftExpression": left,
+                    "operator": operator,
+                    "rightExpression": right,
+                }),
+            );
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> AstParserResult<Value> {
+        let start = self.peek().start;
+        let operator = self.peek_text();
+        if matches!(self.peek().kind, TokenKind::Punct | TokenKind::Ident)
+            && PREFIX_OPERATORS.contains(&operator)
+        {
+            let id = self.id();
+            self.bump();
+            let sub_expression = self.unary()?;
+            return Ok(self.expression_node(
+                "UnaryOperation",
+                id,
+                start,
+                json!({
+                    "operator": operator,
+                    "prefix": true,
+                    "subExpression": sub_expression,
+                }),
+            ));
+        }
+
+        let mut expression = self.postfix()?;
+        while self.at("++") || self.at("--") {
+            let id = self.id();
+            let operator = self.bump();
+            expression = self.expression_node(
+                "UnaryOperation",
+                id,
+                start,
+                json!({
+                    "operator": self.text(operator),
+                    "prefix": false,
+                    "subExpression": expression,
+                }),
+            );
+        }
+        Ok(expression)
+    }
+
+    /// Calls, call options, member and index access.
+    fn postfix(&mut self) -> AstParserResult<Value> {
+        let start = self.peek().start;
+        let mut expression = self.primary()?;
+
+        loop {
+            if self.at("(") {
+                let id = self.id();
+                self.bump();
+                let (arguments, names, name_locations) = self.call_arguments()?;
+                let kind = match expression.get("nodeType").and_then(Value::as_str) {
+                    Some("ElementaryTypeNameExpression") => "typeConversion",
+                    _ => "functionCall",
+                };
+                expression = self.expression_node(
+                    "FunctionCall",
+                    id,
+                    start,
+                    json!({
+                        "arguments": arguments,
+                        "expression": expression,
+                        "kind": kind,
+                        "nameLocations": name_locations,
+                        "names": names,
+                        "tryCall": false,
+                    }),
+                );
+            } else if self.at("{")
+                && self.peek_nth(1).kind == TokenKind::Ident
+                && self.at_nth(2, ":")
+            {
+                let id = self.id();
+                self.bump();
+                let mut names = vec![];
+                let options = self.list("}", |parser| {
+                    names.push(parser.expect_ident()?.0);
+                    parser.expect(":")?;
+                    parser.expression()
+                })?;
+                expression = self.expression_node(
+                    "FunctionCallOptions",
+                    id,
+                    start,
+                    json!({ "expression": expression, "names": names, "options": options }),
+                );
+            } else if self.at(".") {
+                let id = self.id();
+                self.bump();
+                let (member_name, token) = self.expect_ident()?;
+                expression = self.expression_node(
+                    "MemberAccess",
+                    id,
+                    start,
+                    json!({
+                        "expression": expression,
+                        "memberLocation": self.token_src(token),
+                        "memberName": member_name,
+                        "referencedDeclaration": null,
+                    }),
+                );
+            } else if self.at("[") {
+                let id = self.id();
+                self.bump();
+                let index = if self.at("]") || self.at(":") {
+                    Value::Null
+                } else {
+                    self.expression()?
+                };
+                if self.eat(":") {
+                    let end = if self.at("]") {
+                        Value::Null
+                    } else {
+                        self.expression()?
+                    };
+                    self.expect("]")?;
+                    expression = self.expression_node(
+                        "IndexRangeAccess",
+                        id,
+                        start,
+                        json!({
+                            "baseExpression": expression,
+                            "endExpression": end,
+                            "startExpression": index,
+                        }),
+                    );
+                } else {
+                    self.expect("]")?;
+                    expression = self.expression_node(
+                        "IndexAccess",
+                        id,
+                        start,
+                        json!({ "baseExpression": expression, "indexExpression": index }),
+                    );
+                }
+            } else {
+                return Ok(expression);
+            }
+        }
+    }
+
+    /// Positional `(a, b)` or named `({a: 1, b: 2})` arguments, after the `(`.
+    fn call_arguments(&mut self) -> AstParserResult<(Vec<Value>, Vec<String>, Vec<String>)> {
+        let mut names = vec![];
+        let mut name_locations = vec![];
+        let arguments = if self.at("{") && self.peek_nth(1).kind == TokenKind::Ident {
+            self.bump();
+            let arguments = self.list("}", |parser| {
+                let (name, token) = parser.expect_ident()?;
+                names.push(name);
+                name_locations.push(parser.token_src(token));
+                parser.expect(":")?;
+                parser.expression()
+            })?;
+            self.expect(")")?;
+            arguments
+        } else {
+            self.list(")", Self::expression)?
+        };
+        Ok((arguments, names, name_locations))
+    }
+
+    fn primary(&mut self) -> AstParserResult<Value> {
+        let token = self.peek();
+        let start = token.start;
+        let text = self.text(token);
+
+        match token.kind {
+            TokenKind::Number | TokenKind::HexNumber => {
+                let id = self.id();
+                self.bump();
+                let subdenomination =
+                    if self.at_ident() && SUBDENOMINATIONS.contains(&self.peek_text()) {
+                        let token = self.bump();
+                        Value::from(self.text(token))
+                    } else {
+                        Value::Null
+                    };
+                Ok(self.expression_node(
+                    "Literal",
+                    id,
+                    start,
+                    json!({
+                        "hexValue": hex(text.as_bytes()),
+                        "isPure": true,
+                        "kind": "number",
+                        "subdenomination": subdenomination,
+                        "value": text,
+                    }),
+                ))
+            }
+            TokenKind::String | TokenKind::UnicodeString | TokenKind::HexString => {
+                self.string_literal()
+            }
+            TokenKind::Ident => match text {
+                "true" | "false" => {
+                    let id = self.id();
+                    self.bump();
+                    Ok(self.expression_node(
+                        "Literal",
+                        id,
+                        start,
+                        json!({
+                            "hexValue": hex(text.as_bytes()),
+                            "isPure": true,
+                            "kind": "bool",
+                            "value": text,
+                        }),
+                    ))
+                }
+                "new" => {
+                    let id = self.id();
+                    self.bump();
+                    let type_name = self.type_name()?;
+                    Ok(self.expression_node(
+                        "NewExpression",
+                        id,
+                        start,
+                        json!({ "typeName": type_name }),
+                    ))
+                }
+                // `payable(x)` converts to `address payable`
+                "payable" if self.at_nth(1, "(") => {
+                    let id = self.id();
+                    let mut type_name = self.elementary_type_name(false)?;
+                    type_name["name"] = "address".into();
+                    type_name["stateMutability"] = "payable".into();
+                    Ok(self.expression_node(
+                        "ElementaryTypeNameExpression",
+                        id,
+                        start,
+                        json!({ "isPure": true, "typeName": type_name }),
+                    ))
+                }
+                _ if is_elementary_type(text) => {
+                    let id = self.id();
+                    let type_name = self.elementary_type_name(false)?;
+                    Ok(self.expression_node(
+                        "ElementaryTypeNameExpression",
+                        id,
+                        start,
+                        json!({ "isPure": true, "typeName": type_name }),
+                    ))
+                }
+                _ => {
+                    let id = self.id();
+                    self.bump();
+                    Ok(self.expression_node(
+                        "Identifier",
+                        id,
+                        start,
+                        json!({
+                            "name": text,
+                            "overloadedDeclarations": [],
+                            "referencedDeclaration": null,
+                        }),
+                    ))
+                }
+            },
+            TokenKind::Punct if text == "(" || text == "[" => {
+                let id = self.id();
+                self.bump();
+                let is_inline_array = text == "[";
+                let close = if is_inline_array { "]" } else { ")" };
+                let components = self.list(close, |parser| {
+                    if parser.at(",") || parser.at(close) {
+                        Ok(Value::Null)
+                    } else {
+                        parser.expression()
+                    }
+                })?;
+                Ok(self.expression_node(
+                    "TupleExpression",
+                    id,
+                    start,
+                    json!({ "components": components, "isInlineArray": is_inline_array }),
+                ))
+            }
+            _ => Err(self.unexpected("expression")),
+        }
+    }
+
+    /// One literal from adjacent string tokens of the same kind, like solc joins them.
+    fn string_literal(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        let first = self.peek();
+        let kind = first.kind;
+        let mut bytes = vec![];
+
+        while self.peek().kind == kind {
+            let token = self.bump();
+            let text = self.text(token);
+            match kind {
+                TokenKind::HexString => {
+                    let digits: String = text[4..text.len() - 1]
+                        .chars()
+                        .filter(|c| *c != '_')
+                        .collect();
+                    let decoded = (0..digits.len())
+                        .step_by(2)
+                        .map(|i| u8::from_str_radix(digits.get(i..i + 2)?, 16).ok())
+                        .collect::<Option<Vec<u8>>>();
+                    match decoded {
+                        Some(decoded) => bytes.extend(decoded),
+                        None => {
+                            return Err(self.error(token.start, "invalid hex string".to_owned()));
+                        }
+                    }
+                }
+                _ => {
+                    let quoted = text.trim_start_matches("unicode");
+                    match unescape(quoted) {
+                        Some(decoded) => bytes.extend(decoded),
+                        None => {
+                            return Err(
+                                self.error(token.start, "invalid escape sequence".to_owned())
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        let kind = match kind {
+            TokenKind::HexString => "hexString",
+            TokenKind::UnicodeString => "unicodeString",
+            _ => "string",
+        };
+        // solc leaves out values that are not valid UTF-8
+        let value = String::from_utf8(bytes.clone()).ok();
+        Ok(self.expression_node(
+            "Literal",
+            id,
+            first.start,
+            json!({
+                "hexValue": hex(&bytes),
+                "isPure": true,
+                "kind": kind,
+                "value": value,
+            }),
+        ))
+    }
+}
@@ -0,0 +1,945 @@
+//! Source unit and contract level declarations, parameters and type names.
+
+use std::collections::BTreeMap;
+
+use serde_json::{Value, json};
+
+use super::{Parser, TokenKind, lexer::unescape, type_descriptions};
+use crate::AstParserResult;
+
+const UNKNOWN_SRC: &str = "-1:-1:-1";
+
+const VISIBILITIES: &[&str] = &["external", "public", "internal", "private"];
+const STATE_MUTABILITIES: &[&str] = &["pure", "view", "payable", "constant"];
+const STORAGE_LOCATIONS: &[&str] = &["memory", "storage", "calldata"];
+/// Reserved words lexed as identifiers that never name a type.
+const KEYWORDS: &[&str] = &[
+    "assembly",
+    "break",
+    "catch",
+    "continue",
+    "delete",
+    "do",
+    "else",
+    "emit",
+    "false",
+    "for",
+    "if",
+    "new",
+    "return",
+    "returns",
+    "true",
+    "try",
+    "unchecked",
+    "while",
+];
+
+/// How the declaration being parsed may be spelled.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum VariableKind {
+    State,
+    /// Constant at file level.
+    File,
+    Parameter,
+    EventParameter,
+    StructMember,
+    Local,
+}
+
+pub(super) fn is_elementary_type(name: &str) -> bool {
+    let sized = |prefix: &str, sizes: fn(u32) -> bool| {
+        name.strip_prefix(prefix)
+            .and_then(|size| size.parse::<u32>().ok())
+            .is_some_and(sizes)
+    };
+    matches!(
+        name,
+        "address" | "bool" | "string" | "bytes" | "byte" | "uint" | "int" | "fixed" | "ufixed"
+    ) || sized("uint", |bits| bits % 8 == 0 && (8..=256).contains(&bits))
+        || sized("int", |bits| bits % 8 == 0 && (8..=256).contains(&bits))
+        || sized("bytes", |size| (1..=32).contains(&size))
+        || ["fixed", "ufixed"].iter().any(|prefix| {
+            name.strip_prefix(prefix)
+                .and_then(|size| size.split_once('x'))
+                .is_some_and(|(m, n)| m.parse::<u32>().is_ok() && n.parse::<u32>().is_ok())
+        })
+}
+
+impl Parser<'_> {
+    pub(super) fn source_unit(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        self.scopes.push(id);
+        let mut nodes = vec![];
+        let mut exported_symbols: BTreeMap<String, Vec<isize>> = BTreeMap::new();
+
+        while self.peek().kind != TokenKind::Eof {
+            let node = match self.peek_text() {
+                "pragma" => self.pragma()?,
+                "import" => self.import()?,
+                "abstract" | "contract" | "interface" | "library" => self.contract()?,
+                "function" => self.function()?,
+                "struct" => self.struct_definition()?,
+                "enum" => self.enum_definition()?,
+                "error" => self.error_definition()?,
+                "event" => self.event()?,
+                "type" => self.user_defined_value_type()?,
+                "using" => self.using_for()?,
+                _ => self.variable_declaration(VariableKind::File)?,
+            };
+            if let (Some(name), Some(id)) = (
+                node.get("name").and_then(Value::as_str),
+                node.get("id").and_then(Value::as_i64),
+            ) && !name.is_empty()
+            {
+                exported_symbols
+                    .entry(name.to_owned())
+                    .or_default()
+                    .push(id as isize);
+            }
+            nodes.push(node);
+        }
+        self.scopes.pop();
+
+        Ok(json!({
+            "absolutePath": self.absolute_path,
+            "exportedSymbols": exported_symbols,
+            "id": id,
+            "license": self.license,
+            "nodeType": "SourceUnit",
+            "nodes": nodes,
+            "src": self.src(0, self.source.len()),
+        }))
+    }
+
+    /// Literals are the tokens up to `;`, like solc splits them.
+    fn pragma(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        let start = self.expect("pragma")?.start;
+        let mut literals = vec![];
+        while !self.at(";") {
+            if self.peek().kind == TokenKind::Eof {
+                return Err(self.unexpected("`;`"));
+            }
+            let token = self.bump();
+            literals.push(self.text(token).to_owned());
+        }
+        self.bump();
+        Ok(self.node(
+            "PragmaDirective",
+            id,
+            start,
+            json!({ "literals": literals }),
+        ))
+    }
+
+    fn import(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        let start = self.expect("import")?.start;
+        let mut symbol_aliases = vec![];
+        let mut unit_alias = String::new();
+        let mut name_location = UNKNOWN_SRC.to_owned();
+
+        let file = if self.peek().kind == TokenKind::String {
+            let file = self.string_value()?;
+            if self.eat("as") {
+                let (alias, token) = self.expect_ident()?;
+                unit_alias = alias;
+                name_location = self.token_src(token);
+            }
+            file
+        } else {
+            if self.eat("*") {
+                self.expect("as")?;
+                let (alias, token) = self.expect_ident()?;
+                unit_alias = alias;
+                name_location = self.token_src(token);
+            } else {
+                self.expect("{")?;
+                symbol_aliases = self.list("}", Self::symbol_alias)?;
+            }
+            self.expect("from")?;
+            self.string_value()?
+        };
+        self.expect(";")?;
+
+        Ok(self.node(
+            "ImportDirective",
+            id,
+            start,
+            json!({
+                "absolutePath": file,
+                "file": file,
+                "nameLocation": name_location,
+                "scope": self.scope(),
+                "sourceUnit": -1,
+                "symbolAliases": symbol_aliases,
+                "unitAlias": unit_alias,
+            }),
+        ))
+    }
+
+    fn symbol_alias(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        let (name, token) = self.expect_ident()?;
+        let foreign = self.expression_node(
+            "Identifier",
+            id,
+            token.start,
+            json!({
+                "name": name,
+                "overloadedDeclarations": [],
+                "referencedDeclaration": null,
+            }),
+        );
+        let (local, name_location) = if self.eat("as") {
+            let (local, token) = self.expect_ident()?;
+            (Value::from(local), self.token_src(token))
+        } else {
+            (Value::Null, UNKNOWN_SRC.to_owned())
+        };
+        Ok(json!({
+            "foreign": foreign,
+            "local": local,
+            "nameLocation": name_location,
+        }))
+    }
+
+    pub(super) fn contract(&mut self) -> AstParserResult<Value> {
+        let documentation = self.documentation();
+        let id = self.id();
+        let start = self.peek().start;
+        let is_abstract = self.eat("abstract");
+        let kind = match self.peek_text() {
+            kind @ ("contract" | "interface" | "library") => {
+                self.bump();
+                kind
+            }
+            _ => return Err(self.unexpected("`contract`, `interface` or `library`")),
+        };
+        let (name, name_token) = self.expect_ident()?;
+
+        let mut base_contracts = vec![];
+        if self.eat("is") {
+            loop {
+                base_contracts.push(self.inheritance_specifier()?);
+                if !self.eat(",") {
+                    break;
+                }
+            }
+        }
+
+        let scope = self.scope();
+        self.scopes.push(id);
+        let outer = self.contract.replace(name.clone());
+        self.expect("{")?;
+        let mut nodes = vec![];
+        while !self.eat("}") {
+            nodes.push(self.contract_part()?);
+        }
+        self.contract = outer;
+        self.scopes.pop();
+
+        let fully_implemented = !is_abstract
+            && kind != "interface"
+            && nodes.iter().all(|node| {
+                !matches!(
+                    node.get("nodeType").and_then(Value::as_str),
+                    Some("FunctionDefinition" | "ModifierDefinition")
+                ) || node.get("body").is_some_and(|body| !body.is_null())
+            });
+
+        Ok(self.node(
+            "ContractDefinition",
+            id,
+            start,
+            json!({
+                "abstract": is_abstract,
+                "baseContracts": base_contracts,
+                "canonicalName": name,
+                "contractDependencies": [],
+                "contractKind": kind,
+                "documentation": documentation,
+                "fullyImplemented": fully_implemented,
+                "linearizedBaseContracts": [id],
+                "name": name,
+                "nameLocation": self.token_src(name_token),
+                "nodes": nodes,
+                "scope": scope,
+                "usedErrors": [],
+                "usedEvents": [],
+            }),
+        ))
+    }
+
+    fn inheritance_specifier(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        let start = self.peek().start;
+        let base_name = self.identifier_path()?;
+        let arguments = if self.eat("(") {
+            Value::from(self.list(")", Self::expression)?)
+        } else {
+            Value::Null
+        };
+        Ok(self.node(
+            "InheritanceSpecifier",
+            id,
+            start,
+            json!({ "arguments": arguments, "baseName": base_name }),
+        ))
+    }
+
+    fn contract_part(&mut self) -> AstParserResult<Value> {
+        match self.peek_text() {
+            "function" if self.at_nth(1, "(") && self.at_function_type_variable() => {
+                self.variable_declaration(VariableKind::State)
+            }
+            "function" | "constructor" | "fallback" | "receive" => self.function(),
+            "modifier" => self.modifier(),
+            "struct" => self.struct_definition(),
+            "enum" => self.enum_definition(),
+            "error" => self.error_definition(),
+            "event" => self.event(),
+            "type" => self.user_defined_value_type(),
+            "using" => self.using_for(),
+            _ => self.variable_declaration(VariableKind::State),
+        }
+    }
+
+    /// Whether `function(` starts a state variable of function type rather than an unnamed
+    /// pre-0.6 fallback, whose signature a body, `;` or modifier invocation follows.
+    fn at_function_type_variable(&mut self) -> bool {
+        let checkpoint = self.checkpoint();
+        let scopes = self.scopes.len();
+        let is_variable = self.type_name().is_ok() && {
+            while VISIBILITIES.contains(&self.peek_text())
+                || matches!(self.peek_text(), "constant" | "immutable")
+            {
+                self.bump();
+            }
+            self.at_ident() && (self.at_nth(1, ";") || self.at_nth(1, "="))
+        };
+        self.scopes.truncate(scopes);
+        self.rewind(checkpoint);
+        is_variable
+    }
+
+    pub(super) fn function(&mut self) -> AstParserResult<Value> {
+        let documentation = self.documentation();
+        let id = self.id();
+        let start = self.peek().start;
+        let keyword = self.bump();
+
+        let (kind, name, name_location) = match self.text(keyword) {
+            "function" if self.at("(") => ("fallback", String::new(), UNKNOWN_SRC.to_owned()),
+            "function" => {
+                let (name, token) = self.expect_ident()?;
+                let kind = if self.contract.is_some() {
+                    "function"
+                } else {
+                    "freeFunction"
+                };
+                (kind, name, self.token_src(token))
+            }
+            kind @ ("constructor" | "fallback" | "receive") => {
+                (kind, String::new(), self.token_src(keyword))
+            }
+            _ => return Err(self.error(keyword.start, "expected function".to_owned())),
+        };
+
+        let scope = self.scope();
+        self.scopes.push(id);
+        let parameters = self.parameter_list(VariableKind::Parameter)?;
+
+        let mut visibility = None;
+        let mut state_mutability = "nonpayable";
+        let mut is_virtual = false;
+        let mut overrides = Value::Null;
+        let mut modifiers = vec![];
+        loop {
+            match self.peek_text() {
+                text if VISIBILITIES.contains(&text) => {
+                    self.bump();
+                    visibility = Some(text);
+                }
+                text if STATE_MUTABILITIES.contains(&text) => {
+                    self.bump();
+                    // `constant` functions predate `view`
+                    state_mutability = if text == "constant" { "view" } else { text };
+                }
+                "virtual" => {
+                    self.bump();
+                    is_virtual = true;
+                }
+                "override" => overrides = self.override_specifier()?,
+                "returns" | "{" | ";" => break,
+                _ if self.at_ident() => modifiers.push(self.modifier_invocation()?),
+                _ => return Err(self.unexpected("function attribute, `returns` or body")),
+            }
+        }
+
+        let return_parameters = if self.eat("returns") {
+            self.parameter_list(VariableKind::Parameter)?
+        } else {
+            self.empty_parameter_list()
+        };
+
+        self.return_parameters
+            .push(return_parameters["id"].as_i64().unwrap_or_default() as isize);
+        let body = if self.eat(";") {
+            Value::Null
+        } else {
+            self.block()?
+        };
+        self.return_parameters.pop();
+        self.scopes.pop();
+
+        let visibility = visibility.unwrap_or(match kind {
+            "freeFunction" => "internal",
+            "fallback" | "receive" => "external",
+            _ => "public",
+        });
+
+        Ok(self.node(
+            "FunctionDefinition",
+            id,
+            start,
+            json!({
+                "body": body,
+                "documentation": documentation,
+                "implemented": !body.is_null(),
+                "kind": kind,
+                "modifiers": modifiers,
+                "name": name,
+                "nameLocation": name_location,
+                "overrides": overrides,
+                "parameters": parameters,
+                "returnParameters": return_parameters,
+                "scope": scope,
+                "stateMutability": state_mutability,
+                "virtual": is_virtual,
+                "visibility": visibility,
+            }),
+        ))
+    }
+
+    fn modifier(&mut self) -> AstParserResult<Value> {
+        let documentation = self.documentation();
+        let id = self.id();
+        let start = self.expect("modifier")?.start;
+        let (name, name_token) = self.expect_ident()?;
+
+        self.scopes.push(id);
+        let parameters = if self.at("(") {
+            self.parameter_list(VariableKind::Parameter)?
+        } else {
+            self.empty_parameter_list()
+        };
+
+        let mut is_virtual = false;
+        let mut overrides = Value::Null;
+        loop {
+            match self.peek_text() {
+                "virtual" => {
+                    self.bump();
+                    is_virtual = true;
+                }
+                "override" => overrides = self.override_specifier()?,
+                _ => break,
+            }
+        }
+
+        if self.at(";") {
+            return Err(self.error(
+                self.peek().start,
+                "modifiers without a body are not supported".to_owned(),
+            ));
+        }
+        let body = self.block()?;
+        self.scopes.pop();
+
+        Ok(self.node(
+            "ModifierDefinition",
+            id,
+            start,
+            json!({
+                "body": body,
+                "documentation": documentation,
+                "name": name,
+                "nameLocation": self.token_src(name_token),
+                "overrides": overrides,
+                "parameters": parameters,
+                "virtual": is_virtual,
+                "visibility": "internal",
+            }),
+        ))
+    }
+
+    fn modifier_invocation(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        let start = self.peek().start;
+        let modifier_name = self.identifier_path()?;
+        let arguments = if self.eat("(") {
+            Value::from(self.list(")", Self::expression)?)
+        } else {
+            Value::Null
+        };
+        Ok(self.node(
+            "ModifierInvocation",
+            id,
+            start,
+            json!({ "arguments": arguments, "modifierName": modifier_name }),
+        ))
+    }
+
+    fn override_specifier(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        let start = self.expect("override")?.start;
+        let overrides = if self.eat("(") {
+            self.list(")", Self::identifier_path)?
+        } else {
+            vec![]
+        };
+        Ok(self.node(
+            "OverrideSpecifier",
+            id,
+            start,
+            json!({ "overrides": overrides }),
+        ))
+    }
+
+    fn event(&mut self) -> AstParserResult<Value> {
+        let documentation = self.documentation();
+        let id = self.id();
+        let start = self.expect("event")?.start;
+        let (name, name_token) = self.expect_ident()?;
+        self.scopes.push(id);
+        let parameters = self.parameter_list(VariableKind::EventParameter)?;
+        self.scopes.pop();
+        let anonymous = self.eat("anonymous");
+        self.expect(";")?;
+
+        Ok(self.node(
+            "EventDefinition",
+            id,
+            start,
+            json!({
+                "anonymous": anonymous,
+                "documentation": documentation,
+                "name": name,
+                "nameLocation": self.token_src(name_token),
+                "parameters": parameters,
+            }),
+        ))
+    }
+
+    fn error_definition(&mut self) -> AstParserResult<Value> {
+        let documentation = self.documentation();
+        let id = self.id();
+        let start = self.expect("error")?.start;
+        let (name, name_token) = self.expect_ident()?;
+        self.scopes.push(id);
+        let parameters = self.parameter_list(VariableKind::Parameter)?;
+        self.scopes.pop();
+        self.expect(";")?;
+
+        Ok(self.node(
+            "ErrorDefinition",
+            id,
+            start,
+            json!({
+                "documentation": documentation,
+                "name": name,
+                "nameLocation": self.token_src(name_token),
+                "parameters": parameters,
+            }),
+        ))
+    }
+
+    fn struct_definition(&mut self) -> AstParserResult<Value> {
+        let documentation = self.documentation();
+        let id = self.id();
+        let start = self.expect("struct")?.start;
+        let (name, name_token) = self.expect_ident()?;
+        let scope = self.scope();
+
+        self.expect("{")?;
+        self.scopes.push(id);
+        let mut members = vec![];
+        while !self.eat("}") {
+            members.push(self.variable_declaration(VariableKind::StructMember)?);
+        }
+        self.scopes.pop();
+
+        Ok(self.node(
+            "StructDefinition",
+            id,
+            start,
+            json!({
+                "canonicalName": self.canonical_name(&name),
+                "documentation": documentation,
+                "members": members,
+                "name": name,
+                "nameLocation": self.token_src(name_token),
+                "scope": scope,
+                "visibility": "public",
+            }),
+        ))
+    }
+
+    fn enum_definition(&mut self) -> AstParserResult<Value> {
+        let documentation = self.documentation();
+        let id = self.id();
+        let start = self.expect("enum")?.start;
+        let (name, name_token) = self.expect_ident()?;
+        self.expect("{")?;
+        let members = self.list("}", |parser| {
+            let id = parser.id();
+            let (name, token) = parser.expect_ident()?;
+            Ok(parser.node(
+                "EnumValue",
+                id,
+                token.start,
+                json!({ "name": name, "nameLocation": parser.token_src(token) }),
+            ))
+        })?;
+
+        Ok(self.node(
+            "EnumDefinition",
+            id,
+            start,
+            json!({
+                "canonicalName": self.canonical_name(&name),
+                "documentation": documentation,
+                "members": members,
+                "name": name,
+                "nameLocation": self.token_src(name_token),
+            }),
+        ))
+    }
+
+    fn user_defined_value_type(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        let start = self.expect("type")?.start;
+        let (name, name_token) = self.expect_ident()?;
+        self.expect("is")?;
+        let underlying_type = self.type_name()?;
+        self.expect(";")?;
+
+        Ok(self.node(
+            "UserDefinedValueTypeDefinition",
+            id,
+            start,
+            json!({
+                "canonicalName": self.canonical_name(&name),
+                "name": name,
+                "nameLocation": self.token_src(name_token),
+                "underlyingType": underlying_type,
+            }),
+        ))
+    }
+
+    /// `using L for T;`, `using L for *;` and `using {f, g as +} for T global;`.
+    fn using_for(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        let start = self.expect("using")?.start;
+
+        let (library_name, function_list) = if self.eat("{") {
+            let functions = self.list("}", |parser| {
+                let path = parser.identifier_path()?;
+                if parser.eat("as") {
+                    let operator = parser.bump();
+                    Ok(json!({ "definition": path, "operator": parser.text(operator) }))
+                } else {
+                    Ok(json!({ "function": path }))
+                }
+            })?;
+            (Value::Null, Value::from(functions))
+        } else {
+            (self.identifier_path()?, Value::Null)
+        };
+
+        self.expect("for")?;
+        let type_name = if self.eat("*") {
+            Value::Null
+        } else {
+            self.type_name()?
+        };
+        let global = self.eat("global");
+        self.expect(";")?;
+
+        Ok(self.node(
+            "UsingForDirective",
+            id,
+            start,
+            json!({
+                "functionList": function_list,
+                "global": global,
+                "libraryName": library_name,
+                "typeName": type_name,
+            }),
+        ))
+    }
+
+    /// State variables, file level constants, struct members, parameters and locals.
+    pub(super) fn variable_declaration(&mut self, kind: VariableKind) -> AstParserResult<Value> {
+        let documentation = match kind {
+            VariableKind::State | VariableKind::File => self.documentation(),
+            _ => Value::Null,
+        };
+        let id = self.id();
+        let start = self.peek().start;
+        let type_name = self.type_name()?;
+
+        let mut visibility = "internal";
+        let mut mutability = "mutable";
+        let mut storage_location = "default";
+        let mut indexed = false;
+        let mut overrides = Value::Null;
+        loop {
+            match (kind, self.peek_text()) {
+                (VariableKind::State, text) if VISIBILITIES.contains(&text) => visibility = text,
+                (VariableKind::State | VariableKind::File, "constant") => mutability = "constant",
+                (VariableKind::State, "immutable") => mutability = "immutable",
+                (VariableKind::State, "override") => {
+                    overrides = self.override_specifier()?;
+                    continue;
+                }
+                (VariableKind::Parameter | VariableKind::Local, text)
+                    if STORAGE_LOCATIONS.contains(&text) =>
+                {
+                    storage_location = text
+                }
+                (VariableKind::EventParameter, "indexed") => indexed = true,
+                _ => break,
+            }
+            self.bump();
+        }
+
+        let (name, name_location) = match kind {
+            VariableKind::Parameter | VariableKind::EventParameter if !self.at_ident() => {
+                (String::new(), UNKNOWN_SRC.to_owned())
+            }
+            _ => {
+                let (name, token) = self.expect_ident()?;
+                (name, self.token_src(token))
+            }
+        };
+
+        let value = match kind {
+            VariableKind::State | VariableKind::File if self.eat("=") => self.expression()?,
+            _ => Value::Null,
+        };
+
+        let mut node = json!({
+            "constant": mutability == "constant",
+            "documentation": documentation,
+            "mutability": mutability,
+            "name": name,
+            "nameLocation": name_location,
+            "overrides": overrides,
+            "scope": self.scope(),
+            "stateVariable": kind == VariableKind::State,
+            "storageLocation": storage_location,
+            "typeDescriptions": type_descriptions(),
+            "typeName": type_name,
+            "value": value,
+            "visibility": visibility,
+        });
+        if kind == VariableKind::EventParameter {
+            node["indexed"] = indexed.into();
+        }
+        // As in solc, the `;` of a declaration ends no node
+        let node = self.node("VariableDeclaration", id, start, node);
+        if matches!(
+            kind,
+            VariableKind::State | VariableKind::File | VariableKind::StructMember
+        ) {
+            self.expect(";")?;
+        }
+        Ok(node)
+    }
+
+    pub(super) fn parameter_list(&mut self, kind: VariableKind) -> AstParserResult<Value> {
+        let id = self.id();
+        let start = self.expect("(")?.start;
+        let parameters = self.list(")", |parser| parser.variable_declaration(kind))?;
+        Ok(self.node(
+            "ParameterList",
+            id,
+            start,
+            json!({ "parameters": parameters }),
+        ))
+    }
+
+    /// Stands in for an omitted list, located right after the last consumed token.
+    fn empty_parameter_list(&mut self) -> Value {
+        let id = self.id();
+        let end = self.prev_end();
+        json!({
+            "id": id,
+            "nodeType": "ParameterList",
+            "parameters": [],
+            "src": self.src(end, end),
+        })
+    }
+
+    pub(super) fn type_name(&mut self) -> AstParserResult<Value> {
+        let start = self.peek().start;
+        let mut type_name = match self.peek_text() {
+            "mapping" => self.mapping()?,
+            "function" => self.function_type_name()?,
+            text if is_elementary_type(text) => self.elementary_type_name(true)?,
+            text if self.at_ident() && !KEYWORDS.contains(&text) => {
+                let id = self.id();
+                let path_node = self.identifier_path()?;
+                self.node(
+                    "UserDefinedTypeName",
+                    id,
+                    start,
+                    json!({
+                        "pathNode": path_node,
+                        "referencedDeclaration": -1,
+                        "typeDescriptions": type_descriptions(),
+                    }),
+                )
+            }
+            _ => return Err(self.unexpected("type name")),
+        };
+
+        while self.at("[") {
+            let id = self.id();
+            self.bump();
+            let length = if self.at("]") {
+                Value::Null
+            } else {
+                self.expression()?
+            };
+            self.expect("]")?;
+            type_name = self.node(
+                "ArrayTypeName",
+                id,
+                start,
+                json!({
+                    "baseType": type_name,
+                    "length": length,
+                    "typeDescriptions": type_descriptions(),
+                }),
+            );
+        }
+        Ok(type_name)
+    }
+
+    /// `address payable` is only a type name, `payable` alone only an expression.
+    pub(super) fn elementary_type_name(&mut self, allow_payable: bool) -> AstParserResult<Value> {
+        let id = self.id();
+        let token = self.bump();
+        let name = self.text(token);
+        let state_mutability = match name {
+            "address" if allow_payable && self.eat("payable") => Value::from("payable"),
+            "address" => Value::from("nonpayable"),
+            _ => Value::Null,
+        };
+        Ok(self.node(
+            "ElementaryTypeName",
+            id,
+            token.start,
+            json!({
+                "name": name,
+                "stateMutability": state_mutability,
+                "typeDescriptions": type_descriptions(),
+            }),
+        ))
+    }
+
+    fn mapping(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        let start = self.expect("mapping")?.start;
+        self.expect("(")?;
+        let key_type = self.type_name()?;
+        let (key_name, key_name_location) = self.optional_name();
+        self.expect("=>")?;
+        let value_type = self.type_name()?;
+        let (value_name, value_name_location) = self.optional_name();
+        self.expect(")")?;
+
+        Ok(self.node(
+            "Mapping",
+            id,
+            start,
+            json!({
+                "keyName": key_name,
+                "keyNameLocation": key_name_location,
+                "keyType": key_type,
+                "typeDescriptions": type_descriptions(),
+                "valueName": value_name,
+                "valueNameLocation": value_name_location,
+                "valueType": value_type,
+            }),
+        ))
+    }
+
+    fn optional_name(&mut self) -> (String, String) {
+        if self.at_ident() {
+            let token = self.bump();
+            (self.text(token).to_owned(), self.token_src(token))
+        } else {
+            (String::new(), UNKNOWN_SRC.to_owned())
+        }
+    }
+
+    fn function_type_name(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        let start = self.expect("function")?.start;
+        self.scopes.push(id);
+        let parameter_types = self.parameter_list(VariableKind::Parameter)?;
+
+        let mut visibility = "internal";
+        let mut state_mutability = "nonpayable";
+        loop {
+            match self.peek_text() {
+                text @ ("internal" | "external") => visibility = text,
+                text @ ("pure" | "view" | "payable") => state_mutability = text,
+                _ => break,
+            }
+            self.bump();
+        }
+
+        let return_parameter_types = if self.eat("returns") {
+            self.parameter_list(VariableKind::Parameter)?
+        } else {
+            self.empty_parameter_list()
+        };
+        self.scopes.pop();
+
+        Ok(self.node(
+            "FunctionTypeName",
+            id,
+            start,
+            json!({
+                "parameterTypes": parameter_types,
+                "returnParameterTypes": return_parameter_types,
+                "stateMutability": state_mutability,
+                "typeDescriptions": type_descriptions(),
+                "visibility": visibility,
+            }),
+        ))
+    }
+
+    fn canonical_name(&self, name: &str) -> String {
+        match &self.contract {
+            Some(contract) if !contract.is_empty() => format!("{contract}.{name}"),
+            _ => name.to_owned(),
+        }
+    }
+
+    /// Decoded content of the next string literal.
+    fn string_value(&mut self) -> AstParserResult<String> {
+        if self.peek().kind != TokenKind::String {
+            return Err(self.unexpected("string literal"));
+        }
+        let token = self.bump();
+        unescape(self.text(token))
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .ok_or_else(|| self.error(token.start, "invalid escape sequence".to_owned()))
+    }
+}
@@ -0,0 +1,357 @@
+//! Tokens of Solidity and inline Yul.
+
+use getters::Getters;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum TokenKind {
+    /// Identifiers and keywords alike.
+    Ident,
+    Number,
+    HexNumber,
+    String,
+    UnicodeString,
+    HexString,
+    Punct,
+    Eof,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Token {
+    pub(super) kind: TokenKind,
+    pub(super) start: usize,
+    pub(super) end: usize,
+    /// NatSpec comment right before the token, as an index into [`Lexed::docs`].
+    pub(super) doc: Option<usize>,
+}
+
+/// A `///` run or a `/** */` block.
+#[derive(Debug, Clone)]
+pub(super) struct Doc {
+    pub(super) start: usize,
+    pub(super) end: usize,
+    pub(super) text: String,
+}
+
+/// A `//` or `/* */` comment, NatSpec included, by its byte offsets in the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Getters)]
+pub struct Comment {
+    #[copy]
+    start: usize,
+    /// Exclusive, a line comment ends before its newline.
+    #[copy]
+    end: usize,
+    #[copy]
+    kind: CommentKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentKind {
+    /// `//` up to the end of the line.
+    Line,
+    /// `/* */`, possibly over several lines.
+    Block,
+}
+
+impl Comment {
+    pub fn range(&self) -> std::ops::Range<usize> {
+        self.start..self.end
+    }
+
+    /// Text of the comment in `source`, delimiters included.
+    pub fn text<'s>(&self, source: &'s str) -> &'s str {
+        &source[self.range()]
+    }
+}
+
+pub(super) struct Lexed {
+    pub(super) tokens: Vec<Token>,
+    pub(super) docs: Vec<Doc>,
+    pub(super) comments: Vec<Comment>,
+    /// Value of the first `SPDX-License-Identifier` comment.
+    pub(super) license: Option<String>,
+}
+
+/// Longest first, so the first match wins.
+const PUNCTUATION: &[&str] = &[
+    ">>>=", "<<=", ">>=", ">>>", "**", "==", "!=", "<=", ">=", "&&", "||", "++", "--", "+=", "-=",
+    "*=", "/=", "%=", "|=", "&=", "^=", "<<", ">>", "=>", "->", ":=", "(", ")", "{", "}", "[", "]",
+    ";", ",", ".", "?", ":", "=", "+", "-", "*", "/", "%", "!", "~", "&", "|", "^", "<", ">", "@",
+];
+
+const LICENSE_TAG: &str = "SPDX-License-Identifier:";
+
+pub(super) fn lex(source: &str) -> Result<Lexed, (usize, String)> {
+    let mut lexer = Lexer {
+        source,
+        bytes: source.as_bytes(),
+        pos: 0,
+        tokens: vec![],
+        docs: vec![],
+        comments: vec![],
+        pending_doc: None,
+        license: None,
+    };
+    lexer.run()?;
+    Ok(Lexed {
+        tokens: lexer.tokens,
+        docs: lexer.docs,
+        comments: lexer.comments,
+        license: lexer.license,
+    })
+}
+
+struct Lexer<'s> {
+    source: &'s str,
+    bytes: &'s [u8],
+    pos: usize,
+    tokens: Vec<Token>,
+    docs: Vec<Doc>,
+    comments: Vec<Comment>,
+    pending_doc: Option<usize>,
+    license: Option<String>,
+}
+
+impl Lexer<'_> {
+    fn run(&mut self) -> Result<(), (usize, String)> {
+        loop {
+            self.skip_trivia()?;
+            let start = self.pos;
+            let Some(&c) = self.bytes.get(start) else {
+                self.push(TokenKind::Eof, start);
+                return Ok(());
+            };
+
+            let kind = if is_ident_start(c) {
+                self.eat_while(is_ident_part);
+                match (&self.source[start..self.pos], self.bytes.get(self.pos)) {
+                    ("hex", Some(b'"' | b'\'')) => {
+                        self.string()?;
+                        TokenKind::HexString
+                    }
+                    ("unicode", Some(b'"' | b'\'')) => {
+                        self.string()?;
+                        TokenKind::UnicodeString
+                    }
+                    _ => TokenKind::Ident,
+                }
+            } else if c == b'0' && matches!(self.bytes.get(start + 1), Some(b'x' | b'X')) {
+                self.pos += 2;
+                self.eat_while(|c| c.is_ascii_hexdigit() || c == b'_');
+                TokenKind::HexNumber
+            } else if c.is_ascii_digit() || (c == b'.' && self.digit_at(start + 1)) {
+                self.number();
+                TokenKind::Number
+            } else if c == b'"' || c == b'\'' {
+                self.string()?;
+                TokenKind::String
+            } else if let Some(punct) = PUNCTUATION
+                .iter()
+                .find(|punct| self.source[start..].starts_with(**punct))
+            {
+                self.pos += punct.len();
+                TokenKind::Punct
+            } else {
+                let c = self.source[start..].chars().next().unwrap_or_default();
+                return Err((start, format!("unexpected character `{c}`")));
+            };
+            self.push(kind, start);
+        }
+    }
+
+    fn push(&mut self, kind: TokenKind, start: usize) {
+        self.tokens.push(Token {
+            kind,
+            start,
+            end: self.pos,
+            doc: self.pending_doc.take(),
+        });
+    }
+
+    fn digit_at(&self, pos: usize) -> bool {
+        self.bytes.get(pos).is_some_and(u8::is_ascii_digit)
+    }
+
+    fn eat_while(&mut self, f: impl Fn(u8) -> bool) {
+        while self.bytes.get(self.pos).is_some_and(|&c| f(c)) {
+            self.pos += 1;
+        }
+    }
+
+    fn number(&mut self) {
+        self.eat_while(|c| c.is_ascii_digit() || c == b'_');
+        if self.bytes.get(self.pos) == Some(&b'.') && self.digit_at(self.pos + 1) {
+            self.pos += 1;
+            self.eat_while(|c| c.is_ascii_digit() || c == b'_');
+        }
+        if matches!(self.bytes.get(self.pos), Some(b'e' | b'E')) {
+            let sign = usize::from(self.bytes.get(self.pos + 1) == Some(&b'-'));
+            if self.digit_at(self.pos + 1 + sign) {
+                self.pos += 1 + sign;
+                self.eat_while(|c| c.is_ascii_digit() || c == b'_');
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<(), (usize, String)> {
+        let start = self.pos;
+        let quote = self.bytes[start];
+        self.pos += 1;
+        loop {
+            match self.bytes.get(self.pos) {
+                Some(&c) if c == quote => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some(b'\\') => self.pos += 2,
+                Some(b'\n') | None => return Err((start, "unterminated string literal".into())),
+                Some(_) => self.pos += 1,
+            }
+        }
+    }
+
+    fn skip_trivia(&mut self) -> Result<(), (usize, String)> {
+        loop {
+            self.eat_while(|c| c.is_ascii_whitespace());
+            let rest = &self.source[self.pos..];
+            if rest.starts_with("//") {
+                let start = self.pos;
+                self.eat_while(|c| c != b'\n');
+                self.comment(start, CommentKind::Line);
+                let comment = &self.source[start..self.pos];
+                self.license_from(comment);
+                if comment.starts_with("///") && !comment.starts_with("////") {
+                    self.line_doc(start);
+                }
+            } else if let Some(body) = rest.strip_prefix("/*") {
+                let start = self.pos;
+                let Some(len) = body.find("*/") else {
+                    return Err((start, "unterminated comment".into()));
+                };
+                self.pos += len + 4;
+                self.comment(start, CommentKind::Block);
+                let comment = &self.source[start..self.pos];
+                self.license_from(comment);
+                if comment.starts_with("/**") && comment != "/**/" && !comment.starts_with("/***") {
+                    self.block_doc(start);
+                }
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn comment(&mut self, start: usize, kind: CommentKind) {
+        self.comments.push(Comment {
+            start,
+            end: self.pos,
+            kind,
+        });
+    }
+
+    fn license_from(&mut self, comment: &str) {
+        if self.license.is_some() {
+            return;
+        }
+        if let Some((_, rest)) = comment.split_once(LICENSE_TAG) {
+            let license = rest.lines().next().unwrap_or_default();
+            let license = license.trim().trim_end_matches("*/").trim();
+            self.license = Some(license.to_owned());
+        }
+    }
+
+    /// Extends the pending `///` run if only whitespace separates the two.
+    fn line_doc(&mut self, start: usize) {
+        let line = &self.source[start + 3..self.pos];
+        if let Some(index) = self.pending_doc
+            && self.source[self.docs[index].start..self.docs[index].end].starts_with("///")
+            && self.source[self.docs[index].end..start].trim().is_empty()
+        {
+            let doc = &mut self.docs[index];
+            doc.end = self.pos;
+            doc.text.push('\n');
+            doc.text.push_str(line.trim_end());
+            return;
+        }
+
+        self.docs.push(Doc {
+            start,
+            end: self.pos,
+            text: line.trim().to_owned(),
+        });
+        self.pending_doc = Some(self.docs.len() - 1);
+    }
+
+    /// Inner lines lose their leading `*`, like solc reports them.
+    fn block_doc(&mut self, start: usize) {
+        let inner = &self.source[start + 3..self.pos - 2];
+        let lines: Vec<&str> = inner
+            .lines()
+            .map(|line| {
+                let trimmed = line.trim_start();
+                trimmed.strip_prefix('*').unwrap_or(trimmed).trim_end()
+            })
+            .collect();
+        let first = lines.iter().position(|line| !line.trim().is_empty());
+        let last = lines.iter().rposition(|line| !line.trim().is_empty());
+        let text = match (first, last) {
+            (Some(first), Some(last)) => {
+                let mut lines = lines[first..=last].to_vec();
+                lines[0] = lines[0].trim_start();
+                lines.join("\n")
+            }
+            _ => String::new(),
+        };
+
+        self.docs.push(Doc {
+            start,
+            end: self.pos,
+            text,
+        });
+        self.pending_doc = Some(self.docs.len() - 1);
+    }
+}
+
+fn is_ident_start(c: u8) -> bool {
+    c.is_ascii_alphabetic() || c == b'_' || c == b'$'
+}
+
+fn is_ident_part(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c == b'$'
+}
+
+/// Decodes the body of a quoted literal, `None` on an invalid escape.
+pub(super) fn unescape(quoted: &str) -> Option<Vec<u8>> {
+    let body = &quoted[1..quoted.len() - 1];
+    let mut out = Vec::with_capacity(body.len());
+    let mut chars = body.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        match chars.next()? {
+            'n' => out.push(b'\n'),
+            'r' => out.push(b'\r'),
+            't' => out.push(b'\t'),
+            '\\' => out.push(b'\\'),
+            '\'' => out.push(b'\''),
+            '"' => out.push(b'"'),
+            '\n' => {}
+            'x' => {
+                let hex: String = chars.by_ref().take(2).collect();
+                out.push(u8::from_str_radix(&hex, 16).ok()?);
+            }
+            'u' => {
+                let hex: String = chars.by_ref().take(4).collect();
+                let code = u32::from_str_radix(&hex, 16).ok()?;
+                let c = char::from_u32(code)?;
+                let mut buf = [0; 4];
+                out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            }
+            _ => return None,
+        }
+    }
+    Some(out)
+}
@@ -0,0 +1,428 @@
+//! Native Solidity parser, for when there is source but no compiler to produce an AST.
+//!
+//! Builds the compact AST solc would report, then deserializes it into the regular
+//! [`crate::ast_specs`] types, so every visitor and the serializer work on the result.
+//! Only syntax is known: `src`, `nameLocation` and `scope` are filled in and ids are
+//! handed out sequentially, while semantic fields are left empty. `typeDescriptions`
+//! hold no strings, `referencedDeclaration` is `null` (or `-1` where the field is not
+//! optional) and `sourceUnit` of imports is `-1`.
+
+mod expressions;
+mod items;
+mod lexer;
+mod statements;
+mod yul;
+
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value, json};
+
+use crate::{
+    AstParserResult, ErrorKind,
+    ast_source::{ColumnEncoding, LineIndex},
+    ast_specs::{ContractDefinition, Expression, FunctionDefinition, SourceUnit, Statement},
+    utils::json_locator,
+};
+
+use lexer::{Doc, Token, TokenKind};
+
+pub use lexer::{Comment, CommentKind};
+
+/// Parses `source` with the default [`SolidityParser`].
+pub fn parse(source: &str) -> AstParserResult<SourceUnit> {
+    SolidityParser::new().parse_source_unit(source)
+}
+
+/// Every comment of `source` in source order, for printing them back with the AST.
+pub fn comments(source: &str) -> AstParserResult<Vec<Comment>> {
+    let lexed = lexer::lex(source)
+        .map_err(|(offset, message)| syntax_error(source, "", offset, message))?;
+    Ok(lexed.comments)
+}
+
+/// Reads and parses a `.sol` file, using `path` as the unit's `absolutePath`.
+pub fn parse_file<P: AsRef<Path>>(path: P) -> AstParserResult<SourceUnit> {
+    let path = path.as_ref();
+    let source = std::fs::read_to_string(path)?;
+    SolidityParser::new()
+        .with_absolute_path(path.to_string_lossy())
+        .parse_source_unit(&source)
+}
+
+/// Settings for parsing one file.
+#[derive(Debug, Clone)]
+pub struct SolidityParser {
+    absolute_path: String,
+    file_index: usize,
+    first_id: isize,
+}
+
+impl Default for SolidityParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SolidityParser {
+    pub fn new() -> Self {
+        Self {
+            absolute_path: String::new(),
+            file_index: 0,
+            first_id: 1,
+        }
+    }
+
+    pub fn with_absolute_path<S: Into<String>>(mut self, absolute_path: S) -> Self {
+        self.absolute_path = absolute_path.into();
+        self
+    }
+
+    /// Index written into every `src`, so several parsed files can share one id space.
+    pub fn with_file_index(mut self, file_index: usize) -> Self {
+        self.file_index = file_index;
+        self
+    }
+
+    /// Id of the first node; the rest count up from it.
+    pub fn with_first_id(mut self, first_id: isize) -> Self {
+        self.first_id = first_id;
+        self
+    }
+
+    /// Parses a whole file. The unit keeps `source` as its source text.
+    pub fn parse_source_unit(&self, source: &str) -> AstParserResult<SourceUnit> {
+        let mut parser = Parser::new(self, source)?;
+        let unit = parser.source_unit()?;
+        let unit: SourceUnit = parser.deserialize(unit)?;
+        Ok(unit.with_source(source.to_owned()))
+    }
+
+    /// Parses a single contract, interface or library.
+    pub fn parse_contract(&self, source: &str) -> AstParserResult<ContractDefinition> {
+        let mut parser = Parser::new(self, source)?;
+        parser.scope_of_file();
+        let contract = parser.contract()?;
+        parser.expect_eof()?;
+        parser.deserialize(contract)
+    }
+
+    /// Parses a function as if it were declared inside a contract.
+    pub fn parse_function(&self, source: &str) -> AstParserResult<FunctionDefinition> {
+        let mut parser = Parser::new(self, source)?;
+        parser.scope_of_file();
+        parser.contract = Some(String::new());
+        let function = parser.function()?;
+        parser.expect_eof()?;
+        parser.deserialize(function)
+    }
+
+    /// Parses a single statement, including its terminating `;`.
+    pub fn parse_statement(&self, source: &str) -> AstParserResult<Statement> {
+        let mut parser = Parser::new(self, source)?;
+        parser.scope_of_file();
+        let statement = parser.statement()?;
+        parser.expect_eof()?;
+        parser.deserialize(statement)
+    }
+
+    pub fn parse_expression(&self, source: &str) -> AstParserResult<Expression> {
+        let mut parser = Parser::new(self, source)?;
+        let expression = parser.expression()?;
+        parser.expect_eof()?;
+        parser.deserialize(expression)
+    }
+}
+
+/// Position to rewind to after a failed attempt at one reading of ambiguous syntax.
+#[derive(Clone, Copy)]
+struct Checkpoint {
+    pos: usize,
+    next_id: isize,
+}
+
+struct Parser<'s> {
+    source: &'s str,
+    absolute_path: &'s str,
+    file_index: usize,
+    tokens: Vec<Token>,
+    docs: Vec<Doc>,
+    license: Option<String>,
+    pos: usize,
+    next_id: isize,
+    /// Ids of the enclosing source unit, contract, function and blocks.
+    scopes: Vec<isize>,
+    /// Return parameter list of each enclosing function.
+    return_parameters: Vec<isize>,
+    /// Name of the enclosing contract, for canonical names.
+    contract: Option<String>,
+}
+
+impl<'s> Parser<'s> {
+    fn new(settings: &'s SolidityParser, source: &'s str) -> AstParserResult<Self> {
+        let lexed = lexer::lex(source).map_err(|(offset, message)| {
+            syntax_error(source, &settings.absolute_path, offset, message)
+        })?;
+
+        Ok(Self {
+            source,
+            absolute_path: &settings.absolute_path,
+            file_index: settings.file_index,
+            tokens: lexed.tokens,
+            docs: lexed.docs,
+            license: lexed.license,
+            pos: 0,
+            next_id: settings.first_id,
+            scopes: vec![],
+            return_parameters: vec![],
+            contract: None,
+        })
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self, node: Value) -> AstParserResult<T> {
+        json_locator::from_value(&node).map_err(|error| error.with_file(self.absolute_path))
+    }
+
+    /// Gives fragments parsed on their own a scope of `0`.
+    fn scope_of_file(&mut self) {
+        self.scopes.push(0);
+    }
+
+    fn id(&mut self) -> isize {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    fn scope(&self) -> isize {
+        self.scopes.last().copied().unwrap_or_default()
+    }
+
+    fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            pos: self.pos,
+            next_id: self.next_id,
+        }
+    }
+
+    fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.pos = checkpoint.pos;
+        self.next_id = checkpoint.next_id;
+    }
+
+    // Tokens
+
+    fn peek(&self) -> Token {
+        self.peek_nth(0)
+    }
+
+    fn peek_nth(&self, n: usize) -> Token {
+        let last = self.tokens.len() - 1;
+        self.tokens[(self.pos + n).min(last)]
+    }
+
+    fn text(&self, token: Token) -> &'s str {
+        &self.source[token.start..token.end]
+    }
+
+    fn peek_text(&self) -> &'s str {
+        self.text(self.peek())
+    }
+
+    fn bump(&mut self) -> Token {
+        let token = self.peek();
+        if token.kind != TokenKind::Eof {
+            self.pos += 1;
+        }
+        token
+    }
+
+    /// Whether the next token is the keyword or punctuation `text`.
+    fn at(&self, text: &str) -> bool {
+        self.at_nth(0, text)
+    }
+
+    fn at_nth(&self, n: usize, text: &str) -> bool {
+        let token = self.peek_nth(n);
+        matches!(token.kind, TokenKind::Ident | TokenKind::Punct) && self.text(token) == text
+    }
+
+    fn at_ident(&self) -> bool {
+        self.peek().kind == TokenKind::Ident
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        let found = self.at(text);
+        if found {
+            self.bump();
+        }
+        found
+    }
+
+    fn expect(&mut self, text: &str) -> AstParserResult<Token> {
+        if self.at(text) {
+            Ok(self.bump())
+        } else {
+            Err(self.unexpected(&format!("`{text}`")))
+        }
+    }
+
+    fn expect_ident(&mut self) -> AstParserResult<(String, Token)> {
+        if self.at_ident() {
+            let token = self.bump();
+            Ok((self.text(token).to_owned(), token))
+        } else {
+            Err(self.unexpected("identifier"))
+        }
+    }
+
+    fn expect_eof(&self) -> AstParserResult<()> {
+        if self.peek().kind == TokenKind::Eof {
+            Ok(())
+        } else {
+            Err(self.unexpected("end of input"))
+        }
+    }
+
+    /// Comma separated items up to and including `close`.
+    fn list<T>(
+        &mut self,
+        close: &str,
+        mut item: impl FnMut(&mut Self) -> AstParserResult<T>,
+    ) -> AstParserResult<Vec<T>> {
+        let mut items = vec![];
+        while !self.eat(close) {
+            items.push(item(self)?);
+            if !self.at(close) {
+                self.expect(",")?;
+            }
+        }
+        Ok(items)
+    }
+
+    // Locations
+
+    /// End of the last consumed token.
+    fn prev_end(&self) -> usize {
+        self.pos
+            .checked_sub(1)
+            .map(|prev| self.tokens[prev].end)
+            .unwrap_or_default()
+    }
+
+    fn src(&self, start: usize, end: usize) -> String {
+        format!("{start}:{}:{}", end.saturating_sub(start), self.file_index)
+    }
+
+    /// `src` from `start` to the end of the last consumed token.
+    fn src_from(&self, start: usize) -> String {
+        self.src(start, self.prev_end())
+    }
+
+    fn token_src(&self, token: Token) -> String {
+        self.src(token.start, token.end)
+    }
+
+    /// NatSpec comment attached to the next token.
+    fn documentation(&mut self) -> Value {
+        let Some(doc) = self.peek().doc.map(|index| self.docs[index].clone()) else {
+            return Value::Null;
+        };
+        json!({
+            "id": self.id(),
+            "nodeType": "StructuredDocumentation",
+            "src": self.src(doc.start, doc.end),
+            "text": doc.text,
+        })
+    }
+
+    // Nodes
+
+    /// Completes `fields` into a node spanning from `start` to the last consumed token.
+    fn node(&self, node_type: &str, id: isize, start: usize, fields: Value) -> Value {
+        let mut node = into_map(fields);
+        node.insert("id".to_owned(), id.into());
+        node.insert("nodeType".to_owned(), node_type.into());
+        node.insert("src".to_owned(), self.src_from(start).into());
+        Value::Object(node)
+    }
+
+    /// Like [`Self::node`], adding the flags and types every expression carries.
+    fn expression_node(&self, node_type: &str, id: isize, start: usize, fields: Value) -> Value {
+        let mut node = into_map(self.node(node_type, id, start, fields));
+        for flag in ["isConstant", "isLValue", "isPure", "lValueRequested"] {
+            node.entry(flag).or_insert(false.into());
+        }
+        node.insert("typeDescriptions".to_owned(), type_descriptions());
+        Value::Object(node)
+    }
+
+    fn identifier_path(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        let start = self.peek().start;
+        let (mut name, first) = self.expect_ident()?;
+        let mut name_locations = vec![self.token_src(first)];
+        while self.at(".") && self.peek_nth(1).kind == TokenKind::Ident {
+            self.bump();
+            let (part, token) = self.expect_ident()?;
+            name.push('.');
+            name.push_str(&part);
+            name_locations.push(self.token_src(token));
+        }
+        Ok(self.node(
+            "IdentifierPath",
+            id,
+            start,
+            json!({
+                "name": name,
+                "nameLocations": name_locations,
+                "referencedDeclaration": -1,
+            }),
+        ))
+    }
+
+    // Errors
+
+    fn error(&self, offset: usize, message: String) -> crate::AstParserError {
+        syntax_error(self.source, self.absolute_path, offset, message)
+    }
+
+    fn unexpected(&self, expected: &str) -> crate::AstParserError {
+        let token = self.peek();
+        let found = match token.kind {
+            TokenKind::Eof => "end of input".to_owned(),
+            _ => format!("`{}`", self.text(token)),
+        };
+        self.error(token.start, format!("expected {expected}, found {found}"))
+    }
+}
+
+fn syntax_error(source: &str, file: &str, offset: usize, message: String) -> crate::AstParserError {
+    let position = LineIndex::new(source).line_column(offset, ColumnEncoding::Utf8);
+    ErrorKind::SyntaxError {
+        message,
+        file: (!file.is_empty()).then(|| file.to_owned()),
+        offset,
+        line: position.map(|position| position.line()).unwrap_or_default(),
+        column: position
+            .map(|position| position.column())
+            .unwrap_or_default(),
+    }
+    .into()
+}
+
+fn into_map(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+fn type_descriptions() -> Value {
+    json!({ "typeIdentifier": null, "typeString": null })
+}
+
+/// Hex of the bytes of `value`, as solc reports it in `hexValue`.
+fn hex(value: &[u8]) -> String {
+    value.iter().map(|b| format!("{b:02x}")).collect()
+}
@@ -0,0 +1,385 @@
+//! Statements and blocks.
+
+use serde_json::{Value, json};
+
+use super::{Parser, TokenKind, items::VariableKind};
+use crate::AstParserResult;
+
+impl Parser<'_> {
+    pub(super) fn block(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        let start = self.expect("{")?.start;
+        let statements = self.statements(id)?;
+        Ok(self.node("Block", id, start, json!({ "statements": statements })))
+    }
+
+    /// Statements up to the closing `}`, with `id` as the scope of their declarations.
+    fn statements(&mut self, id: isize) -> AstParserResult<Vec<Value>> {
+        self.scopes.push(id);
+        let mut statements = vec![];
+        while !self.eat("}") {
+            statements.push(self.statement()?);
+        }
+        self.scopes.pop();
+        Ok(statements)
+    }
+
+    pub(super) fn statement(&mut self) -> AstParserResult<Value> {
+        let start = self.peek().start;
+        match self.peek_text() {
+            "{" if self.peek().kind == TokenKind::Punct => self.block(),
+            "if" => self.if_statement(),
+            "for" => self.for_statement(),
+            "while" => {
+                let id = self.id();
+                self.bump();
+                let condition = self.parenthesized()?;
+                let body = self.statement()?;
+                Ok(self.node(
+                    "WhileStatement",
+                    id,
+                    start,
+                    json!({ "body": body, "condition": condition }),
+                ))
+            }
+            "do" => {
+                let id = self.id();
+                self.bump();
+                let body = self.statement()?;
+                self.expect("while")?;
+                let condition = self.parenthesized()?;
+                self.expect(";")?;
+                Ok(self.node(
+                    "DoWhileStatement",
+                    id,
+                    start,
+                    json!({ "body": body, "condition": condition }),
+                ))
+            }
+            keyword @ ("break" | "continue") => {
+                let id = self.id();
+                self.bump();
+                self.expect(";")?;
+                let node_type = if keyword == "break" {
+                    "Break"
+                } else {
+                    "Continue"
+                };
+                Ok(self.node(node_type, id, start, json!({})))
+            }
+            "_" if self.at_nth(1, ";") => {
+                let id = self.id();
+                self.bump();
+                self.bump();
+                Ok(self.node("PlaceholderStatement", id, start, json!({})))
+            }
+            "return" => {
+                let id = self.id();
+                self.bump();
+                let expression = if self.at(";") {
+                    Value::Null
+                } else {
+                    self.expression()?
+                };
+                self.expect(";")?;
+                Ok(self.node(
+                    "Return",
+                    id,
+                    start,
+                    json!({
+                        "expression": expression,
+                        "functionReturnParameters": self.return_parameters.last(),
+                    }),
+                ))
+            }
+            "emit" => {
+                let id = self.id();
+                self.bump();
+                let event_call = self.call("emit")?;
+                self.expect(";")?;
+                Ok(self.node(
+                    "EmitStatement",
+                    id,
+                    start,
+                    json!({ "eventCall": event_call }),
+                ))
+            }
+            // `revert(...)` is a plain call, `revert Error(...)` a statement
+            "revert" if self.peek_nth(1).kind == TokenKind::Ident => {
+                let id = self.id();
+                self.bump();
+                let error_call = self.call("revert")?;
+                self.expect(";")?;
+                Ok(self.node(
+                    "RevertStatement",
+                    id,
+                    start,
+                    json!({ "errorCall": error_call }),
+                ))
+            }
+            "try" => self.try_statement(),
+            "assembly" => self.inline_assembly(),
+            "unchecked" => {
+                let id = self.id();
+                self.bump();
+                self.expect("{")?;
+                let statements = self.statements(id)?;
+                Ok(self.node(
+                    "UncheckedBlock",
+                    id,
+                    start,
+                    json!({ "statements": statements }),
+                ))
+            }
+            _ => self.simple_statement(),
+        }
+    }
+
+    fn parenthesized(&mut self) -> AstParserResult<Value> {
+        self.expect("(")?;
+        let expression = self.expression()?;
+        self.expect(")")?;
+        Ok(expression)
+    }
+
+    /// Expression that has to be a function call, as after `emit` or `try`.
+    fn call(&mut self, context: &str) -> AstParserResult<Value> {
+        let start = self.peek().start;
+        let call = self.expression()?;
+        if call.get("nodeType").and_then(Value::as_str) == Some("FunctionCall") {
+            Ok(call)
+        } else {
+            Err(self.error(start, format!("expected function call after `{context}`")))
+        }
+    }
+
+    fn if_statement(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        let start = self.expect("if")?.start;
+        let condition = self.parenthesized()?;
+        let true_body = self.statement()?;
+        let false_body = if self.eat("else") {
+            self.statement()?
+        } else {
+            Value::Null
+        };
+        Ok(self.node(
+            "IfStatement",
+            id,
+            start,
+            json!({
+                "condition": condition,
+                "falseBody": false_body,
+                "trueBody": true_body,
+            }),
+        ))
+    }
+
+    fn for_statement(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        let start = self.expect("for")?.start;
+        self.expect("(")?;
+        // Declarations in the header are scoped to the loop
+        self.scopes.push(id);
+        let initialization_expression = if self.eat(";") {
+            Value::Null
+        } else {
+            self.simple_statement()?
+        };
+        let condition = if self.at(";") {
+            Value::Null
+        } else {
+            self.expression()?
+        };
+        self.expect(";")?;
+        let loop_expression = if self.at(")") {
+            Value::Null
+        } else {
+            let id = self.id();
+            let start = self.peek().start;
+            let expression = self.expression()?;
+            self.node(
+                "ExpressionStatement",
+                id,
+                start,
+                json!({ "expression": expression }),
+            )
+        };
+        self.expect(")")?;
+        let body = self.statement()?;
+        self.scopes.pop();
+
+        Ok(self.node(
+            "ForStatement",
+            id,
+            start,
+            json!({
+                "body": body,
+                "condition": condition,
+                "initializationExpression": initialization_expression,
+                "loopExpression": loop_expression,
+            }),
+        ))
+    }
+
+    /// `try call [returns (...)] { } catch [Name](...) { } ...`
+    fn try_statement(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        let start = self.expect("try")?.start;
+        let mut external_call = self.call("try")?;
+        external_call["tryCall"] = true.into();
+
+        let clause_id = self.id();
+        let clause_start = self.peek().start;
+        self.scopes.push(clause_id);
+        let parameters = if self.eat("returns") {
+            self.parameter_list(VariableKind::Parameter)?
+        } else {
+            Value::Null
+        };
+        let block = self.block()?;
+        self.scopes.pop();
+        let mut clauses = vec![self.node(
+            "TryCatchClause",
+            clause_id,
+            clause_start,
+            json!({ "block": block, "errorName": "", "parameters": parameters }),
+        )];
+
+        while self.at("catch") {
+            let clause_id = self.id();
+            let clause_start = self.bump().start;
+            self.scopes.push(clause_id);
+            let error_name = if self.at_ident() {
+                self.expect_ident()?.0
+            } else {
+                String::new()
+            };
+            let parameters = if self.at("(") {
+                self.parameter_list(VariableKind::Parameter)?
+            } else {
+                Value::Null
+            };
+            let block = self.block()?;
+            self.scopes.pop();
+            clauses.push(self.node(
+                "TryCatchClause",
+                clause_id,
+                clause_start,
+                json!({ "block": block, "errorName": error_name, "parameters": parameters }),
+            ));
+        }
+        if clauses.len() == 1 {
+            return Err(self.unexpected("`catch`"));
+        }
+
+        Ok(self.node(
+            "TryStatement",
+            id,
+            start,
+            json!({ "clauses": clauses, "externalCall": external_call }),
+        ))
+    }
+
+    /// `assembly ["evmasm"] [("memory-safe", ...)] { ... }`
+    fn inline_assembly(&mut self) -> AstParserResult<Value> {
+        let id = self.id();
+        let start = self.expect("assembly")?.start;
+        let dialect = if self.peek().kind == TokenKind::String {
+            Some(self.yul_string_value()?)
+        } else {
+            None
+        };
+        let flags = if self.eat("(") {
+            let flags = self.list(")", Self::yul_string_value)?;
+            Value::from(flags)
+        } else {
+            Value::Null
+        };
+        let ast = self.yul_block()?;
+
+        Ok(self.node(
+            "InlineAssembly",
+            id,
+            start,
+            json!({
+                "AST": ast,
+                "dialect": dialect,
+                "externalReferences": [],
+                "flags": flags,
+            }),
+        ))
+    }
+
+    /// Variable declaration or expression statement, which only tell apart after the type.
+    fn simple_statement(&mut self) -> AstParserResult<Value> {
+        if let Some(statement) = self.try_variable_declaration_statement()? {
+            return Ok(statement);
+        }
+
+        let id = self.id();
+        let start = self.peek().start;
+        let expression = self.expression()?;
+        self.expect(";")?;
+        Ok(self.node(
+            "ExpressionStatement",
+            id,
+            start,
+            json!({ "expression": expression }),
+        ))
+    }
+
+    fn try_variable_declaration_statement(&mut self) -> AstParserResult<Option<Value>> {
+        let checkpoint = self.checkpoint();
+        let id = self.id();
+        let start = self.peek().start;
+
+        let declarations = if self.eat("(") {
+            let declarations = self.list(")", |parser| {
+                if parser.at(",") || parser.at(")") {
+                    Ok(Value::Null)
+                } else {
+                    parser.variable_declaration(VariableKind::Local)
+                }
+            });
+            match declarations {
+                Ok(declarations) if self.at("=") => declarations,
+                _ => {
+                    self.rewind(checkpoint);
+                    return Ok(None);
+                }
+            }
+        } else {
+            // Only `=` or `;` commit to a declaration, anything else was an expression
+            match self.variable_declaration(VariableKind::Local) {
+                Ok(declaration) if self.at("=") || self.at(";") => vec![declaration],
+                _ => {
+                    self.rewind(checkpoint);
+                    return Ok(None);
+                }
+            }
+        };
+
+        let initial_value = if self.eat("=") {
+            self.expression()?
+        } else {
+            Value::Null
+        };
+        self.expect(";")?;
+
+        let assignments: Vec<Value> = declarations
+            .iter()
+            .map(|declaration| declaration.get("id").cloned().unwrap_or(Value::Null))
+            .collect();
+        Ok(Some(self.node(
+            "VariableDeclarationStatement",
+            id,
+            start,
+            json!({
+                "assignments": assignments,
+                "declarations": declarations,
+                "initialValue": initial_value,
+            }),
+        )))
+    }
+}
@@ -0,0 +1,265 @@
+//! Inline assembly. Yul nodes have no ids, only `src` and `nativeSrc`.
+
+use serde_json::{Map, Value, json};
+
+use super::{Parser, TokenKind, hex, into_map, lexer::unescape};
+use crate::AstParserResult;
+
+impl Parser<'_> {
+    fn yul_node(&self, node_type: &str, start: usize, fields: Value) -> Value {
+        let src = self.src_from(start);
+        let mut node = into_map(fields);
+        node.insert("nativeSrc".to_owned(), src.clone().into());
+        node.insert("nodeType".to_owned(), node_type.into());
+        node.insert("src".to_owned(), src.into());
+        Value::Object(node)
+    }
+
+    pub(super) fn yul_block(&mut self) -> AstParserResult<Value> {
+        let start = self.expect("{")?.start;
+        let mut statements = vec![];
+        while !self.eat("}") {
+            statements.push(self.yul_statement()?);
+        }
+        Ok(self.yul_node("YulBlock", start, json!({ "statements": statements })))
+    }
+
+    fn yul_statement(&mut self) -> AstParserResult<Value> {
+        let start = self.peek().start;
+        match self.peek_text() {
+            "{" if self.peek().kind == TokenKind::Punct => self.yul_block(),
+            "let" => {
+                self.bump();
+                let mut variables = vec![self.yul_typed_name()?];
+                while self.eat(",") {
+                    variables.push(self.yul_typed_name()?);
+                }
+                let value = if self.eat(":=") {
+                    self.yul_expression()?
+                } else {
+                    Value::Null
+                };
+                Ok(self.yul_node(
+                    "YulVariableDeclaration",
+                    start,
+                    json!({ "value": value, "variables": variables }),
+                ))
+            }
+            "function" => {
+                self.bump();
+                let name = self.expect_ident()?.0;
+                self.expect("(")?;
+                let parameters = self.list(")", Self::yul_typed_name)?;
+                let mut return_variables = vec![];
+                if self.eat("->") {
+                    return_variables.push(self.yul_typed_name()?);
+                    while self.eat(",") {
+                        return_variables.push(self.yul_typed_name()?);
+                    }
+                }
+                let body = self.yul_block()?;
+                Ok(self.yul_node(
+                    "YulFunctionDefinition",
+                    start,
+                    json!({
+                        "body": body,
+                        "name": name,
+                        "parameters": parameters,
+                        "returnVariables": return_variables,
+                    }),
+                ))
+            }
+            "if" => {
+                self.bump();
+                let condition = self.yul_expression()?;
+                let body = self.yul_block()?;
+                Ok(self.yul_node(
+                    "YulIf",
+                    start,
+                    json!({ "body": body, "condition": condition }),
+                ))
+            }
+            "switch" => self.yul_switch(),
+            "for" => {
+                self.bump();
+                let pre = self.yul_block()?;
+                let condition = self.yul_expression()?;
+                let post = self.yul_block()?;
+                let body = self.yul_block()?;
+                Ok(self.yul_node(
+                    "YulForLoop",
+                    start,
+                    json!({ "body": body, "condition": condition, "post": post, "pre": pre }),
+                ))
+            }
+            keyword @ ("break" | "continue" | "leave") => {
+                self.bump();
+                let node_type = match keyword {
+                    "break" => "YulBreak",
+                    "continue" => "YulContinue",
+                    _ => "YulLeave",
+                };
+                Ok(self.yul_node(node_type, start, json!({})))
+            }
+            _ => {
+                let expression = self.yul_expression()?;
+                if !self.at(",") && !self.at(":=") {
+                    return Ok(self.yul_node(
+                        "YulExpressionStatement",
+                        start,
+                        json!({ "expression": expression }),
+                    ));
+                }
+
+                let mut variable_names = vec![expression];
+                while self.eat(",") {
+                    variable_names.push(self.yul_identifier()?);
+                }
+                self.expect(":=")?;
+                let value = self.yul_expression()?;
+                if let Some(call) = variable_names
+                    .iter()
+                    .find(|name| name["nodeType"] != "YulIdentifier")
+                {
+                    let offset = call["src"]
+                        .as_str()
+                        .and_then(|src| src.split(':').next()?.parse().ok())
+                        .unwrap_or(start);
+                    return Err(self.error(offset, "can only assign to identifiers".to_owned()));
+                }
+                Ok(self.yul_node(
+                    "YulAssignment",
+                    start,
+                    json!({ "value": value, "variableNames": variable_names }),
+                ))
+            }
+        }
+    }
+
+    fn yul_switch(&mut self) -> AstParserResult<Value> {
+        let start = self.expect("switch")?.start;
+        let expression = self.yul_expression()?;
+        let mut cases = vec![];
+        loop {
+            let case_start = self.peek().start;
+            let value = if self.eat("case") {
+                self.yul_literal()?
+            } else if self.eat("default") {
+                Value::from("default")
+            } else {
+                break;
+            };
+            let body = self.yul_block()?;
+            cases.push(self.yul_node(
+                "YulCase",
+                case_start,
+                json!({ "body": body, "value": value }),
+            ));
+        }
+        if cases.is_empty() {
+            return Err(self.unexpected("`case` or `default`"));
+        }
+        Ok(self.yul_node(
+            "YulSwitch",
+            start,
+            json!({ "cases": cases, "expression": expression }),
+        ))
+    }
+
+    fn yul_expression(&mut self) -> AstParserResult<Value> {
+        match self.peek().kind {
+            TokenKind::Ident if !matches!(self.peek_text(), "true" | "false") => {
+                let start = self.peek().start;
+                let identifier = self.yul_identifier()?;
+                if !self.eat("(") {
+                    return Ok(identifier);
+                }
+                let arguments = self.list(")", Self::yul_expression)?;
+                Ok(self.yul_node(
+                    "YulFunctionCall",
+                    start,
+                    json!({ "arguments": arguments, "functionName": identifier }),
+                ))
+            }
+            _ => self.yul_literal(),
+        }
+    }
+
+    /// Identifier, dotted like `x.slot` when written without spaces.
+    fn yul_identifier(&mut self) -> AstParserResult<Value> {
+        let start = self.peek().start;
+        let name = self.yul_name()?;
+        Ok(self.yul_node("YulIdentifier", start, json!({ "name": name })))
+    }
+
+    fn yul_name(&mut self) -> AstParserResult<String> {
+        let (mut name, mut last) = self.expect_ident()?;
+        while self.at(".")
+            && self.peek().start == last.end
+            && self.peek_nth(1).kind == TokenKind::Ident
+            && self.peek_nth(1).start == self.peek().end
+        {
+            self.bump();
+            let (part, token) = self.expect_ident()?;
+            name.push('.');
+            name.push_str(&part);
+            last = token;
+        }
+        Ok(name)
+    }
+
+    fn yul_typed_name(&mut self) -> AstParserResult<Value> {
+        let start = self.peek().start;
+        let name = self.yul_name()?;
+        let mut fields = Map::new();
+        fields.insert("name".to_owned(), name.into());
+        if self.eat(":") {
+            fields.insert("type".to_owned(), self.yul_name()?.into());
+        } else {
+            fields.insert("type".to_owned(), "".into());
+        }
+        Ok(self.yul_node("YulTypedName", start, Value::Object(fields)))
+    }
+
+    fn yul_literal(&mut self) -> AstParserResult<Value> {
+        let token = self.peek();
+        let text = self.text(token);
+        let fields = match token.kind {
+            TokenKind::Number | TokenKind::HexNumber => {
+                json!({ "kind": "number", "type": "", "value": text })
+            }
+            TokenKind::Ident if matches!(text, "true" | "false") => {
+                json!({ "kind": "bool", "type": "", "value": text })
+            }
+            TokenKind::String => {
+                let bytes = unescape(text)
+                    .ok_or_else(|| self.error(token.start, "invalid escape sequence".to_owned()))?;
+                json!({
+                    "hexValue": hex(&bytes),
+                    "kind": "string",
+                    "type": "",
+                    "value": String::from_utf8_lossy(&bytes),
+                })
+            }
+            TokenKind::HexString => {
+                let digits = text[4..text.len() - 1].replace('_', "");
+                json!({ "hexValue": digits, "kind": "string", "type": "" })
+            }
+            _ => return Err(self.unexpected("Yul literal")),
+        };
+        self.bump();
+        Ok(self.yul_node("YulLiteral", token.start, fields))
+    }
+
+    /// Dialect or flag of an `assembly "evmasm" (...)` block.
+    pub(super) fn yul_string_value(&mut self) -> AstParserResult<String> {
+        let token = self.peek();
+        if token.kind != TokenKind::String {
+            return Err(self.unexpected("string literal"));
+        }
+        self.bump();
+        let bytes = unescape(self.text(token))
+            .ok_or_else(|| self.error(token.start, "invalid escape sequence".to_owned()))?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
@@ -0,0 +1,193 @@
+//! Comments of the original source, printed back next to the nodes they were written by.
+
+use std::ops::Range;
+
+use crate::{
+    AstParserResult,
+    ast_parse::{self, CommentKind},
+};
+
+const LICENSE_TAG: &str = "SPDX-License-Identifier:";
+
+#[derive(Debug, Clone)]
+pub(super) struct Comments {
+    comments: Vec<SourceComment>,
+    cursor: CommentCursor,
+}
+
+/// Printing progress, saved and restored around output rendered aside.
+#[derive(Debug, Clone, Default)]
+pub(super) struct CommentCursor {
+    /// First comment not printed yet.
+    next: usize,
+    /// Comments to print at the end of the current line.
+    trailing: Vec<usize>,
+}
+
+impl CommentCursor {
+    /// Number of comments queued for the end of the line.
+    pub(super) fn queued(&self) -> usize {
+        self.trailing.len()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SourceComment {
+    range: Range<usize>,
+    text: String,
+    kind: CommentKind,
+    /// Whether only whitespace precedes the comment on its line.
+    own_line: bool,
+    /// Whether code follows the comment on its line.
+    code_after: bool,
+    /// End of the code the comment follows on its line, `;` and `,` left out.
+    code_end: usize,
+    /// Whether only whitespace separates the comment from that code.
+    touches_code: bool,
+}
+
+/// Comment taken to be printed before the code at its position.
+pub(super) struct LeadingComment {
+    pub(super) text: String,
+    pub(super) is_line: bool,
+    /// Whether code followed it on its line, where it stays.
+    pub(super) code_after: bool,
+}
+
+impl Comments {
+    /// Comments of `source`, except the license comment the source unit header prints.
+    pub(super) fn new(source: &str) -> AstParserResult<Self> {
+        let mut license_seen = false;
+        let comments = ast_parse::comments(source)?
+            .into_iter()
+            .filter(|comment| {
+                let license = !license_seen && comment.text(source).contains(LICENSE_TAG);
+                license_seen |= license;
+                !license
+            })
+            .map(|comment| {
+                let line_start = source[..comment.start()]
+                    .rfind('\n')
+                    .map_or(0, |newline| newline + 1);
+                let before = &source[line_start..comment.start()];
+                let code =
+                    before.trim_end_matches(|c: char| c.is_whitespace() || c == ';' || c == ',');
+                let after = &source[comment.range().end..];
+                let line_end = after.find('\n').unwrap_or(after.len());
+                SourceComment {
+                    range: comment.range(),
+                    text: reindent(comment.text(source)),
+                    kind: comment.kind(),
+                    own_line: before.trim().is_empty(),
+                    code_after: !after[..line_end].trim().is_empty(),
+                    code_end: line_start + code.len(),
+                    touches_code: before[code.len()..].trim().is_empty(),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            comments,
+            cursor: CommentCursor::default(),
+        })
+    }
+
+    pub(super) fn cursor(&self) -> &CommentCursor {
+        &self.cursor
+    }
+
+    pub(super) fn set_cursor(&mut self, cursor: CommentCursor) {
+        self.cursor = cursor;
+    }
+
+    /// Sets `cursor`, returning the one it replaces.
+    pub(super) fn replace_cursor(&mut self, cursor: CommentCursor) -> CommentCursor {
+        std::mem::replace(&mut self.cursor, cursor)
+    }
+
+    /// Takes the next comment if it ends by `offset` of the source.
+    pub(super) fn next_before(&mut self, offset: usize) -> Option<LeadingComment> {
+        let comment = self.comments.get(self.cursor.next)?;
+        if comment.range.end > offset {
+            return None;
+        }
+        self.cursor.next += 1;
+        Some(LeadingComment {
+            text: comment.text.clone(),
+            is_line: comment.kind == CommentKind::Line,
+            code_after: comment.code_after,
+        })
+    }
+
+    /// Takes the next comment if it follows code ending at `offset` on the same line.
+    ///
+    /// A block comment right after that code is returned to be printed in place, any other one
+    /// is queued for the end of the line.
+    pub(super) fn trail(&mut self, offset: usize) -> Option<String> {
+        let comment = self.comments.get(self.cursor.next)?;
+        if comment.own_line || comment.code_end > offset || offset > comment.range.start {
+            return None;
+        }
+        self.cursor.next += 1;
+        if comment.kind == CommentKind::Block && comment.touches_code {
+            return Some(comment.text.clone());
+        }
+        self.queue(self.cursor.next - 1);
+        None
+    }
+
+    /// Queues the comment just taken by [`Self::next_before`] for the end of the line.
+    pub(super) fn trail_taken(&mut self) {
+        self.queue(self.cursor.next - 1);
+    }
+
+    fn queue(&mut self, index: usize) {
+        self.cursor.trailing.push(index);
+    }
+
+    /// Number of comments queued for the end of the line.
+    pub(super) fn queued(&self) -> usize {
+        self.cursor.queued()
+    }
+
+    /// Comments queued for the end of the line, emptying the queue.
+    pub(super) fn take_trailing(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.cursor.trailing)
+            .into_iter()
+            .map(|index| self.comments[index].text.clone())
+            .collect()
+    }
+
+    /// Whether the next comment ends by `offset`.
+    pub(super) fn has_before(&self, offset: usize) -> bool {
+        self.comments
+            .get(self.cursor.next)
+            .is_some_and(|comment| comment.range.end <= offset)
+    }
+
+    /// Whether some comment, printed or not, lies within `range` of the source.
+    pub(super) fn covers(&self, range: Range<usize>) -> bool {
+        let first = self
+            .comments
+            .partition_point(|comment| comment.range.start < range.start);
+        self.comments
+            .get(first)
+            .is_some_and(|comment| comment.range.end <= range.end)
+    }
+}
+
+/// `text` with the inner lines of a block comment stripped of their original indentation, the
+/// printer indents them again. Lines starting with `*` keep one space to line up under `/*`.
+fn reindent(text: &str) -> String {
+    let mut lines = text.lines();
+    let mut out = lines.next().unwrap_or_default().trim_end().to_owned();
+    for line in lines {
+        let line = line.trim();
+        out.push('\n');
+        if line.starts_with('*') {
+            out.push(' ');
+        }
+        out.push_str(line);
+    }
+    out
+}
@@ -0,0 +1,129 @@
+use getters::Getters;
+
+/// Layout of the printed Solidity, [`PrinterConfig::forge_fmt`] by default.
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+pub struct PrinterConfig {
+    #[copy]
+    indent_width: usize,
+    #[copy]
+    use_tabs: bool,
+    /// Lines longer than this wrap their parameter and argument lists, `0` for no limit.
+    #[copy]
+    max_line_width: usize,
+    #[copy]
+    brace_style: BraceStyle,
+    /// Between members of a contract and between top-level directives. Consecutive one-line
+    /// members of the same kind, like state variables or imports, stay together.
+    #[copy]
+    blank_lines_between_members: usize,
+    /// Order of the attributes of function headers. Attributes left out follow the listed ones in
+    /// the order of [`PrinterConfig::forge_fmt`].
+    #[return_type = "&[FunctionAttribute]"]
+    attribute_order: Vec<FunctionAttribute>,
+    #[copy]
+    int_types: IntTypes,
+}
+
+/// Where the opening brace of a contract, function, modifier, struct or enum body goes. Blocks
+/// of statements always open on the line of their statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BraceStyle {
+    SameLine,
+    NextLine,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FunctionAttribute {
+    Visibility,
+    StateMutability,
+    Virtual,
+    Override,
+    Modifiers,
+}
+
+/// Spelling of `uint` and `int`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntTypes {
+    /// `uint256`
+    Long,
+    /// `uint`
+    Short,
+    Preserve,
+}
+
+impl Default for PrinterConfig {
+    fn default() -> Self {
+        Self::forge_fmt()
+    }
+}
+
+impl PrinterConfig {
+    /// Layout of `forge fmt` with its default settings.
+    pub fn forge_fmt() -> Self {
+        Self {
+            indent_width: 4,
+            use_tabs: false,
+            max_line_width: 120,
+            brace_style: BraceStyle::SameLine,
+            blank_lines_between_members: 1,
+            attribute_order: vec![
+                FunctionAttribute::Visibility,
+                FunctionAttribute::StateMutability,
+                FunctionAttribute::Virtual,
+                FunctionAttribute::Override,
+                FunctionAttribute::Modifiers,
+            ],
+            int_types: IntTypes::Long,
+        }
+    }
+
+    pub fn with_indent_width(mut self, indent_width: usize) -> Self {
+        self.indent_width = indent_width;
+        self
+    }
+
+    /// Indents with one tab per level, counted as `indent_width` columns.
+    pub fn with_tabs(mut self, use_tabs: bool) -> Self {
+        self.use_tabs = use_tabs;
+        self
+    }
+
+    pub fn with_max_line_width(mut self, max_line_width: usize) -> Self {
+        self.max_line_width = max_line_width;
+        self
+    }
+
+    pub fn with_brace_style(mut self, brace_style: BraceStyle) -> Self {
+        self.brace_style = brace_style;
+        self
+    }
+
+    pub fn with_blank_lines_between_members(mut self, blank_lines: usize) -> Self {
+        self.blank_lines_between_members = blank_lines;
+        self
+    }
+
+    pub fn with_attribute_order<I: IntoIterator<Item = FunctionAttribute>>(
+        mut self,
+        attribute_order: I,
+    ) -> Self {
+        self.attribute_order = attribute_order.into_iter().collect();
+        self
+    }
+
+    pub fn with_int_types(mut self, int_types: IntTypes) -> Self {
+        self.int_types = int_types;
+        self
+    }
+
+    /// [`Self::attribute_order`] completed with the attributes it leaves out.
+    pub(super) fn full_attribute_order(&self) -> Vec<FunctionAttribute> {
+        let mut order = self.attribute_order.clone();
+        for attribute in Self::forge_fmt().attribute_order {
+            if !order.contains(&attribute) {
+                order.push(attribute);
+            }
+        }
+        order
+    }
+}
@@ -0,0 +1,469 @@
+use std::io::{self, Write};
+
+use getters::Getters;
+
+use crate::{AstParserResult, ast_source::SrcLocation, ast_specs::NodeTypeInternalRef};
+
+use super::{
+    PrinterConfig, SourceMap, SourceMapping,
+    comments::{CommentCursor, Comments},
+};
+
+/// State of a print: the config, the indentation level and the position in the output.
+///
+/// Serializers write through [`PrintContext::write`], which indents the lines it starts, so
+/// the context always knows the offset and column of the next byte.
+#[derive(Debug, Clone, Default, Getters)]
+pub struct PrintContext {
+    config: PrinterConfig,
+    /// Indentation level of the node being printed.
+    #[copy]
+    depth: usize,
+    /// Bytes written so far.
+    #[copy]
+    offset: usize,
+    /// Column of the next character, indentation included.
+    #[copy]
+    column: usize,
+    /// Whether the indentation of the current line is still to be written.
+    #[skip_getter]
+    line_start: bool,
+    /// Widest line written so far.
+    #[skip_getter]
+    widest: usize,
+    /// Recorded only when asked for with [`PrintContext::with_source_map`].
+    #[use_as_ref]
+    #[return_type = "Option<&SourceMap>"]
+    source_map: Option<SourceMap>,
+    /// Comments of the original source, printed only when given with
+    /// [`PrintContext::with_comments`].
+    #[skip_getter]
+    comments: Option<Comments>,
+    /// End in the original source of the innermost node being printed.
+    #[skip_getter]
+    scope_end: usize,
+    /// Whether lists are kept on one line whatever their width, while measuring an enclosing one.
+    #[skip_getter]
+    flat: bool,
+}
+
+/// Output rendered aside, to be measured before it is written.
+#[derive(Debug, Clone)]
+pub(super) struct Rendered {
+    bytes: Vec<u8>,
+    /// Widest line of the output, counted from the column it was rendered at.
+    widest: usize,
+    /// Column after the output.
+    column: usize,
+    /// Nodes of the output, from its first byte on.
+    mappings: Vec<SourceMapping>,
+    /// Comments printed so far, the output included.
+    comments: Option<CommentCursor>,
+}
+
+impl Rendered {
+    pub(super) fn ends_with(&self, suffix: &str) -> bool {
+        self.bytes.ends_with(suffix.as_bytes())
+    }
+}
+
+const SPACES: [u8; 64] = [b' '; 64];
+
+impl PrintContext {
+    pub fn new(config: PrinterConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// Records a [`SourceMap`] of everything written through this context.
+    pub fn with_source_map(mut self) -> Self {
+        self.source_map = Some(SourceMap::default());
+        self
+    }
+
+    /// The recorded [`SourceMap`], `None` unless asked for with [`Self::with_source_map`].
+    pub fn into_source_map(self) -> Option<SourceMap> {
+        self.source_map
+    }
+
+    /// Prints the comments of `source` back next to the nodes they were written by.
+    ///
+    /// `source` must be the text the printed nodes were parsed from, their `src` placing the
+    /// comments.
+    pub fn with_comments(mut self, source: &str) -> AstParserResult<Self> {
+        self.comments = Some(Comments::new(source)?);
+        Ok(self)
+    }
+
+    /// Runs `f`, the printing of `node`, and maps the bytes it writes to the node.
+    ///
+    /// Nodes without a valid `src`, see [`NodeTypeInternalRef::own_src`], are printed unmapped
+    /// and get no comments, see [`Self::commented`].
+    pub(super) fn mapped<W: Write + ?Sized>(
+        &mut self,
+        w: &mut W,
+        node: NodeTypeInternalRef<'_>,
+        f: impl FnOnce(&mut W, &mut Self) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let Some(src) = node.own_src() else {
+            return f(w, self);
+        };
+        self.commented(w, src, |w, cx| {
+            let Some(src) = cx.source_map.as_ref().and_then(|_| src.parse().ok()) else {
+                return f(w, cx);
+            };
+            // The indentation of a line belongs to no node
+            let start = cx.offset + cx.pending_indent();
+            let slot = cx.map().open(start, node.id(), src);
+            f(w, cx)?;
+            let end = cx.offset;
+            cx.map().close(slot, end);
+            Ok(())
+        })
+    }
+
+    /// Runs `f`, the printing of the node at `src`, between the comments around it.
+    ///
+    /// The comments written before the node are printed first, a comment after it on the same
+    /// line at the end of the line.
+    pub(super) fn commented<W: Write + ?Sized>(
+        &mut self,
+        w: &mut W,
+        src: &str,
+        f: impl FnOnce(&mut W, &mut Self) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let Some(src) = self
+            .comments
+            .as_ref()
+            .and_then(|_| src.parse::<SrcLocation>().ok())
+        else {
+            return f(w, self);
+        };
+        self.comments_before(w, src.start())?;
+        let scope_end = std::mem::replace(&mut self.scope_end, src.end());
+        f(w, self)?;
+        self.scope_end = scope_end;
+        if let Some(text) = self.comments_mut().trail(src.end()) {
+            self.write(w, " ")?;
+            self.write(w, text)?;
+        }
+        Ok(())
+    }
+
+    /// Prints the comments ending by `offset` of the source, before the code written next.
+    ///
+    /// A block comment followed by code on its line stays in front of the code. Otherwise a
+    /// comment at the start of a line gets a line of its own, and within a line a block comment
+    /// stays in place and a line comment moves to the end of the line.
+    fn comments_before<W: Write + ?Sized>(&mut self, w: &mut W, offset: usize) -> io::Result<()> {
+        while let Some(comment) = self
+            .comments
+            .as_mut()
+            .and_then(|comments| comments.next_before(offset))
+        {
+            if !comment.is_line && comment.code_after {
+                self.write(w, comment.text)?;
+                self.write(w, " ")?;
+            } else if self.line_start || self.column == 0 {
+                self.write(w, comment.text)?;
+                self.write(w, "\n")?;
+            } else if comment.is_line {
+                self.comments_mut().trail_taken();
+            } else {
+                self.write(w, comment.text)?;
+                self.write(w, " ")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether comments are left to print before the end of the innermost node.
+    pub(super) fn has_comments_left(&self) -> bool {
+        self.comments
+            .as_ref()
+            .is_some_and(|comments| comments.has_before(self.scope_end))
+    }
+
+    /// Prints the comments left before the end of the innermost node, each on a line of its own.
+    ///
+    /// For the comments after the last statement or member of a braced node.
+    pub(super) fn write_comments_left<W: Write + ?Sized>(&mut self, w: &mut W) -> io::Result<()> {
+        self.write_comments_until(w, self.scope_end)
+    }
+
+    /// Prints the comments ending by `offset` of the source, each on a line of its own.
+    pub(super) fn write_comments_until<W: Write + ?Sized>(
+        &mut self,
+        w: &mut W,
+        offset: usize,
+    ) -> io::Result<()> {
+        while let Some(comment) = self
+            .comments
+            .as_mut()
+            .and_then(|comments| comments.next_before(offset))
+        {
+            self.write(w, "\n")?;
+            self.write(w, comment.text)?;
+        }
+        Ok(())
+    }
+
+    /// Whether printing comments, in which case they stand for the documentation of the nodes.
+    pub(super) fn prints_comments(&self) -> bool {
+        self.comments.is_some()
+    }
+
+    /// Whether a comment of the source lies within `src`, a node written as a comment.
+    pub(super) fn has_comment_within(&self, src: &str) -> bool {
+        let Some(comments) = &self.comments else {
+            return false;
+        };
+        src.parse::<SrcLocation>()
+            .is_ok_and(|src| comments.covers(src.range()))
+    }
+
+    fn comments_mut(&mut self) -> &mut Comments {
+        self.comments
+            .as_mut()
+            .expect("Only called while printing comments")
+    }
+
+    fn map(&mut self) -> &mut SourceMap {
+        self.source_map
+            .as_mut()
+            .expect("Only called while recording a source map")
+    }
+
+    /// Bytes of indentation the next write starts with.
+    fn pending_indent(&self) -> usize {
+        match (self.line_start, self.config.use_tabs()) {
+            (false, _) => 0,
+            (true, true) => self.depth,
+            (true, false) => self.depth * self.config.indent_width(),
+        }
+    }
+
+    /// Writes `text`, indenting every non-empty line it starts.
+    pub(super) fn write<W: Write + ?Sized>(
+        &mut self,
+        w: &mut W,
+        text: impl AsRef<[u8]>,
+    ) -> io::Result<()> {
+        for (i, line) in text.as_ref().split(|byte| *byte == b'\n').enumerate() {
+            if i > 0 {
+                self.write_trailing_comments(w)?;
+                w.write_all(b"\n")?;
+                self.offset += 1;
+                self.column = 0;
+                self.line_start = true;
+            }
+            if line.is_empty() {
+                continue;
+            }
+            if self.line_start {
+                self.line_start = false;
+                self.indent(w)?;
+            }
+            w.write_all(line)?;
+            self.offset += line.len();
+            self.column += width(line);
+            self.widest = self.widest.max(self.column);
+        }
+        Ok(())
+    }
+
+    /// Ends the line with the comments queued for it.
+    fn write_trailing_comments<W: Write + ?Sized>(&mut self, w: &mut W) -> io::Result<()> {
+        let Some(comments) = &mut self.comments else {
+            return Ok(());
+        };
+        for text in comments.take_trailing() {
+            self.write(w, " ")?;
+            self.write(w, text)?;
+        }
+        Ok(())
+    }
+
+    fn indent<W: Write + ?Sized>(&mut self, w: &mut W) -> io::Result<()> {
+        let columns = self.depth * self.config.indent_width();
+        if self.config.use_tabs() {
+            for _ in 0..self.depth {
+                w.write_all(b"\t")?;
+            }
+            self.offset += self.depth;
+        } else {
+            let mut left = columns;
+            while left > 0 {
+                let chunk = left.min(SPACES.len());
+                w.write_all(&SPACES[..chunk])?;
+                left -= chunk;
+            }
+            self.offset += columns;
+        }
+        self.column += columns;
+        Ok(())
+    }
+
+    /// Runs `f` one indentation level deeper, for the children of a braced node.
+    pub(super) fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Output `f` would write at the current position, without writing it.
+    pub(super) fn render(
+        &mut self,
+        f: impl FnOnce(&mut Vec<u8>, &mut Self) -> io::Result<()>,
+    ) -> io::Result<Rendered> {
+        self.render_into(Vec::new(), |buffer| buffer, f)
+    }
+
+    /// Like [`Self::render`], with every list inside kept on one line.
+    pub(super) fn render_flat(
+        &mut self,
+        f: impl FnOnce(&mut Vec<u8>, &mut Self) -> io::Result<()>,
+    ) -> io::Result<Rendered> {
+        let flat = std::mem::replace(&mut self.flat, true);
+        let rendered = self.render(f);
+        self.flat = flat;
+        rendered
+    }
+
+    /// Like [`Self::render`] as long as `f` writes a single line, `None` as soon as it starts a
+    /// second one.
+    pub(super) fn render_line(
+        &mut self,
+        f: impl FnOnce(&mut SingleLine, &mut Self) -> io::Result<()>,
+    ) -> Option<Rendered> {
+        self.render_into(SingleLine(Vec::new()), |line| line.0, f)
+            .ok()
+    }
+
+    /// Renders `f` into `buffer`, the state of the context left as it was whether `f` fails or
+    /// not.
+    fn render_into<B: Write>(
+        &mut self,
+        mut buffer: B,
+        into_bytes: fn(B) -> Vec<u8>,
+        f: impl FnOnce(&mut B, &mut Self) -> io::Result<()>,
+    ) -> io::Result<Rendered> {
+        let saved = (
+            self.offset,
+            self.column,
+            self.line_start,
+            self.widest,
+            self.depth,
+            self.scope_end,
+        );
+        let recorded = self.source_map.as_ref().map(|map| map.mappings().len());
+        let cursor = self
+            .comments
+            .as_ref()
+            .map(|comments| comments.cursor().clone());
+        self.widest = self.column;
+        let written = f(&mut buffer, self);
+        let mappings = match recorded {
+            Some(recorded) => self.map().split_off(recorded, saved.0),
+            None => vec![],
+        };
+        let rendered = Rendered {
+            bytes: into_bytes(buffer),
+            widest: self.widest,
+            column: self.column,
+            mappings,
+            comments: cursor.map(|cursor| self.comments_mut().replace_cursor(cursor)),
+        };
+        (
+            self.offset,
+            self.column,
+            self.line_start,
+            self.widest,
+            self.depth,
+            self.scope_end,
+        ) = saved;
+        written.map(|()| rendered)
+    }
+
+    /// Whether lists are to be written on one line without measuring them, see
+    /// [`Self::render_flat`].
+    pub(super) fn is_flat(&self) -> bool {
+        self.flat
+    }
+
+    /// Whether `rendered`, followed by `trailing` more columns, stays within the line width.
+    ///
+    /// Output that leaves a comment for the end of its line does not fit either, the comment
+    /// would move past the code that follows on that line.
+    pub(super) fn fits(&self, rendered: &Rendered, trailing: usize) -> bool {
+        let limit = self.config.max_line_width();
+        let within = limit == 0 || rendered.widest.max(rendered.column + trailing) <= limit;
+        within && !self.queues_comments(rendered)
+    }
+
+    /// Whether `rendered` queued comments for the end of its line.
+    fn queues_comments(&self, rendered: &Rendered) -> bool {
+        match (&self.comments, &rendered.comments) {
+            (Some(comments), Some(cursor)) => cursor.queued() > comments.queued(),
+            _ => false,
+        }
+    }
+
+    pub(super) fn is_unlimited(&self) -> bool {
+        self.config.max_line_width() == 0
+    }
+
+    /// Writes `rendered` as it is, it carries its own indentation.
+    pub(super) fn emit<W: Write + ?Sized>(
+        &mut self,
+        w: &mut W,
+        rendered: &Rendered,
+    ) -> io::Result<()> {
+        if let (Some(comments), Some(cursor)) = (&mut self.comments, &rendered.comments) {
+            comments.set_cursor(cursor.clone());
+        }
+        if rendered.bytes.is_empty() {
+            return Ok(());
+        }
+        w.write_all(&rendered.bytes)?;
+        if let Some(map) = &mut self.source_map {
+            map.extend_at(&rendered.mappings, self.offset);
+        }
+        self.offset += rendered.bytes.len();
+        for (i, line) in rendered.bytes.split(|byte| *byte == b'\n').enumerate() {
+            match i {
+                0 => self.column += width(line),
+                _ => self.column = width(line),
+            }
+            self.widest = self.widest.max(self.column);
+        }
+        self.line_start = rendered.bytes.ends_with(b"\n");
+        Ok(())
+    }
+}
+
+/// Buffer failing the write of a line break, for [`PrintContext::render_line`].
+pub(super) struct SingleLine(Vec<u8>);
+
+impl Write for SingleLine {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.contains(&b'\n') {
+            return Err(io::Error::other("second line"));
+        }
+        self.0.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Columns taken by `line`, a tab counting as one character.
+fn width(line: &[u8]) -> usize {
+    // Continuation bytes of UTF-8 do not start a character
+    line.iter().filter(|byte| **byte & 0xC0 != 0x80).count()
+}
@@ -0,0 +1,266 @@
+//! Indentation, wrapping and spacing shared by the serializers.
+
+use std::{
+    io::{self, Write},
+    mem::discriminant,
+};
+
+use crate::ast_specs::{
+    Block, ModifierInvocation, OverrideSpecifier, ParameterList, VariableDeclaration,
+};
+
+use super::{AstSerializer, AstSerializerContexted, BraceStyle, Context, PrintContext};
+
+/// `node`, for the item writers of [`write_list`].
+pub(super) fn write_node<T: AstSerializer>(
+    node: &T,
+    mut w: &mut dyn Write,
+    cx: &mut PrintContext,
+) -> io::Result<()> {
+    node.write_sol(&mut w, cx)
+}
+
+/// `parameter` of a parameter list, for the item writers of [`write_list`].
+pub(super) fn write_parameter(
+    parameter: &VariableDeclaration,
+    mut w: &mut dyn Write,
+    cx: &mut PrintContext,
+) -> io::Result<()> {
+    parameter.write_sol_contexted(Context::ParameterList, &mut w, cx)
+}
+
+/// `open`, the comma separated `items` and `close` on one line if they fit, else one item per
+/// line one level deeper.
+///
+/// The one line layout is measured with the lists inside it on one line too, so a list is
+/// rendered flat once per enclosing list rather than twice per level of nesting.
+pub(super) fn write_list<W: Write, T>(
+    w: &mut W,
+    cx: &mut PrintContext,
+    open: &str,
+    items: &[T],
+    close: &str,
+    item: impl Fn(&T, &mut dyn Write, &mut PrintContext) -> io::Result<()>,
+) -> io::Result<()> {
+    let flat = |w: &mut dyn Write, cx: &mut PrintContext| {
+        cx.write(w, open)?;
+        for (i, node) in items.iter().enumerate() {
+            if i > 0 {
+                cx.write(w, ", ")?;
+            }
+            item(node, w, cx)?;
+        }
+        cx.write(w, close)
+    };
+    if items.is_empty() || cx.is_unlimited() || cx.is_flat() {
+        return flat(w, cx);
+    }
+    let rendered = cx.render_flat(|buffer, cx| flat(buffer, cx))?;
+    if cx.fits(&rendered, 0) {
+        return cx.emit(w, &rendered);
+    }
+
+    cx.write(w, open)?;
+    write_one_per_line(w, cx, items, &item)?;
+    cx.write(w, "\n")?;
+    cx.write(w, close)
+}
+
+/// `items` one level deeper, each on a line of its own and all but the last followed by a comma.
+fn write_one_per_line<W: Write, T>(
+    w: &mut W,
+    cx: &mut PrintContext,
+    items: &[T],
+    item: &impl Fn(&T, &mut dyn Write, &mut PrintContext) -> io::Result<()>,
+) -> io::Result<()> {
+    cx.nested(|cx| {
+        for (i, node) in items.iter().enumerate() {
+            cx.write(w, "\n")?;
+            item(node, w, cx)?;
+            if i + 1 < items.len() {
+                cx.write(w, ",")?;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// `body` between braces one level deeper, `{}` when it is empty.
+pub(super) fn write_braced<W: Write>(
+    w: &mut W,
+    cx: &mut PrintContext,
+    is_empty: bool,
+    body: impl FnOnce(&mut W, &mut PrintContext) -> io::Result<()>,
+) -> io::Result<()> {
+    if is_empty && !cx.has_comments_left() {
+        return cx.write(w, "{}");
+    }
+    cx.write(w, "{")?;
+    cx.nested(|cx| {
+        if is_empty {
+            return cx.write_comments_left(w);
+        }
+        cx.write(w, "\n")?;
+        body(w, cx)?;
+        cx.write_comments_left(w)
+    })?;
+    cx.write(w, "\n}")
+}
+
+/// Braced body of a contract, struct or enum declaration, the brace placed by the brace style.
+pub(super) fn write_declaration_body<W: Write>(
+    w: &mut W,
+    cx: &mut PrintContext,
+    is_empty: bool,
+    body: impl FnOnce(&mut W, &mut PrintContext) -> io::Result<()>,
+) -> io::Result<()> {
+    match cx.config().brace_style() {
+        BraceStyle::NextLine if !is_empty => cx.write(w, "\n")?,
+        _ => cx.write(w, " ")?,
+    }
+    write_braced(w, cx, is_empty, body)
+}
+
+/// Members of a contract or directives of a source unit, separated by blank lines.
+///
+/// Consecutive one-line members of the same kind, like state variables or imports, stay
+/// together. Only the first line of a member is rendered aside to tell, the rest is written
+/// straight to `w`.
+pub(super) fn write_members<W: Write, T: AstSerializer>(
+    w: &mut W,
+    cx: &mut PrintContext,
+    nodes: &[T],
+) -> io::Result<()> {
+    let blank_lines = cx.config().blank_lines_between_members();
+    let mut previous: Option<(&T, bool)> = None;
+    for node in nodes {
+        if previous.is_some() {
+            cx.write(w, "\n")?;
+        }
+        let line = cx.render_line(|buffer, cx| node.write_sol(buffer, cx));
+        let one_liner = line.as_ref().is_some_and(|line| !line.ends_with("}"));
+        if let Some((previous_node, previous_one_liner)) = previous {
+            let grouped = discriminant(previous_node) == discriminant(node)
+                && previous_one_liner
+                && one_liner;
+            if !grouped {
+                for _ in 0..blank_lines {
+                    cx.write(w, "\n")?;
+                }
+            }
+        }
+        match line {
+            Some(line) => cx.emit(w, &line)?,
+            None => node.write_sol(w, cx)?,
+        }
+        previous = Some((node, one_liner));
+    }
+    Ok(())
+}
+
+/// Attribute of a function or modifier header.
+pub(super) enum Attribute<'a> {
+    Keyword(&'static str),
+    Override(&'a OverrideSpecifier),
+    Modifier(&'a ModifierInvocation),
+    Returns(&'a ParameterList),
+}
+
+impl AstSerializer for Attribute<'_> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        match self {
+            Attribute::Keyword(keyword) => cx.write(w, keyword),
+            Attribute::Override(override_specifier) => override_specifier.write_sol(w, cx),
+            Attribute::Modifier(modifier_invocation) => modifier_invocation.write_sol(w, cx),
+            Attribute::Returns(parameter_list) => {
+                cx.write(w, "returns (")?;
+                parameter_list.write_sol(w, cx)?;
+                cx.write(w, ")")
+            }
+        }
+    }
+}
+
+/// Function or modifier header with its body, `;` without one.
+///
+/// The header stays on one line if it fits. Otherwise its parameters go one per line if they do
+/// not fit, and its attributes go one per line if they do not fit after the parameters.
+pub(super) fn write_signature<W: Write>(
+    w: &mut W,
+    cx: &mut PrintContext,
+    head: &str,
+    parameters: &[VariableDeclaration],
+    attributes: &[Attribute],
+    body: Option<&Block>,
+) -> io::Result<()> {
+    let opener = match body {
+        Some(_) => " {".len(),
+        None => ";".len(),
+    };
+    let write_parameters = |w: &mut dyn Write, cx: &mut PrintContext| {
+        cx.write(w, head)?;
+        cx.write(w, "(")?;
+        for (i, parameter) in parameters.iter().enumerate() {
+            if i > 0 {
+                cx.write(w, ", ")?;
+            }
+            write_parameter(parameter, w, cx)?;
+        }
+        cx.write(w, ")")
+    };
+    let write_attributes = |w: &mut dyn Write, cx: &mut PrintContext| {
+        for attribute in attributes {
+            cx.write(w, " ")?;
+            write_node(attribute, w, cx)?;
+        }
+        Ok(())
+    };
+
+    let mut attributes_wrapped = false;
+    if cx.is_unlimited() || cx.is_flat() {
+        write_parameters(w, cx)?;
+        write_attributes(w, cx)?;
+    } else {
+        let flat = cx.render(|buffer, cx| {
+            write_parameters(buffer, cx)?;
+            write_attributes(buffer, cx)
+        })?;
+        if cx.fits(&flat, opener) {
+            cx.emit(w, &flat)?;
+        } else {
+            let flat_parameters = cx.render(|buffer, cx| write_parameters(buffer, cx))?;
+            if cx.fits(&flat_parameters, 0) {
+                cx.emit(w, &flat_parameters)?;
+            } else {
+                cx.write(w, head)?;
+                cx.write(w, "(")?;
+                write_one_per_line(w, cx, parameters, &write_parameter)?;
+                cx.write(w, "\n)")?;
+            }
+            let tail = cx.render(|buffer, cx| write_attributes(buffer, cx))?;
+            if cx.fits(&tail, opener) {
+                cx.emit(w, &tail)?;
+            } else {
+                attributes_wrapped = true;
+                cx.nested(|cx| -> io::Result<()> {
+                    for attribute in attributes {
+                        cx.write(w, "\n")?;
+                        attribute.write_sol(w, cx)?;
+                    }
+                    Ok(())
+                })?;
+            }
+        }
+    }
+
+    let Some(body) = body else {
+        return cx.write(w, ";");
+    };
+    let next_line = attributes_wrapped
+        || (cx.config().brace_style() == BraceStyle::NextLine
+            && body
+                .statements()
+                .is_some_and(|statements| !statements.is_empty()));
+    cx.write(w, if next_line { "\n" } else { " " })?;
+    body.write_sol(w, cx)
+}
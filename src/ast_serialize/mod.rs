@@ -1,3 +1,7 @@
+use std::io::{self, Write};
+
+use crate::AstParserResult;
+
 use crate::ast_specs::{
     ArrayTypeName, Assignment, BaseName, BaseNode, BinaryOperation, Block, Body, Break,
     CompatabilityTypeName, Conditional, Continue, ContractDefinition, ContractKind, Directive,
@@ -6,13 +10,57 @@ use crate::ast_specs::{
     FalseBody, ForStatement, FunctionCall, FunctionCallOptions, FunctionDefinition, FunctionKind,
     FunctionTypeName, Identifier, IdentifierPath, IfStatement, ImportDirective, IndexAccess,
     IndexRangeAccess, InheritanceSpecifier, InitializationExpression, LibraryName, Literal,
-    Mapping, MemberAccess, ModifierDefinition, ModifierInvocation, ModifierName, Mutability,
-    NewExpression, OverrideSpecifier, Overrides, ParameterList, PlaceholderStatement,
-    PragmaDirective, Return, RevertStatement, SourceUnit, StateMutability, Statement,
-    StorageLocation, StructDefinition, StructuredDocumentation, SymbolAliases, TryCatchClause,
-    TryStatement, TupleExpression, TypeName, UnaryOperation, UncheckedBlock, UserDefinedTypeName,
-    UserDefinedValueTypeDefinition, UsingForDirective, VariableDeclaration,
-    VariableDeclarationStatement, Visibility, WhileStatement, inline_assembly::InlineAssembly,
+    LiteralKind, Mapping, MemberAccess, ModifierDefinition, ModifierInvocation, ModifierName,
+    Mutability, NewExpression, NodeTypeInternalRef, OverrideSpecifier, Overrides, ParameterList,
+    PlaceholderStatement, PragmaDirective, Return, RevertStatement, SourceUnit, StateMutability,
+    Statement, StorageLocation, StructDefinition, StructuredDocumentation, Subdenomination,
+    SymbolAliases, Throw, TryCatchClause, TryStatement, TupleExpression, TypeName, UnaryOperation,
+    UncheckedBlock, Unknown, UserDefinedTypeName, UserDefinedValueTypeDefinition,
+    UsingForDirective, VariableDeclaration, VariableDeclarationStatement, Visibility,
+    WhileStatement,
+    inline_assembly::{
+        InlineAssembly,
+        yul_expression::{
+            YulExpression,
+            yul_function_call::YulFunctionCall,
+            yul_identifier::YulIdentifier,
+            yul_literal::{
+                YulLiteral,
+                yul_literal_hex_value::{self, YulLiteralHexValue},
+                yul_literal_value::{self, YulLiteralValue},
+            },
+        },
+        yul_statements::{
+            YulStatement,
+            yul_assignment::YulAssignment,
+            yul_block::YulBlock,
+            yul_break::YulBreak,
+            yul_continue::YulContinue,
+            yul_expression_statement::YulExpressionStatement,
+            yul_for_loop::YulForLoop,
+            yul_function_definition::YulFunctionDefinition,
+            yul_if::YulIf,
+            yul_leave::YulLeave,
+            yul_switch::{CaseValue, YulCase, YulSwitch},
+            yul_variable_declaration::YulVariableDeclaration,
+        },
+        yul_typed_name::YulTypedName,
+    },
+};
+
+mod comments;
+mod config;
+mod context;
+mod layout;
+mod source_map;
+
+pub use config::{BraceStyle, FunctionAttribute, IntTypes, PrinterConfig};
+pub use context::PrintContext;
+pub use source_map::{SourceMap, SourceMapping};
+
+use layout::{
+    Attribute, write_braced, write_declaration_body, write_list, write_members, write_node,
+    write_parameter, write_signature,
 };
 
 macro_rules! ternary {
@@ -21,216 +69,61 @@ macro_rules! ternary {
     };
 }
 
-pub const LICENSE: &str = "// SPDX-License-Identifier: <LICENSE>\n";
-pub const LICENSE_KEY: &str = "<LICENSE>";
-
-pub const EVENT: &str = "<DOCUMENTATION>event <EVENT_NAME>(<EVENT_ARGS>);";
-pub const EVENT_DOCUMENTATION_KEY: &str = "<DOCUMENTATION>";
-pub const EVENT_NAME_KEY: &str = "<EVENT_NAME>";
-pub const EVENT_ARGS_KEY: &str = "<EVENT_ARGS>";
-
-pub const VARIABLE: &str = "<DOCUMENTATION><TYPE><INDEXED><VISIBILITY><MUTABILITY><STORAGE_LOCATION><NAME><ASSIGNMENT><INITIAL_VALUE><TERMINATOR>";
-pub const VARIABLE_DOCUMENTATION_KEY: &str = "<DOCUMENTATION>";
-pub const VARIABLE_TYPE_KEY: &str = "<TYPE>";
-pub const VARIABLE_INDEXED_KEY: &str = "<INDEXED>";
-pub const VARIABLE_INDEXED_KEYWORD: &str = "indexed";
-pub const VARIABLE_VISIBILITY_KEY: &str = "<VISIBILITY>";
-pub const VARIABLE_MUTABILITY_KEY: &str = "<MUTABILITY>";
-pub const VARIABLE_STORAGE_LOCATION_KEY: &str = "<STORAGE_LOCATION>";
-pub const VARIABLE_NAME_KEY: &str = "<NAME>";
-pub const VARIABLE_ASSIGNMENT_KEY: &str = "<ASSIGNMENT>";
-pub const VARIABLE_INITIAL_VALUE_KEY: &str = "<INITIAL_VALUE>";
-pub const VARIABLE_TERMINATOR_KEY: &str = "<TERMINATOR>";
-
-pub const ARRAY_TYPE_NAME: &str = "<BASE_TYPE>[<SIZE>]";
-pub const ARRAY_TYPE_NAME_BASE_TYPE_KEY: &str = "<BASE_TYPE>";
-pub const ARRAY_TYPE_NAME_SIZE_KEY: &str = "<SIZE>";
-
-pub const ASSIGNMENT: &str = "<LEFT_HAND_SIDE> <OPERATOR> <RIGHT_HAND_SIDE>";
-pub const ASSIGNMENT_LEFT_HAND_SIDE_KEY: &str = "<LEFT_HAND_SIDE>";
-pub const ASSIGNMENT_OPERATOR_KEY: &str = "<OPERATOR>";
-pub const ASSIGNMENT_RIGHT_HAND_SIDE_KEY: &str = "<RIGHT_HAND_SIDE>";
-
-pub const BINARY_OPERATION: &str = "<LEFT_EXPRESSION> <OPERATOR> <RIGHT_EXPRESSION>";
-pub const BINARY_OPERATION_LEFT_EXPRESSION_KEY: &str = "<LEFT_EXPRESSION>";
-pub const BINARY_OPERATION_OPERATOR_KEY: &str = "<OPERATOR>";
-pub const BINARY_OPERATION_RIGHT_EXPRESSION_KEY: &str = "<RIGHT_EXPRESSION>";
-
-pub const CONDITIONAL: &str = "<CONDITION> ? <TRUE_EXPRESSION> : <FALSE_EXPRESSION>";
-pub const CONDITIONAL_CONDITION_KEY: &str = "<CONDITION>";
-pub const CONDITIONAL_TRUE_EXPRESSION_KEY: &str = "<TRUE_EXPRESSION>";
-pub const CONDITIONAL_FALSE_EXPRESSION_KEY: &str = "<FALSE_EXPRESSION>";
-
-pub const FUNCTION_CALL: &str = "<FUNCTION_NAME>(<FUNCTION_ARGS>)";
-pub const FUNCTION_CALL_NAME_KEY: &str = "<FUNCTION_NAME>";
-pub const FUNCTION_CALL_ARGS_KEY: &str = "<FUNCTION_ARGS>";
-
-pub const FUNCTION_CALL_OPTIONS: &str = "<FUNCTION_NAME>{<OPTIONS>}(<FUNCTION_ARGS>)";
-pub const FUNCTION_CALL_OPTIONS_NAME_KEY: &str = "<FUNCTION_NAME>";
-// pub const FUNCTION_CALL_OPTIONS_ARGS_KEY: &str = "<FUNCTION_ARGS>";
-pub const FUNCTION_CALL_OPTIONS_OPTIONS_KEY: &str = "<OPTIONS>";
-
-pub const INDEX_ACCESS: &str = "<BASE>[<INDEX>]";
-pub const INDEX_ACCESS_BASE_KEY: &str = "<BASE>";
-pub const INDEX_ACCESS_INDEX_KEY: &str = "<INDEX>";
-
-pub const INDEX_RANGE_ACCESS: &str = "<BASE>[<START>:<END>]";
-pub const INDEX_RANGE_ACCESS_BASE_KEY: &str = "<BASE>";
-pub const INDEX_RANGE_ACCESS_START_KEY: &str = "<START>";
-pub const INDEX_RANGE_ACCESS_END_KEY: &str = "<END>";
-
-pub const MEMBER_ACCESS: &str = "<BASE>.<MEMBER>";
-pub const MEMBER_ACCESS_BASE_KEY: &str = "<BASE>";
-pub const MEMBER_ACCESS_MEMBER_KEY: &str = "<MEMBER>";
-
-pub const NEW_EXPRESSION: &str = "new <EXPRESSION>";
-pub const NEW_EXPRESSION_EXPRESSION_KEY: &str = "<EXPRESSION>";
-
-pub const TUPLE_EXPRESSION: &str = "(<COMPONENTS>)";
-pub const TUPLE_EXPRESSION_COMPONENTS_KEY: &str = "<COMPONENTS>";
-
-pub const UNARY_OPERATION: &str = "<PREFIX><EXPRESSION><SUFFIX>";
-pub const UNARY_OPERATION_PREFIX_KEY: &str = "<PREFIX>";
-pub const UNARY_OPERATION_EXPRESSION_KEY: &str = "<EXPRESSION>";
-pub const UNARY_OPERATION_SUFFIX_KEY: &str = "<SUFFIX>";
-
-pub const FUNCTION_TYPE_NAME: &str = "function(<PARAMETERS>) <VISIBILITY> <MUTABILITY>";
-pub const FUNCTION_TYPE_NAME_PARAMETERS_KEY: &str = "<PARAMETERS>";
-pub const FUNCTION_TYPE_NAME_VISIBILITY_KEY: &str = "<VISIBILITY>";
-pub const FUNCTION_TYPE_NAME_MUTABILITY_KEY: &str = "<MUTABILITY>";
-
-pub const MAPPING: &str = "mapping(<TYPE_LEFT> <NAME_LEFT> => <TYPE_RIGHT> <NAME_RIGHT>)";
-pub const MAPPING_TYPE_LEFT_KEY: &str = "<TYPE_LEFT>";
-pub const MAPPING_NAME_LEFT_KEY: &str = "<NAME_LEFT>";
-pub const MAPPING_TYPE_RIGHT_KEY: &str = "<TYPE_RIGHT>";
-pub const MAPPING_NAME_RIGHT_KEY: &str = "<NAME_RIGHT>";
-
-pub const CONTRACT: &str =
-    "<DOCUMENTATION><ABSTRACT> <CONTRACT_KIND> <CONTRACT_NAME> <IS> <INHERITANCE> {\n<BODY>\n}";
-pub const CONTRACT_DOCUMENTATION_KEY: &str = "<DOCUMENTATION>";
-pub const CONTRACT_ABSTRACT_KEY: &str = "<ABSTRACT>";
-pub const CONTRACT_CONTRACT_KIND_KEY: &str = "<CONTRACT_KIND>";
-pub const CONTRACT_CONTRACT_NAME_KEY: &str = "<CONTRACT_NAME>";
-pub const CONTRACT_IS_KEY: &str = "<IS>";
-pub const CONTRACT_INHERITANCE_KEY: &str = "<INHERITANCE>";
-pub const CONTRACT_BODY_KEY: &str = "<BODY>";
-
-pub const ENUM: &str = "enum <ENUM_NAME> {<ENUM_VALUES>}";
-pub const ENUM_NAME_KEY: &str = "<ENUM_NAME>";
-pub const ENUM_ENUM_VALUES_KEY: &str = "<ENUM_VALUES>";
-
-pub const ERROR: &str = "error <NAME>(<PARAMETERS>);";
-pub const ERROR_NAME_KEY: &str = "<NAME>";
-pub const ERROR_PARAMETERS_KEY: &str = "<PARAMETERS>";
-
-pub const FUNCTION: &str = "<DOCUMENTATION><KIND> <NAME>(<PARAMETERS>) <STATE_MUTABILITY> <VISIBILITY> <OVERRIDE> <MODIFIERS> <RETURNS> <RETURN_PARAMETERS> <BODY>";
-pub const FUNCTION_DOCUMENTATION_KEY: &str = "<DOCUMENTATION>";
-pub const FUNCTION_KIND_KEY: &str = "<KIND>";
-pub const FUNCTION_NAME_KEY: &str = "<NAME>";
-pub const FUNCTION_PARAMETERS_KEY: &str = "<PARAMETERS>";
-pub const FUNCTION_STATE_MUTABILITY_KEY: &str = "<STATE_MUTABILITY>";
-pub const FUNCTION_VISIBILITY_KEY: &str = "<VISIBILITY>";
-pub const FUNCTION_OVERRIDE_KEY: &str = "<OVERRIDE>";
-pub const FUNCTION_MODIFIERS_KEY: &str = "<MODIFIERS>";
-pub const FUNCTION_RETURNS_KEY: &str = "<RETURNS>";
-pub const FUNCTION_RETURN_PARAMETERS_KEY: &str = "<RETURN_PARAMETERS>";
-pub const FUNCTION_BODY_KEY: &str = "<BODY>";
-
-pub const MODIFIER_INVOCATION: &str = "<NAME>(<ARGUMENTS>)";
-pub const MODIFIER_INVOCATION_NAME_KEY: &str = "<NAME>";
-pub const MODIFIER_INVOCATION_ARGUMENTS_KEY: &str = "<ARGUMENTS>";
-
-pub const DO_WHILE_STATEMENT: &str = "do {<BODY>} while (<CONDITION>)";
-pub const DO_WHILE_STATEMENT_BODY_KEY: &str = "<BODY>";
-pub const DO_WHILE_STATEMENT_CONDITION_KEY: &str = "<CONDITION>";
-
-pub const EMIT_STATEMENT: &str = "emit <FUNCTION>";
-pub const EMIT_EVENT_CALL_KEY: &str = "<FUNCTION>";
-
-pub const FOR_STATEMENT: &str = "for (<INITIALIZATION>;<CONDITION>;<SUB_EXPRESSION>) <BODY>";
-pub const FOR_STATEMENT_INITIALIZATION_KEY: &str = "<INITIALIZATION>";
-pub const FOR_STATEMENT_CONDITION_KEY: &str = "<CONDITION>";
-pub const FOR_STATEMENT_SUB_EXPRESSION_KEY: &str = "<SUB_EXPRESSION>";
-pub const FOR_STATEMENT_BODY_KEY: &str = "<BODY>";
-
-pub const VARIABLE_DECLARATION_STATEMENT: &str = "<DECLARATIONS><EQUAL><INITIALIZATION>";
-pub const VARIABLE_DECLARATION_STATEMENT_DECLARATION_KEY: &str = "<DECLARATIONS>";
-pub const VARIABLE_DECLARATION_STATEMENT_EQUAL_KEY: &str = "<EQUAL>";
-pub const EQUAL_KEYWORD: &str = "=";
-pub const VARIABLE_DECLARATION_STATEMENT_INITIALIZATION_KEY: &str = "<INITIALIZATION>";
-
-pub const IF_STATEMENT: &str = "if (<CONDITION>)<TRUE_BODY><ELSE><FALSE_BODY>";
-pub const IF_STATEMENT_CONDITION_KEY: &str = "<CONDITION>";
-pub const IF_STATEMENT_TRUE_BODY_KEY: &str = "<TRUE_BODY>";
-pub const IF_STATEMENT_ELSE_KEY: &str = "<ELSE>";
-pub const ELSE_KEYWORD: &str = "else";
-pub const IF_STATEMENT_FALSE_BODY_KEY: &str = "<FALSE_BODY>";
-
-pub const REVERT_STATEMENT: &str = "revert <FUNCTION_CALL>";
-pub const REVERT_STATEMENT_FUNCTION_CALL: &str = "<FUNCTION_CALL>";
-
-pub const TRY_CATCH_STATEMENT: &str = "try <EXPRESSION> {} catch {<CLAUSES>}";
-pub const TRY_CATCH_STATEMENT_EXPRESSION_KEY: &str = "<EXPRESSION>";
-pub const TRY_CATCH_STATEMENT_CLAUSES_KEY: &str = "<CLAUSES>";
-
-pub const CATCH_CLAUSE: &str = "catch <ERROR> <PARAMS> {<BODY>}";
-pub const CATCH_CLAUSE_ERROR_KEY: &str = "<ERROR>";
-pub const CATCH_CLAUSE_PARAMS_KEY: &str = "<PARAMS>";
-pub const CATCH_CLAUSE_BODY_KEY: &str = "<BODY>";
-
-pub const UNCHECKED_BLOCK: &str = "unchecked <BLOCK>";
-pub const UNCHECKED_BLOCK_BLOCK_KEY: &str = "<BLOCK>";
-
-pub const WHILE_STATEMENT: &str = "while <CONDITION> {<BODY>}";
-pub const WHILE_STATEMENT_CONDITION_KEY: &str = "<CONDITION>";
-pub const WHILE_STATEMENT_BODY_KEY: &str = "<BODY>";
-
-pub const STRUCT_STATEMENT: &str = "struct <NAME> {<MEMBERS>}";
-pub const STRUCT_STATEMENT_NAME_KEY: &str = "<NAME>";
-pub const STRUCT_STATEMENT_MEMBERS_KEY: &str = "<MEMBERS>";
-
-pub const USER_DEFINED_TYPE_DEFINITION: &str = "type <NAME> is <TYPE>";
-pub const USER_DEFINED_TYPE_DEFINITION_NAME_KEY: &str = "<NAME>";
-pub const USER_DEFINED_TYPE_DEFINITION_TYPE_KEY: &str = "<TYPE>";
-
-pub const USING_FOR_DIRECTIVE: &str = "using <LIBRARY> for <TYPE>;";
-pub const USING_FOR_DIRECTIVE_TYPE_KEY: &str = "<TYPE>";
-pub const USING_FOR_DIRECTIVE_LIBRARY_KEY: &str = "<LIBRARY>";
-
-pub const MODIFIER: &str = "<NAME>(<PARAMETERS>) <VISIBILITY> <OVERRIDE> {<BODY>}";
-pub const MODIFIER_NAME_KEY: &str = "<NAME>";
-pub const MODIFIER_PARAMETERS_KEY: &str = "<PARAMETERS>";
-pub const MODIFIER_VISIBILITY_KEY: &str = "<VISIBILITY>";
-pub const MODIFIER_OVERRIDE_KEY: &str = "<OVERRIDE>";
-pub const MODIFIER_BODY_KEY: &str = "<BODY>";
-
-pub const IMPORT_DIRECTIVE: &str = "import <ALIASES><FROM>\"<PATH>\";";
-pub const IMPORT_DIRECTIVE_ALIASES_KEY: &str = "<ALIASES>";
-pub const IMPORT_DIRECTIVE_FROM_KEY: &str = "<FROM>";
-pub const FROM_KEYWORD: &str = "from";
-pub const IMPORT_DIRECTIVE_PATH_KEY: &str = "<PATH>";
-
-pub const SYMBOL_ALIASES: &str = "<FOREIGN><AS><LOCAL>";
-pub const SYMBOL_ALIASES_AS_KEY: &str = "<AS>";
-pub const SYMBOL_ALIASES_FOREIGN_KEY: &str = "<FOREIGN>";
-pub const SYMBOL_ALIASES_LOCAL_KEY: &str = "<LOCAL>";
-
-pub const PRAGMA: &str = "pragma <LITERALS>;\n";
-pub const PRAGMA_LITERALS_KEY: &str = "<LITERALS>";
-
-pub const RETURN: &str = "return<EXPRESSION>";
-pub const RETURN_EXPRESSION_KEY: &str = "<EXPRESSION>";
-
 pub trait AstSerializer {
-    fn to_sol_vec(&self) -> Vec<u8>;
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()>;
+
+    fn to_sol_vec(&self) -> Vec<u8> {
+        render(&PrinterConfig::default(), |w, cx| self.write_sol(w, cx))
+    }
 
     fn to_sol_string(&self) -> String {
         to_string(self.to_sol_vec())
     }
+
+    /// Prints with `config` instead of the `forge fmt` layout.
+    fn to_sol_string_with(&self, config: &PrinterConfig) -> String {
+        to_string(render(config, |w, cx| self.write_sol(w, cx)))
+    }
+
+    /// Prints with `config` and maps the output back to the nodes it was printed from.
+    fn to_sol_string_with_source_map(&self, config: &PrinterConfig) -> (String, SourceMap) {
+        let mut out = Vec::new();
+        let mut cx = PrintContext::new(config.clone()).with_source_map();
+        self.write_sol(&mut out, &mut cx)
+            .expect("Writing to a Vec never fails");
+        let source_map = cx
+            .into_source_map()
+            .expect("The context records a source map");
+        (to_string(out), source_map)
+    }
+
+    /// Prints with `config` and the comments of `source`, the text the node was parsed from.
+    fn to_sol_string_with_comments(
+        &self,
+        config: &PrinterConfig,
+        source: &str,
+    ) -> AstParserResult<String> {
+        let mut out = Vec::new();
+        let mut cx = PrintContext::new(config.clone()).with_comments(source)?;
+        self.write_sol(&mut out, &mut cx)
+            .expect("Writing to a Vec never fails");
+        Ok(to_string(out))
+    }
 }
 
 pub trait AstSerializerDelimited<'a, D: Into<&'a [u8]> + Copy> {
-    fn to_sol_vec_with_delimiter(&self, d: D) -> Vec<u8>;
+    fn write_sol_with_delimiter(
+        &self,
+        d: D,
+        w: &mut impl Write,
+        cx: &mut PrintContext,
+    ) -> io::Result<()>;
+
+    fn to_sol_vec_with_delimiter(&self, d: D) -> Vec<u8> {
+        render(&PrinterConfig::default(), |w, cx| {
+            self.write_sol_with_delimiter(d, w, cx)
+        })
+    }
 
     fn to_sol_string_with_delimiter(&self, d: D) -> String {
         to_string(self.to_sol_vec_with_delimiter(d))
@@ -238,7 +131,18 @@ pub trait AstSerializerDelimited<'a, D: Into<&'a [u8]> + Copy> {
 }
 
 pub trait AstSerializerContexted {
-    fn to_sol_vec_contexted(&self, context: Context) -> Vec<u8>;
+    fn write_sol_contexted(
+        &self,
+        context: Context,
+        w: &mut impl Write,
+        cx: &mut PrintContext,
+    ) -> io::Result<()>;
+
+    fn to_sol_vec_contexted(&self, context: Context) -> Vec<u8> {
+        render(&PrinterConfig::default(), |w, cx| {
+            self.write_sol_contexted(context, w, cx)
+        })
+    }
 
     fn to_sol_string_contexted(&self, context: Context) -> String {
         to_string(self.to_sol_vec_contexted(context))
@@ -246,7 +150,19 @@ pub trait AstSerializerContexted {
 }
 
 pub trait AstSerializerContextedAndDelimited<'a, D: Into<&'a [u8]> + Copy> {
-    fn to_sol_vec_contexted_and_delimited(&self, context: Context, d: D) -> Vec<u8>;
+    fn write_sol_contexted_and_delimited(
+        &self,
+        context: Context,
+        d: D,
+        w: &mut impl Write,
+        cx: &mut PrintContext,
+    ) -> io::Result<()>;
+
+    fn to_sol_vec_contexted_and_delimited(&self, context: Context, d: D) -> Vec<u8> {
+        render(&PrinterConfig::default(), |w, cx| {
+            self.write_sol_contexted_and_delimited(context, d, w, cx)
+        })
+    }
 
     fn to_sol_string_contexted_and_delimited(&self, context: Context, d: D) -> String {
         to_string(self.to_sol_vec_contexted_and_delimited(context, d))
@@ -259,1352 +175,1678 @@ pub enum Context {
     ParameterList,
 }
 
+/// Output of `write` in a fresh context with `config`.
+fn render(
+    config: &PrinterConfig,
+    write: impl FnOnce(&mut Vec<u8>, &mut PrintContext) -> io::Result<()>,
+) -> Vec<u8> {
+    let mut out = Vec::new();
+    write(&mut out, &mut PrintContext::new(config.clone())).expect("Writing to a Vec never fails");
+    out
+}
+
 impl AstSerializer for SourceUnit {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("SourceUnit");
-        let mut out = Vec::new();
-        let license = LICENSE.replace(
-            LICENSE_KEY,
-            self.license().as_deref().unwrap_or("UNLICENSED"),
-        );
-
-        out.extend_from_slice(license.as_bytes());
-        out.extend(self.nodes().to_sol_vec_with_delimiter(Delimiter::NewLine));
-
-        out
+        cx.mapped(w, self.into(), |w, cx| {
+            cx.write(w, "// SPDX-License-Identifier: ")?;
+            cx.write(w, self.license().as_deref().unwrap_or("UNLICENSED"))?;
+            cx.write(w, "\n")?;
+            write_members(w, cx, self.nodes())?;
+            cx.write_comments_until(w, usize::MAX)?;
+            cx.write(w, "\n")
+        })
     }
 }
 
 impl AstSerializer for Directive {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("Directive");
         match self {
-            Directive::EventDefinition(event_definition) => event_definition.to_sol_vec(),
-            Directive::ContractDefinition(contract_definition) => contract_definition.to_sol_vec(),
-            Directive::EnumDefinition(enum_definition) => enum_definition.to_sol_vec(),
-            Directive::ErrorDefinition(error_definition) => error_definition.to_sol_vec(),
-            Directive::FunctionDefinition(function_definition) => function_definition.to_sol_vec(),
-            Directive::ImportDirective(import_directive) => import_directive.to_sol_vec(),
-            Directive::PragmaDirective(pragma_directive) => pragma_directive.to_sol_vec(),
-            Directive::StructDefinition(struct_definition) => struct_definition.to_sol_vec(),
+            Directive::EventDefinition(event_definition) => write_mapped(w, cx, event_definition),
+            Directive::ContractDefinition(contract_definition) => {
+                write_mapped(w, cx, contract_definition)
+            }
+            Directive::EnumDefinition(enum_definition) => write_mapped(w, cx, enum_definition),
+            Directive::ErrorDefinition(error_definition) => write_mapped(w, cx, error_definition),
+            Directive::FunctionDefinition(function_definition) => {
+                write_mapped(w, cx, function_definition)
+            }
+            Directive::ImportDirective(import_directive) => write_mapped(w, cx, import_directive),
+            Directive::PragmaDirective(pragma_directive) => write_mapped(w, cx, pragma_directive),
+            Directive::StructDefinition(struct_definition) => {
+                write_mapped(w, cx, struct_definition)
+            }
             Directive::UserDefinedValueTypeDefinition(user_defined_value_type_definition) => {
-                user_defined_value_type_definition.to_sol_vec()
+                write_mapped(w, cx, user_defined_value_type_definition)
+            }
+            Directive::UsingForDirective(using_for_directive) => {
+                write_mapped(w, cx, using_for_directive)
             }
-            Directive::UsingForDirective(using_for_directive) => using_for_directive.to_sol_vec(),
             Directive::VariableDeclaration(variable_declaration) => {
-                variable_declaration.to_sol_vec_contexted(Context::ContractScope)
+                variable_declaration.write_sol_contexted(Context::ContractScope, w, cx)
             }
+            Directive::Unknown(unknown) => unknown.write_sol(w, cx),
         }
     }
 }
 
 impl AstSerializer for EventDefinition {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("EventDefinition");
-        EVENT
-            .replace(
-                EVENT_DOCUMENTATION_KEY,
-                &to_string(self.documentation().to_sol_vec()),
-            )
-            .replace(EVENT_NAME_KEY, self.name())
-            .replace(EVENT_ARGS_KEY, &to_string(self.parameters().to_sol_vec()))
-            .as_bytes()
-            .to_vec()
+        self.documentation().write_sol(w, cx)?;
+        cx.write(w, "event ")?;
+        cx.write(w, self.name())?;
+        write_list(
+            w,
+            cx,
+            "(",
+            self.parameters().parameters(),
+            ternary!(*self.anonymous() => ") anonymous;"; ");"),
+            write_parameter,
+        )
     }
 }
 
 impl AstSerializer for StructuredDocumentation {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("StructuredDocumentation");
-        self.text()
-            .lines()
-            .map(|line| {
-                [
-                    if line.starts_with(" ") { "///" } else { "/// " },
-                    line,
-                    "\n",
-                ]
-                .concat()
-            })
-            .collect::<Vec<String>>()
-            .join("")
-            .as_bytes()
-            .to_vec()
+        if cx.has_comment_within(self.src()) {
+            // Printed with the comments of the source
+            return Ok(());
+        }
+        write_doc_comment(w, cx, self.text())
+    }
+}
+
+/// `text` as `///` lines, each ending with a newline.
+fn write_doc_comment(w: &mut impl Write, cx: &mut PrintContext, text: &str) -> io::Result<()> {
+    for line in text.lines() {
+        cx.write(w, if line.starts_with(" ") { "///" } else { "/// " })?;
+        cx.write(w, line)?;
+        cx.write(w, "\n")?;
     }
+    Ok(())
 }
 
 impl AstSerializer for ParameterList {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("ParameterList");
-        self.parameters()
-            .to_sol_vec_contexted_and_delimited(Context::ParameterList, Delimiter::Comma)
+        self.parameters().write_sol_contexted_and_delimited(
+            Context::ParameterList,
+            Delimiter::Comma,
+            w,
+            cx,
+        )
     }
 }
 
 impl AstSerializerContexted for VariableDeclaration {
-    fn to_sol_vec_contexted(&self, context: Context) -> Vec<u8> {
+    fn write_sol_contexted(
+        &self,
+        context: Context,
+        w: &mut impl Write,
+        cx: &mut PrintContext,
+    ) -> io::Result<()> {
+        cx.mapped(w, self.into(), |w, cx| {
+            self.write_declaration(context, w, cx)
+        })
+    }
+}
+
+impl VariableDeclaration {
+    fn write_declaration(
+        &self,
+        context: Context,
+        w: &mut impl Write,
+        cx: &mut PrintContext,
+    ) -> io::Result<()> {
+        self.documentation().write_sol(w, cx)?;
+        self.type_name().write_sol(w, cx)?;
+        if *self.indexed() == Some(true) {
+            cx.write(w, " indexed")?;
+        }
         match context {
-            Context::ContractScope => VARIABLE
-                .replace(
-                    VARIABLE_DOCUMENTATION_KEY,
-                    &self.documentation().to_sol_string(),
-                )
-                .replace(VARIABLE_TYPE_KEY, &to_string(self.type_name().to_sol_vec()))
-                .replace(
-                    VARIABLE_INDEXED_KEY,
-                    &match self.indexed() {
-                        Some(true) => [" ", VARIABLE_INDEXED_KEYWORD].concat(),
-                        _ => String::default(),
-                    },
-                )
-                .replace(
-                    VARIABLE_VISIBILITY_KEY,
-                    &self.visibility().to_sol_string().pad_front(),
-                )
-                .replace(
-                    VARIABLE_MUTABILITY_KEY,
-                    &self.mutability().to_sol_string().pad_front(),
-                )
-                .replace(VARIABLE_STORAGE_LOCATION_KEY, "")
-                .replace(VARIABLE_NAME_KEY, &self.name().pad_front())
-                .replace(
-                    VARIABLE_ASSIGNMENT_KEY,
-                    &if self.value().is_none() {
-                        String::default()
-                    } else {
-                        "=".pad_front()
-                    },
-                )
-                .replace(
-                    VARIABLE_INITIAL_VALUE_KEY,
-                    &if self.value().is_none() {
-                        String::default()
-                    } else {
-                        self.value().to_sol_string().pad_front()
-                    },
-                )
-                .replace(VARIABLE_TERMINATOR_KEY, ";")
-                .as_bytes()
-                .to_vec(),
-            Context::ParameterList => VARIABLE
-                .replace(
-                    VARIABLE_DOCUMENTATION_KEY,
-                    &self.documentation().to_sol_string(),
-                )
-                .replace(VARIABLE_TYPE_KEY, &to_string(self.type_name().to_sol_vec()))
-                .replace(
-                    VARIABLE_INDEXED_KEY,
-                    &match self.indexed() {
-                        Some(true) => VARIABLE_INDEXED_KEYWORD.pad_front(),
-                        _ => String::default(),
-                    },
-                )
-                .replace(VARIABLE_VISIBILITY_KEY, "")
-                .replace(VARIABLE_MUTABILITY_KEY, "")
-                .replace(
-                    VARIABLE_STORAGE_LOCATION_KEY,
-                    &self.storage_location().to_sol_string().pad_front(),
-                )
-                .replace(VARIABLE_NAME_KEY, &self.name().pad_front())
-                .replace(VARIABLE_ASSIGNMENT_KEY, "")
-                .replace(VARIABLE_INITIAL_VALUE_KEY, "")
-                .replace(VARIABLE_TERMINATOR_KEY, "")
-                .as_bytes()
-                .to_vec(),
+            Context::ContractScope => {
+                // File-level constants take no visibility
+                if self.state_variable() {
+                    write_padded(w, cx, self.visibility().keyword())?;
+                }
+                write_padded(w, cx, self.mutability().map_or("", |m| m.keyword()))?;
+            }
+            Context::ParameterList => {
+                write_padded(w, cx, self.storage_location().keyword())?;
+            }
+        }
+        write_padded(w, cx, &self.name())?;
+        if let Context::ContractScope = context {
+            if let Some(value) = self.value() {
+                cx.write(w, " = ")?;
+                value.write_sol(w, cx)?;
+            }
+            cx.write(w, ";")?;
         }
+        Ok(())
     }
 }
 
-impl AstSerializer for StorageLocation {
-    fn to_sol_vec(&self) -> Vec<u8> {
-        //dbg!("StorageLocation");
+/// `text` after a space, nothing when it is empty.
+fn write_padded(w: &mut impl Write, cx: &mut PrintContext, text: &str) -> io::Result<()> {
+    if text.is_empty() {
+        return Ok(());
+    }
+    cx.write(w, " ")?;
+    cx.write(w, text)
+}
+
+/// Solidity spelling of the enums printed as a single keyword.
+trait Keyword {
+    fn keyword(&self) -> &'static str;
+}
+
+impl Keyword for StorageLocation {
+    fn keyword(&self) -> &'static str {
         match self {
             StorageLocation::Calldata => "calldata",
             StorageLocation::Default => "",
             StorageLocation::Memory => "memory",
             StorageLocation::Storage => "storage",
         }
-        .as_bytes()
-        .to_vec()
     }
 }
 
-impl AstSerializer for Mutability {
-    fn to_sol_vec(&self) -> Vec<u8> {
-        //dbg!("Mutability");
+impl AstSerializer for StorageLocation {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        //dbg!("StorageLocation");
+        cx.write(w, self.keyword())
+    }
+}
+
+impl Keyword for Mutability {
+    fn keyword(&self) -> &'static str {
         match self {
-            Mutability::Mutable => b"".to_vec(),
-            Mutability::Immutable => b"immutable".to_vec(),
-            Mutability::Constant => b"constant".to_vec(),
+            Mutability::Mutable => "",
+            Mutability::Immutable => "immutable",
+            Mutability::Constant => "constant",
         }
     }
 }
 
+impl AstSerializer for Mutability {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        //dbg!("Mutability");
+        cx.write(w, self.keyword())
+    }
+}
+
 impl AstSerializer for TypeName {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("TypeName");
         match self {
-            TypeName::ArrayTypeName(array_type_name) => array_type_name.to_sol_vec(),
-            TypeName::ElementaryTypeName(elementary_type_name) => elementary_type_name.to_sol_vec(),
-            TypeName::FunctionTypeName(function_type_name) => function_type_name.to_sol_vec(),
-            TypeName::Mapping(mapping) => mapping.to_sol_vec(),
+            TypeName::ArrayTypeName(array_type_name) => write_mapped(w, cx, array_type_name),
+            TypeName::ElementaryTypeName(elementary_type_name) => {
+                write_mapped(w, cx, elementary_type_name)
+            }
+            TypeName::FunctionTypeName(function_type_name) => {
+                write_mapped(w, cx, function_type_name)
+            }
+            TypeName::Mapping(mapping) => write_mapped(w, cx, mapping),
             TypeName::UserDefinedTypeName(user_defined_type_name) => {
-                user_defined_type_name.to_sol_vec()
+                write_mapped(w, cx, user_defined_type_name)
             }
+            TypeName::Unknown(unknown) => unknown.write_sol(w, cx),
         }
     }
 }
 
 impl AstSerializer for ArrayTypeName {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("ArrayTypeName");
-        let mut array = ARRAY_TYPE_NAME.replace(
-            ARRAY_TYPE_NAME_BASE_TYPE_KEY,
-            &to_string(self.base_type().to_sol_vec()),
-        );
-        if let Some(l_exp) = self.length() {
-            array = array.replace(ARRAY_TYPE_NAME_SIZE_KEY, &to_string(l_exp.to_sol_vec()));
-        } else {
-            array = array.replace(ARRAY_TYPE_NAME_SIZE_KEY, "");
-        }
-
-        array.as_bytes().to_vec()
+        self.base_type().write_sol(w, cx)?;
+        cx.write(w, "[")?;
+        self.length().write_sol(w, cx)?;
+        cx.write(w, "]")
     }
 }
 
 impl AstSerializer for Expression {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("Expression");
         match self {
-            Expression::Assignment(assignment) => assignment.to_sol_vec(),
-            Expression::BinaryOperation(binary_operation) => binary_operation.to_sol_vec(),
-            Expression::Conditional(conditional) => conditional.to_sol_vec(),
+            Expression::Assignment(assignment) => write_mapped(w, cx, assignment),
+            Expression::BinaryOperation(binary_operation) => write_mapped(w, cx, binary_operation),
+            Expression::Conditional(conditional) => write_mapped(w, cx, conditional),
             Expression::ElementaryTypeNameExpression(elementary_type_name_expression) => {
-                elementary_type_name_expression.to_sol_vec()
+                write_mapped(w, cx, elementary_type_name_expression)
             }
-            Expression::FunctionCall(function_call) => function_call.to_sol_vec(),
+            Expression::FunctionCall(function_call) => write_mapped(w, cx, function_call),
             Expression::FunctionCallOptions(function_call_options) => {
-                function_call_options.to_sol_vec()
+                write_mapped(w, cx, function_call_options)
+            }
+            Expression::Identifier(identifier) => write_mapped(w, cx, identifier),
+            Expression::IndexAccess(index_access) => write_mapped(w, cx, index_access),
+            Expression::IndexRangeAccess(index_range_access) => {
+                write_mapped(w, cx, index_range_access)
             }
-            Expression::Identifier(identifier) => identifier.to_sol_vec(),
-            Expression::IndexAccess(index_access) => index_access.to_sol_vec(),
-            Expression::IndexRangeAccess(index_range_access) => index_range_access.to_sol_vec(),
-            Expression::Literal(literal) => literal.to_sol_vec(),
-            Expression::MemberAccess(member_access) => member_access.to_sol_vec(),
-            Expression::NewExpression(new_expression) => new_expression.to_sol_vec(),
-            Expression::TupleExpression(tuple_expression) => tuple_expression.to_sol_vec(),
-            Expression::UnaryOperation(unary_operation) => unary_operation.to_sol_vec(),
+            Expression::Literal(literal) => write_mapped(w, cx, literal),
+            Expression::MemberAccess(member_access) => write_mapped(w, cx, member_access),
+            Expression::NewExpression(new_expression) => write_mapped(w, cx, new_expression),
+            Expression::TupleExpression(tuple_expression) => write_mapped(w, cx, tuple_expression),
+            Expression::UnaryOperation(unary_operation) => write_mapped(w, cx, unary_operation),
+            Expression::Unknown(unknown) => unknown.write_sol(w, cx),
         }
     }
 }
 
 impl AstSerializer for ElementaryTypeNameExpression {
-    fn to_sol_vec(&self) -> Vec<u8> {
-        self.type_name().to_sol_vec()
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        match self.type_name() {
+            // Conversions to `address payable` are written `payable(...)`
+            CompatabilityTypeName::ElementaryTypeName(type_name)
+                if *type_name.state_mutability() == Some(StateMutability::Payable) =>
+            {
+                cx.write(w, "payable")
+            }
+            type_name => type_name.write_sol(w, cx),
+        }
     }
 }
 
 impl AstSerializer for Assignment {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("Assignment");
-        let mut ass = ASSIGNMENT.replace(
-            ASSIGNMENT_LEFT_HAND_SIDE_KEY,
-            &self.left_hand_side().to_sol_string(),
-        );
-        ass = ass.replace(ASSIGNMENT_OPERATOR_KEY, self.operator());
-        ass = ass.replace(
-            ASSIGNMENT_RIGHT_HAND_SIDE_KEY,
-            &self.right_hand_side().to_sol_string(),
-        );
-
-        ass.as_bytes().to_vec()
+        self.left_hand_side().write_sol(w, cx)?;
+        cx.write(w, " ")?;
+        cx.write(w, self.operator())?;
+        cx.write(w, " ")?;
+        self.right_hand_side().write_sol(w, cx)
     }
 }
 
 impl AstSerializer for BinaryOperation {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("BinaryOperation");
-        let mut bin_op = BINARY_OPERATION.replace(
-            BINARY_OPERATION_LEFT_EXPRESSION_KEY,
-            &self.left_expression().to_sol_string(),
-        );
-        bin_op = bin_op.replace(BINARY_OPERATION_OPERATOR_KEY, self.operator());
-        bin_op = bin_op.replace(
-            BINARY_OPERATION_RIGHT_EXPRESSION_KEY,
-            &self.right_expression().to_sol_string(),
-        );
-
-        bin_op.as_bytes().to_vec()
+        self.left_expression().write_sol(w, cx)?;
+        cx.write(w, " ")?;
+        cx.write(w, self.operator())?;
+        cx.write(w, " ")?;
+        self.right_expression().write_sol(w, cx)
     }
 }
 
 impl AstSerializer for Conditional {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("Conditional");
-        let mut c =
-            CONDITIONAL.replace(CONDITIONAL_CONDITION_KEY, &self.condition().to_sol_string());
-        c = c.replace(
-            CONDITIONAL_TRUE_EXPRESSION_KEY,
-            &self.true_expression().to_sol_string(),
-        );
-        c = c.replace(
-            CONDITIONAL_FALSE_EXPRESSION_KEY,
-            &self.false_expression().to_sol_string(),
-        );
-
-        c.as_bytes().to_vec()
+        self.condition().write_sol(w, cx)?;
+        cx.write(w, " ? ")?;
+        self.true_expression().write_sol(w, cx)?;
+        cx.write(w, " : ")?;
+        self.false_expression().write_sol(w, cx)
     }
 }
+
 impl AstSerializer for CompatabilityTypeName {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         match self {
             CompatabilityTypeName::ElementaryTypeName(elementary_type_name) => {
-                elementary_type_name.to_sol_vec()
+                elementary_type_name.write_sol(w, cx)
+            }
+            CompatabilityTypeName::Name(s) => {
+                let name = int_type(cx.config(), s);
+                cx.write(w, name)
             }
-            CompatabilityTypeName::Name(s) => s.as_bytes().to_vec(),
         }
     }
 }
 
 impl AstSerializer for ElementaryTypeName {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("ElementaryTypeName");
+        let name = int_type(cx.config(), self.name());
+        cx.write(w, name)?;
         if *self.state_mutability() == Some(StateMutability::Payable) {
-            format!("{} payable", self.name())
-        } else {
-            self.name().to_owned()
+            cx.write(w, " payable")?;
         }
-        .as_bytes()
-        .to_vec()
+        Ok(())
+    }
+}
+
+/// `name` spelled as `config` wants `uint` and `int`.
+fn int_type<'a>(config: &PrinterConfig, name: &'a str) -> &'a str {
+    match (config.int_types(), name) {
+        (IntTypes::Long, "uint") => "uint256",
+        (IntTypes::Long, "int") => "int256",
+        (IntTypes::Short, "uint256") => "uint",
+        (IntTypes::Short, "int256") => "int",
+        _ => name,
     }
 }
 
 impl AstSerializer for FunctionCall {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("FunctionCall");
-        let mut fc =
-            FUNCTION_CALL.replace(FUNCTION_CALL_NAME_KEY, &self.expression().to_sol_string());
-        fc = fc.replace(
-            FUNCTION_CALL_ARGS_KEY,
-            &self
-                .arguments()
-                .to_sol_string_with_delimiter(Delimiter::Comma),
-        );
-
-        fc.as_bytes().to_vec()
+        self.expression().write_sol(w, cx)?;
+        if self.names().is_empty() {
+            write_list(w, cx, "(", self.arguments(), ")", write_node)
+        } else {
+            cx.write(w, "({")?;
+            write_named_arguments(w, cx, self.names(), self.arguments())?;
+            cx.write(w, "})")
+        }
     }
 }
 
 impl AstSerializer for FunctionCallOptions {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("FunctionCallOptions");
-        let mut fc =
-            FUNCTION_CALL.replace(FUNCTION_CALL_NAME_KEY, &self.expression().to_sol_string());
-
-        fc = fc.replace(
-            FUNCTION_CALL_OPTIONS_OPTIONS_KEY,
-            &self.options().to_sol_string(),
-        );
-
-        fc.as_bytes().to_vec()
+        self.expression().write_sol(w, cx)?;
+        cx.write(w, "{")?;
+        write_named_arguments(w, cx, self.names(), self.options())?;
+        cx.write(w, "}")
     }
 }
 
 impl AstSerializer for Identifier {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("Identifier");
-        self.name().as_bytes().to_vec()
+        cx.write(w, self.name())
     }
 }
 
 impl AstSerializer for IndexAccess {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("IndexAccess");
-        INDEX_ACCESS
-            .replace(
-                INDEX_ACCESS_BASE_KEY,
-                &self.base_expression().to_sol_string(),
-            )
-            .replace(
-                INDEX_ACCESS_INDEX_KEY,
-                &self.index_expression().to_sol_string(),
-            )
-            .as_bytes()
-            .to_vec()
+        self.base_expression().write_sol(w, cx)?;
+        cx.write(w, "[")?;
+        self.index_expression().write_sol(w, cx)?;
+        cx.write(w, "]")
     }
 }
 
 impl AstSerializer for IndexRangeAccess {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("IndexRangeAccess");
-        INDEX_RANGE_ACCESS
-            .replace(
-                INDEX_RANGE_ACCESS_BASE_KEY,
-                &self.base_expression().to_sol_string(),
-            )
-            .replace(
-                INDEX_RANGE_ACCESS_START_KEY,
-                &self.start_expression().to_sol_string(),
-            )
-            .replace(
-                INDEX_RANGE_ACCESS_END_KEY,
-                &self.end_expression().to_sol_string(),
-            )
-            .as_bytes()
-            .to_vec()
+        self.base_expression().write_sol(w, cx)?;
+        cx.write(w, "[")?;
+        self.start_expression().write_sol(w, cx)?;
+        cx.write(w, ":")?;
+        self.end_expression().write_sol(w, cx)?;
+        cx.write(w, "]")
     }
 }
 
 impl AstSerializer for Literal {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("Literal");
-        self.value()
-            .expect("Expect Value in literal AST node, but....")
-            .as_bytes()
-            .to_vec()
+        match self.kind() {
+            LiteralKind::String => cx.write(w, quote(&self.value().unwrap_or_default())),
+            LiteralKind::UnicodeString => {
+                cx.write(w, "unicode")?;
+                cx.write(w, quote(&self.value().unwrap_or_default()))
+            }
+            // `value` is missing when the bytes are not valid UTF-8
+            LiteralKind::HexString => {
+                cx.write(w, "hex\"")?;
+                cx.write(w, self.hex_value())?;
+                cx.write(w, "\"")
+            }
+            LiteralKind::Bool | LiteralKind::Number => {
+                let value = self
+                    .value()
+                    .expect("Expect Value in literal AST node, but....");
+                cx.write(w, value)?;
+                match self.subdenomination() {
+                    Some(subdenomination) => write_padded(w, cx, subdenomination.keyword()),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl Keyword for Subdenomination {
+    fn keyword(&self) -> &'static str {
+        match self {
+            Subdenomination::Weeks => "weeks",
+            Subdenomination::Days => "days",
+            Subdenomination::Hours => "hours",
+            Subdenomination::Minutes => "minutes",
+            Subdenomination::Seconds => "seconds",
+            Subdenomination::Wei => "wei",
+            Subdenomination::Gwei => "gwei",
+            Subdenomination::Ether => "ether",
+            Subdenomination::Finney => "finney",
+            Subdenomination::Szabo => "szabo",
+        }
+    }
+}
+
+impl AstSerializer for Subdenomination {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        cx.write(w, self.keyword())
     }
 }
 
+/// `name: value` pairs, as in call options and named arguments.
+fn write_named_arguments(
+    w: &mut impl Write,
+    cx: &mut PrintContext,
+    names: &[String],
+    values: &[Expression],
+) -> io::Result<()> {
+    for (i, (name, value)) in names.iter().zip(values).enumerate() {
+        if i > 0 {
+            cx.write(w, ", ")?;
+        }
+        cx.write(w, name)?;
+        cx.write(w, ": ")?;
+        value.write_sol(w, cx)?;
+    }
+    Ok(())
+}
+
+/// Double-quoted string literal with the escapes Solidity understands.
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_ascii_control() => quoted.push_str(&format!("\\x{:02x}", c as u8)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
 impl AstSerializer for MemberAccess {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("MemberAccess");
-        MEMBER_ACCESS
-            .replace(MEMBER_ACCESS_BASE_KEY, &self.expression().to_sol_string())
-            .replace(MEMBER_ACCESS_MEMBER_KEY, self.member_name())
-            .as_bytes()
-            .to_vec()
+        self.expression().write_sol(w, cx)?;
+        cx.write(w, ".")?;
+        cx.write(w, self.member_name())
     }
 }
 
 impl AstSerializer for NewExpression {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("NewExpression");
-        NEW_EXPRESSION
-            .replace(
-                NEW_EXPRESSION_EXPRESSION_KEY,
-                &self.type_name().to_sol_string(),
-            )
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "new ")?;
+        self.type_name().write_sol(w, cx)
     }
 }
 
 impl AstSerializer for TupleExpression {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("TupleExpression");
-        TUPLE_EXPRESSION
-            .replace(
-                TUPLE_EXPRESSION_COMPONENTS_KEY,
-                &self
-                    .components()
-                    .to_sol_string_with_delimiter(Delimiter::Comma),
-            )
-            .as_bytes()
-            .to_vec()
+        let (open, close) = ternary!(*self.is_inline_array() => ("[", "]"); ("(", ")"));
+        cx.write(w, open)?;
+        self.components()
+            .write_sol_with_delimiter(Delimiter::Comma, w, cx)?;
+        cx.write(w, close)
     }
 }
 
 impl AstSerializer for UnaryOperation {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("UnaryOperation");
         if self.prefix() {
-            UNARY_OPERATION
-                .replace(
-                    UNARY_OPERATION_PREFIX_KEY,
-                    match self.operator() {
-                        "delete" => "delete ",
-                        _ => self.operator(),
-                    },
-                )
-                .replace(
-                    UNARY_OPERATION_EXPRESSION_KEY,
-                    &self.sub_expression().to_sol_string(),
-                )
-                .replace(UNARY_OPERATION_SUFFIX_KEY, "")
+            cx.write(
+                w,
+                match self.operator() {
+                    "delete" => "delete ",
+                    _ => self.operator(),
+                },
+            )?;
+            self.sub_expression().write_sol(w, cx)
         } else {
-            UNARY_OPERATION
-                .replace(UNARY_OPERATION_PREFIX_KEY, "")
-                .replace(
-                    UNARY_OPERATION_EXPRESSION_KEY,
-                    &self.sub_expression().to_sol_string(),
-                )
-                .replace(UNARY_OPERATION_SUFFIX_KEY, self.operator())
+            self.sub_expression().write_sol(w, cx)?;
+            cx.write(w, self.operator())
         }
-        .as_bytes()
-        .to_vec()
     }
 }
 
 impl AstSerializer for FunctionTypeName {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("FunctionTypeName");
-        FUNCTION_TYPE_NAME
-            .replace(
-                FUNCTION_TYPE_NAME_PARAMETERS_KEY,
-                &self.parameter_types().to_sol_string(),
-            )
-            .replace(
-                FUNCTION_TYPE_NAME_VISIBILITY_KEY,
-                &self.visibility().to_sol_string(),
-            )
-            .replace(
-                FUNCTION_TYPE_NAME_MUTABILITY_KEY,
-                &self.state_mutability().to_sol_string(),
-            )
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "function(")?;
+        self.parameter_types().write_sol(w, cx)?;
+        cx.write(w, ")")?;
+        write_padded(w, cx, self.visibility().keyword())?;
+        write_padded(w, cx, self.state_mutability().keyword())?;
+        if !self.return_parameter_types().parameters().is_empty() {
+            cx.write(w, " returns (")?;
+            self.return_parameter_types().write_sol(w, cx)?;
+            cx.write(w, ")")?;
+        }
+        Ok(())
+    }
+}
+
+impl Keyword for Visibility {
+    fn keyword(&self) -> &'static str {
+        match self {
+            Visibility::External => "external",
+            Visibility::Public => "public",
+            Visibility::Internal => "internal",
+            Visibility::Private => "private",
+        }
     }
 }
 
 impl AstSerializer for Visibility {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("Visibility");
+        cx.write(w, self.keyword())
+    }
+}
+
+impl Keyword for StateMutability {
+    fn keyword(&self) -> &'static str {
         match self {
-            Visibility::External => b"external".to_vec(),
-            Visibility::Public => b"public".to_vec(),
-            Visibility::Internal => b"internal".to_vec(),
-            Visibility::Private => b"private".to_vec(),
+            StateMutability::Payable => "payable",
+            StateMutability::Pure => "pure",
+            StateMutability::Nonpayable => "",
+            StateMutability::View => "view",
         }
     }
 }
 
 impl AstSerializer for StateMutability {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("StateMutability");
-        match self {
-            StateMutability::Payable => b"payable".to_vec(),
-            StateMutability::Pure => b"pure".to_vec(),
-            StateMutability::Nonpayable => b"".to_vec(),
-            StateMutability::View => b"view".to_vec(),
-        }
+        cx.write(w, self.keyword())
     }
 }
 
 impl AstSerializer for Mapping {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("Mapping");
-        MAPPING
-            .replace(MAPPING_TYPE_LEFT_KEY, &self.key_type().to_sol_string())
-            .replace(MAPPING_NAME_LEFT_KEY, self.key_name().unwrap_or_default())
-            .replace(MAPPING_TYPE_RIGHT_KEY, &self.value_type().to_sol_string())
-            .replace(
-                MAPPING_NAME_RIGHT_KEY,
-                self.value_name().unwrap_or_default(),
-            )
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "mapping(")?;
+        self.key_type().write_sol(w, cx)?;
+        write_padded(w, cx, self.key_name().unwrap_or_default())?;
+        cx.write(w, " => ")?;
+        self.value_type().write_sol(w, cx)?;
+        write_padded(w, cx, self.value_name().unwrap_or_default())?;
+        cx.write(w, ")")
     }
 }
 
 impl AstSerializer for UserDefinedTypeName {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("UserDefinedTypeName");
-        self.path_node()
-            .expect("Expected Path Node in UserDefinedTypeName, but...")
-            .name()
-            .as_bytes()
-            .to_vec()
+        cx.write(
+            w,
+            self.path_node()
+                .expect("Expected Path Node in UserDefinedTypeName, but...")
+                .name(),
+        )
     }
 }
 
 impl AstSerializer for ContractDefinition {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("ContractDefinition");
-        CONTRACT
-            .replace(
-                CONTRACT_DOCUMENTATION_KEY,
-                &self.documentation().to_sol_string(),
-            )
-            .replace(
-                CONTRACT_ABSTRACT_KEY,
-                if self._abstract().unwrap_or_default() {
-                    "abstract"
-                } else {
-                    ""
-                },
-            )
-            .replace(
-                CONTRACT_CONTRACT_KIND_KEY,
-                &self.contract_kind().to_sol_string(),
-            )
-            .replace(CONTRACT_CONTRACT_NAME_KEY, self.name())
-            .replace(
-                CONTRACT_IS_KEY,
-                if self.base_contracts().is_empty() {
-                    ""
-                } else {
-                    "is"
-                },
-            )
-            .replace(
-                CONTRACT_INHERITANCE_KEY,
-                &if self.base_contracts().is_empty() {
-                    String::default()
-                } else {
-                    self.base_contracts()
-                        .to_sol_string_with_delimiter(Delimiter::Comma)
-                },
-            )
-            .replace(
-                CONTRACT_BODY_KEY,
-                &self
-                    .nodes()
-                    .to_sol_string_with_delimiter(Delimiter::NewLine),
-            )
-            .as_bytes()
-            .to_vec()
+        self.documentation().write_sol(w, cx)?;
+        if self._abstract().unwrap_or_default() {
+            cx.write(w, "abstract ")?;
+        }
+        cx.write(w, self.contract_kind().keyword())?;
+        write_padded(w, cx, self.name())?;
+        if !self.base_contracts().is_empty() {
+            cx.write(w, " is ")?;
+            self.base_contracts()
+                .write_sol_with_delimiter(Delimiter::Comma, w, cx)?;
+        }
+        write_declaration_body(w, cx, self.nodes().is_empty(), |w, cx| {
+            write_members(w, cx, self.nodes())
+        })
     }
 }
 
-impl AstSerializer for ContractKind {
-    fn to_sol_vec(&self) -> Vec<u8> {
-        //dbg!("ContractKind");
+impl Keyword for ContractKind {
+    fn keyword(&self) -> &'static str {
         match self {
-            ContractKind::Contract => b"contract".to_vec(),
-            ContractKind::Interface => b"interface".to_vec(),
-            ContractKind::Library => b"library".to_vec(),
+            ContractKind::Contract => "contract",
+            ContractKind::Interface => "interface",
+            ContractKind::Library => "library",
         }
     }
 }
 
+impl AstSerializer for ContractKind {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        //dbg!("ContractKind");
+        cx.write(w, self.keyword())
+    }
+}
+
 impl AstSerializer for InheritanceSpecifier {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("InheritanceSpecifier");
-        self.base_name().to_sol_vec()
+        self.base_name().write_sol(w, cx)?;
+        if let Some(arguments) = self.arguments() {
+            write_list(w, cx, "(", arguments, ")", write_node)?;
+        }
+        Ok(())
     }
 }
 
 impl AstSerializer for BaseName {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("BaseName");
         match self {
             BaseName::UserDefinedTypeName(user_defined_type_name) => {
-                user_defined_type_name.to_sol_vec()
+                user_defined_type_name.write_sol(w, cx)
             }
-            BaseName::IdentifierPath(identifier_path) => identifier_path.to_sol_vec(),
+            BaseName::IdentifierPath(identifier_path) => identifier_path.write_sol(w, cx),
             BaseName::Fallback => panic!("Fallback couldn't be used in real code"),
         }
     }
 }
 
 impl AstSerializer for IdentifierPath {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("IdentifierPath");
-        self.name().as_bytes().to_vec()
+        cx.write(w, self.name())
     }
 }
 
 impl AstSerializer for BaseNode {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("BaseNode");
         match self {
-            BaseNode::EnumDefinition(enum_definition) => enum_definition.to_sol_vec(),
-            BaseNode::ErrorDefinition(error_definition) => error_definition.to_sol_vec(),
-            BaseNode::FunctionDefinition(function_definition) => function_definition.to_sol_vec(),
-            BaseNode::StructDefinition(struct_definition) => struct_definition.to_sol_vec(),
+            BaseNode::EnumDefinition(enum_definition) => write_mapped(w, cx, enum_definition),
+            BaseNode::ErrorDefinition(error_definition) => write_mapped(w, cx, error_definition),
+            BaseNode::FunctionDefinition(function_definition) => {
+                write_mapped(w, cx, function_definition)
+            }
+            BaseNode::StructDefinition(struct_definition) => write_mapped(w, cx, struct_definition),
             BaseNode::UserDefinedValueTypeDefinition(user_defined_value_type_definition) => {
-                user_defined_value_type_definition.to_sol_vec()
+                write_mapped(w, cx, user_defined_value_type_definition)
+            }
+            BaseNode::UsingForDirective(using_for_directive) => {
+                write_mapped(w, cx, using_for_directive)
             }
-            BaseNode::UsingForDirective(using_for_directive) => using_for_directive.to_sol_vec(),
             BaseNode::VariableDeclaration(variable_declaration) => {
-                variable_declaration.to_sol_vec_contexted(Context::ContractScope)
+                variable_declaration.write_sol_contexted(Context::ContractScope, w, cx)
             }
-            BaseNode::EventDefinition(event_definition) => event_definition.to_sol_vec(),
-            BaseNode::ModifierDefinition(modifier_definition) => modifier_definition.to_sol_vec(),
+            BaseNode::EventDefinition(event_definition) => write_mapped(w, cx, event_definition),
+            BaseNode::ModifierDefinition(modifier_definition) => {
+                write_mapped(w, cx, modifier_definition)
+            }
+            BaseNode::Unknown(unknown) => unknown.write_sol(w, cx),
         }
     }
 }
 
+impl AstSerializer for Unknown {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        cx.write(w, "/* unknown node: ")?;
+        cx.write(w, self.node_type())?;
+        cx.write(w, " */")
+    }
+}
+
 impl AstSerializer for EnumDefinition {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("EnumDefinition");
-        ENUM.replace(ENUM_NAME_KEY, self.name())
-            .replace(ENUM_ENUM_VALUES_KEY, &self.members().to_sol_string())
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "enum ")?;
+        cx.write(w, self.name())?;
+        write_declaration_body(w, cx, self.members().is_empty(), |w, cx| {
+            for (i, member) in self.members().iter().enumerate() {
+                if i > 0 {
+                    cx.write(w, ",\n")?;
+                }
+                write_mapped(w, cx, member)?;
+            }
+            Ok(())
+        })
     }
 }
 
 impl AstSerializer for EnumValue {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("EnumValue");
-        self.name().as_bytes().to_vec()
+        cx.write(w, self.name())
     }
 }
 
 impl AstSerializer for ErrorDefinition {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("ErrorDefinition");
-        ERROR
-            .replace(ERROR_NAME_KEY, self.name())
-            .replace(ERROR_PARAMETERS_KEY, &self.parameters().to_sol_string())
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "error ")?;
+        cx.write(w, self.name())?;
+        write_list(
+            w,
+            cx,
+            "(",
+            self.parameters().parameters(),
+            ");",
+            write_parameter,
+        )
     }
 }
 
 impl AstSerializer for FunctionDefinition {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("FunctionDefinition");
-        match self.kind() {
-            FunctionKind::Function => FUNCTION
-                .replace(
-                    FUNCTION_DOCUMENTATION_KEY,
-                    &self.documentation().to_sol_string(),
-                )
-                .replace(FUNCTION_KIND_KEY, &self.kind().to_sol_string())
-                .replace(FUNCTION_NAME_KEY, self.name())
-                .replace(FUNCTION_PARAMETERS_KEY, &self.parameters().to_sol_string())
-                .replace(
-                    FUNCTION_STATE_MUTABILITY_KEY,
-                    &self.state_mutability().to_sol_string(),
-                )
-                .replace(FUNCTION_VISIBILITY_KEY, &self.visibility().to_sol_string())
-                .replace(FUNCTION_OVERRIDE_KEY, &self.overrides().to_sol_string())
-                .replace(FUNCTION_MODIFIERS_KEY, &self.modifiers().to_sol_string())
-                .replace(
-                    FUNCTION_RETURNS_KEY,
-                    if self.return_parameter_list().is_none()
-                        || self.return_parameter_list().unwrap().is_empty()
-                    {
-                        ""
-                    } else {
-                        "returns"
-                    },
-                )
-                .replace(
-                    FUNCTION_RETURN_PARAMETERS_KEY,
-                    &if self.return_parameter_list().is_none()
-                        || self.return_parameter_list().unwrap().is_empty()
-                    {
-                        String::default()
-                    } else {
-                        format!("({})", self.return_parameters().to_sol_string())
-                    },
-                )
-                .replace(FUNCTION_BODY_KEY, &self.body().to_sol_string())
-                .as_bytes()
-                .to_vec(),
-            FunctionKind::Receive => todo!(),
-            FunctionKind::Constructor => FUNCTION
-                .replace(
-                    FUNCTION_DOCUMENTATION_KEY,
-                    &self.documentation().to_sol_string(),
-                )
-                .replace(FUNCTION_KIND_KEY, &self.kind().to_sol_string())
-                .replace(FUNCTION_NAME_KEY, self.name())
-                .replace(FUNCTION_PARAMETERS_KEY, &self.parameters().to_sol_string())
-                .replace(FUNCTION_STATE_MUTABILITY_KEY, "")
-                .replace(FUNCTION_VISIBILITY_KEY, "")
-                .replace(FUNCTION_OVERRIDE_KEY, &self.overrides().to_sol_string())
-                .replace(FUNCTION_MODIFIERS_KEY, &self.modifiers().to_sol_string())
-                .replace(
-                    FUNCTION_RETURNS_KEY,
-                    if self.return_parameter_list().is_none()
-                        || self.return_parameter_list().unwrap().is_empty()
-                    {
-                        ""
-                    } else {
-                        "returns"
-                    },
-                )
-                .replace(
-                    FUNCTION_RETURN_PARAMETERS_KEY,
-                    &if self.return_parameter_list().is_none()
-                        || self.return_parameter_list().unwrap().is_empty()
-                    {
-                        String::default()
-                    } else {
-                        self.return_parameters().to_sol_string()
-                    },
-                )
-                .replace(FUNCTION_BODY_KEY, &self.body().to_sol_string())
-                .as_bytes()
-                .to_vec(),
-            FunctionKind::Fallback => todo!(),
-            FunctionKind::FreeFunction => todo!(),
+        let mut attributes = vec![];
+        for attribute in cx.config().full_attribute_order() {
+            match attribute {
+                // Constructors and free functions take no visibility
+                FunctionAttribute::Visibility => match self.kind() {
+                    FunctionKind::Constructor | FunctionKind::FreeFunction => {}
+                    _ => attributes.push(Attribute::Keyword(self.visibility().keyword())),
+                },
+                FunctionAttribute::StateMutability => {
+                    attributes.push(Attribute::Keyword(self.state_mutability().keyword()))
+                }
+                FunctionAttribute::Virtual => {
+                    if self._virtual().unwrap_or_default() {
+                        attributes.push(Attribute::Keyword("virtual"));
+                    }
+                }
+                FunctionAttribute::Override => {
+                    attributes.extend(self.overrides().map(Attribute::Override))
+                }
+                FunctionAttribute::Modifiers => {
+                    attributes.extend(self.modifiers().iter().map(Attribute::Modifier))
+                }
+            }
+        }
+        attributes.retain(|attribute| !matches!(attribute, Attribute::Keyword("")));
+        if let Some(returns) = self.return_parameters()
+            && !returns.parameters().is_empty()
+        {
+            attributes.push(Attribute::Returns(returns));
+        }
+
+        let mut head = self.kind().keyword().to_owned();
+        if !self.name().is_empty() {
+            head.push(' ');
+            head.push_str(self.name());
         }
+
+        self.documentation().write_sol(w, cx)?;
+        write_signature(
+            w,
+            cx,
+            &head,
+            self.parameters()
+                .map(ParameterList::parameters)
+                .unwrap_or_default(),
+            &attributes,
+            self.body(),
+        )
     }
 }
 
 impl AstSerializer for OverrideSpecifier {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("OverrideSpecifier");
-        self.overrides().to_sol_vec()
+        cx.write(w, "override")?;
+        if !self.overrides().is_empty() {
+            cx.write(w, "(")?;
+            self.overrides()
+                .write_sol_with_delimiter(Delimiter::Comma, w, cx)?;
+            cx.write(w, ")")?;
+        }
+        Ok(())
     }
 }
 
 impl AstSerializer for Overrides {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("Overrides");
         match self {
             Overrides::UserDefinedTypeName(user_defined_type_name) => {
-                user_defined_type_name.to_sol_vec()
+                user_defined_type_name.write_sol(w, cx)
             }
-            Overrides::IdentifierPath(identifier_path) => identifier_path.to_sol_vec(),
+            Overrides::IdentifierPath(identifier_path) => identifier_path.write_sol(w, cx),
         }
     }
 }
 
 impl AstSerializer for ModifierInvocation {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("ModifierInvocation");
-        MODIFIER_INVOCATION
-            .replace(
-                MODIFIER_INVOCATION_NAME_KEY,
-                &self.modifier_name().to_sol_string(),
-            )
-            .replace(
-                MODIFIER_INVOCATION_ARGUMENTS_KEY,
-                &self.arguments().to_sol_string(),
-            )
-            .as_bytes()
-            .to_vec()
+        self.modifier_name().write_sol(w, cx)?;
+        match self.arguments() {
+            Some(arguments) => write_list(w, cx, "(", arguments, ")", write_node),
+            None => Ok(()),
+        }
     }
 }
 
 impl AstSerializer for ModifierName {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("ModifierName");
         match self {
-            ModifierName::Identifier(identifier) => identifier.to_sol_vec(),
-            ModifierName::IdentifierPath(identifier_path) => identifier_path.to_sol_vec(),
+            ModifierName::Identifier(identifier) => identifier.write_sol(w, cx),
+            ModifierName::IdentifierPath(identifier_path) => identifier_path.write_sol(w, cx),
         }
     }
 }
 
-impl AstSerializer for FunctionKind {
-    fn to_sol_vec(&self) -> Vec<u8> {
-        //dbg!("FunctionKind");
+impl Keyword for FunctionKind {
+    fn keyword(&self) -> &'static str {
         match self {
-            FunctionKind::Function => b"function".to_vec(),
-            FunctionKind::Receive => b"receive".to_vec(),
-            FunctionKind::Constructor => b"constructor".to_vec(),
-            FunctionKind::Fallback => b"fallback".to_vec(),
-            FunctionKind::FreeFunction => b"freeFunction".to_vec(),
+            FunctionKind::Function => "function",
+            FunctionKind::Receive => "receive",
+            FunctionKind::Constructor => "constructor",
+            FunctionKind::Fallback => "fallback",
+            FunctionKind::FreeFunction => "function",
         }
     }
 }
 
+impl AstSerializer for FunctionKind {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        //dbg!("FunctionKind");
+        cx.write(w, self.keyword())
+    }
+}
+
 impl AstSerializer for Block {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("Block");
-        let mut inner = self
-            .statements()
-            .to_sol_string_with_delimiter(Delimiter::NewLine)
-            .as_bytes()
-            .to_vec();
-        inner.insert(0, b'{');
-        inner.push(b'}');
-        //dbg!(std::str::from_utf8(inner.as_slice())).unwrap();
-        inner
+        let statements = self.statements().unwrap_or_default();
+        write_braced(w, cx, statements.is_empty(), |w, cx| {
+            statements.write_sol_with_delimiter(Delimiter::NewLine, w, cx)
+        })
     }
 }
 
+/// `node` followed by `;`, for the statements that do not end with a block.
+fn write_terminated<'a, T>(w: &mut impl Write, cx: &mut PrintContext, node: &'a T) -> io::Result<()>
+where
+    T: AstSerializer,
+    &'a T: Into<NodeTypeInternalRef<'a>>,
+{
+    cx.mapped(w, node.into(), |w, cx| {
+        node.write_sol(w, cx)?;
+        cx.write(w, ";")
+    })
+}
+
+/// `node`, mapped to its bytes when the context records a source map.
+fn write_mapped<'a, T>(w: &mut impl Write, cx: &mut PrintContext, node: &'a T) -> io::Result<()>
+where
+    T: AstSerializer,
+    &'a T: Into<NodeTypeInternalRef<'a>>,
+{
+    cx.mapped(w, node.into(), |w, cx| node.write_sol(w, cx))
+}
+
 impl AstSerializer for Statement {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("Statement");
         match self {
-            Statement::Block(block) => block.to_sol_vec(),
-            Statement::Break(_break) => _break.to_sol_vec().terminate(";").as_bytes().to_vec(),
-            Statement::Continue(_continue) => {
-                _continue.to_sol_vec().terminate(";").as_bytes().to_vec()
+            Statement::Block(block) => write_mapped(w, cx, block),
+            Statement::Break(_break) => write_terminated(w, cx, _break),
+            Statement::Continue(_continue) => write_terminated(w, cx, _continue),
+            Statement::DoWhileStatement(do_while_statement) => {
+                write_mapped(w, cx, do_while_statement)
+            }
+            Statement::EmitStatement(emit_statement) => write_terminated(w, cx, emit_statement),
+            Statement::ExpressionStatement(expression_statement) => {
+                write_terminated(w, cx, expression_statement)
+            }
+            Statement::ForStatement(for_statement) => write_mapped(w, cx, for_statement),
+            Statement::IfStatement(if_statement) => write_mapped(w, cx, if_statement),
+            Statement::InlineAssembly(inline_assembly) => write_mapped(w, cx, inline_assembly),
+            Statement::PlaceholderStatement(placeholder_statement) => {
+                write_terminated(w, cx, placeholder_statement)
             }
-            Statement::DoWhileStatement(do_while_statement) => do_while_statement.to_sol_vec(),
-            Statement::EmitStatement(emit_statement) => emit_statement
-                .to_sol_vec()
-                .terminate(";")
-                .as_bytes()
-                .to_vec(),
-            Statement::ExpressionStatement(expression_statement) => expression_statement
-                .to_sol_vec()
-                .terminate(";")
-                .as_bytes()
-                .to_vec(),
-            Statement::ForStatement(for_statement) => for_statement.to_sol_vec(),
-            Statement::IfStatement(if_statement) => if_statement.to_sol_vec(),
-            Statement::InlineAssembly(inline_assembly) => inline_assembly.to_sol_vec(),
-            Statement::PlaceholderStatement(placeholder_statement) => placeholder_statement
-                .to_sol_vec()
-                .terminate(";")
-                .as_bytes()
-                .to_vec(),
-            Statement::Return(_return) => _return.to_sol_vec().terminate(";").as_bytes().to_vec(),
-            Statement::RevertStatement(revert_statement) => revert_statement
-                .to_sol_vec()
-                .terminate(";")
-                .as_bytes()
-                .to_vec(),
-            Statement::TryStatement(try_statement) => try_statement.to_sol_vec(),
-            Statement::UncheckedBlock(unchecked_block) => unchecked_block.to_sol_vec(),
+            Statement::Return(_return) => write_terminated(w, cx, _return),
+            Statement::RevertStatement(revert_statement) => {
+                write_terminated(w, cx, revert_statement)
+            }
+            Statement::Throw(throw) => write_terminated(w, cx, throw),
+            Statement::TryStatement(try_statement) => write_mapped(w, cx, try_statement),
+            Statement::UncheckedBlock(unchecked_block) => write_mapped(w, cx, unchecked_block),
             Statement::VariableDeclarationStatement(variable_declaration_statement) => {
-                variable_declaration_statement
-                    .to_sol_vec()
-                    .terminate(";")
-                    .as_bytes()
-                    .to_vec()
+                write_terminated(w, cx, variable_declaration_statement)
             }
-            Statement::WhileStatement(while_statement) => while_statement.to_sol_vec(),
+            Statement::WhileStatement(while_statement) => write_mapped(w, cx, while_statement),
+            Statement::Unknown(unknown) => unknown.write_sol(w, cx),
         }
     }
 }
 
 impl AstSerializer for Break {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("Break");
-        b"break".to_vec()
+        cx.write(w, "break")
     }
 }
 
 impl AstSerializer for Continue {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("Continue");
-        b"continue".to_vec()
+        cx.write(w, "continue")
     }
 }
 
 impl AstSerializer for Return {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("Return");
-        let expr = match self.expression() {
-            Some(e) => e.to_sol_string().pad_front(),
-            None => String::default(),
-        };
-        RETURN
-            .replace(RETURN_EXPRESSION_KEY, &expr)
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "return")?;
+        if let Some(expression) = self.expression() {
+            cx.write(w, " ")?;
+            expression.write_sol(w, cx)?;
+        }
+        Ok(())
     }
 }
 
 impl AstSerializer for DoWhileStatement {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("DoWhileStatement");
-        DO_WHILE_STATEMENT
-            .replace(DO_WHILE_STATEMENT_BODY_KEY, &self.body().to_sol_string())
-            .replace(
-                DO_WHILE_STATEMENT_CONDITION_KEY,
-                &self.condition().to_sol_string(),
-            )
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "do ")?;
+        self.body().write_sol(w, cx)?;
+        cx.write(w, " while (")?;
+        self.condition().write_sol(w, cx)?;
+        cx.write(w, ");")
     }
 }
 
 impl AstSerializer for Body {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("Body");
         match self {
-            Body::Block(block) => block.to_sol_vec(),
-            Body::Break(_break) => _break.to_sol_vec().terminate(";").as_bytes().to_vec(),
-            Body::Continue(_continue) => _continue.to_sol_vec().terminate(";").as_bytes().to_vec(),
-            Body::DoWhileStatement(do_while_statement) => do_while_statement.to_sol_vec(),
-            Body::EmitStatement(emit_statement) => emit_statement
-                .to_sol_vec()
-                .terminate(";")
-                .as_bytes()
-                .to_vec(),
-            Body::ExpressionStatement(expression_statement) => expression_statement
-                .to_sol_vec()
-                .terminate(";")
-                .as_bytes()
-                .to_vec(),
-            Body::ForStatement(for_statement) => for_statement.to_sol_vec(),
-            Body::IfStatement(if_statement) => if_statement.to_sol_vec(),
-            Body::InlineAssembly(value) => value.to_sol_vec(),
-            Body::PlaceholderStatement(placeholder_statement) => placeholder_statement
-                .to_sol_vec()
-                .terminate(";")
-                .as_bytes()
-                .to_vec(),
-            Body::Return(_return) => _return.to_sol_vec().terminate(";").as_bytes().to_vec(),
-            Body::RevertStatement(revert_statement) => revert_statement
-                .to_sol_vec()
-                .terminate(";")
-                .as_bytes()
-                .to_vec(),
-            Body::TryStatement(try_statement) => try_statement.to_sol_vec(),
-            Body::UncheckedBlock(unchecked_block) => unchecked_block.to_sol_vec(),
+            Body::Block(block) => write_mapped(w, cx, block),
+            Body::Break(_break) => write_terminated(w, cx, _break),
+            Body::Continue(_continue) => write_terminated(w, cx, _continue),
+            Body::DoWhileStatement(do_while_statement) => write_mapped(w, cx, do_while_statement),
+            Body::EmitStatement(emit_statement) => write_terminated(w, cx, emit_statement),
+            Body::ExpressionStatement(expression_statement) => {
+                write_terminated(w, cx, expression_statement)
+            }
+            Body::ForStatement(for_statement) => write_mapped(w, cx, for_statement),
+            Body::IfStatement(if_statement) => write_mapped(w, cx, if_statement),
+            Body::InlineAssembly(value) => write_mapped(w, cx, value),
+            Body::PlaceholderStatement(placeholder_statement) => {
+                write_terminated(w, cx, placeholder_statement)
+            }
+            Body::Return(_return) => write_terminated(w, cx, _return),
+            Body::RevertStatement(revert_statement) => write_terminated(w, cx, revert_statement),
+            Body::Throw(throw) => write_terminated(w, cx, throw),
+            Body::TryStatement(try_statement) => write_mapped(w, cx, try_statement),
+            Body::UncheckedBlock(unchecked_block) => write_mapped(w, cx, unchecked_block),
             Body::VariableDeclarationStatement(variable_declaration_statement) => {
-                variable_declaration_statement
-                    .to_sol_vec()
-                    .terminate(";")
-                    .as_bytes()
-                    .to_vec()
+                write_terminated(w, cx, variable_declaration_statement)
             }
-            Body::WhileStatement(while_statement) => while_statement.to_sol_vec(),
+            Body::WhileStatement(while_statement) => write_mapped(w, cx, while_statement),
+            Body::Unknown(unknown) => unknown.write_sol(w, cx),
         }
     }
 }
 
 impl AstSerializer for EmitStatement {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("EmitStatement");
-        EMIT_STATEMENT
-            .replace(EMIT_EVENT_CALL_KEY, &self.event_call().to_sol_string())
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "emit ")?;
+        self.event_call().write_sol(w, cx)
     }
 }
 
 impl AstSerializer for ExpressionStatement {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("ExpressionStatement");
-        self.expression().to_sol_vec()
+        self.expression().write_sol(w, cx)
     }
 }
 
 impl AstSerializer for ForStatement {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("ForStatement");
-        FOR_STATEMENT
-            .replace(
-                FOR_STATEMENT_INITIALIZATION_KEY,
-                &self.initialization_expression().to_sol_string(),
-            )
-            .replace(
-                FOR_STATEMENT_CONDITION_KEY,
-                &self.condition().to_sol_string(),
-            )
-            .replace(
-                FOR_STATEMENT_SUB_EXPRESSION_KEY,
-                &self.loop_expression().to_sol_string(),
-            )
-            .replace(FOR_STATEMENT_BODY_KEY, &self.body().to_sol_string())
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "for (")?;
+        self.initialization_expression().write_sol(w, cx)?;
+        cx.write(w, ";")?;
+        if let Some(condition) = self.condition() {
+            cx.write(w, " ")?;
+            condition.write_sol(w, cx)?;
+        }
+        cx.write(w, ";")?;
+        if let Some(loop_expression) = self.loop_expression() {
+            cx.write(w, " ")?;
+            loop_expression.write_sol(w, cx)?;
+        }
+        cx.write(w, ") ")?;
+        self.body().write_sol(w, cx)
     }
 }
 
 impl AstSerializer for InitializationExpression {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("InitializationExpression");
         match self {
             InitializationExpression::ExpressionStatement(expression_statement) => {
-                expression_statement.to_sol_vec()
+                write_mapped(w, cx, expression_statement)
             }
             InitializationExpression::VariableDeclarationStatement(
                 variable_declaration_statement,
-            ) => variable_declaration_statement.to_sol_vec(),
+            ) => write_mapped(w, cx, variable_declaration_statement),
         }
     }
 }
 
 impl AstSerializer for VariableDeclarationStatement {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("VariableDeclarationStatement");
-        let is_initialized = self.initial_value().is_some();
         let tuple = self.declarations().len() > 1;
-        let declarations = self
-            .declarations()
-            .to_sol_string_contexted_and_delimited(Context::ParameterList, Delimiter::Comma);
-
-        VARIABLE_DECLARATION_STATEMENT
-            .replace(
-                VARIABLE_DECLARATION_STATEMENT_DECLARATION_KEY,
-                & ternary!(tuple =>  format!("({})", declarations); declarations),
-            )
-            .replace(
-                VARIABLE_DECLARATION_STATEMENT_EQUAL_KEY,
-                &ternary!(is_initialized => EQUAL_KEYWORD.pad_front(); String::default()),
-            )
-            .replace(
-                VARIABLE_DECLARATION_STATEMENT_INITIALIZATION_KEY,
-                &ternary!(is_initialized => self.initial_value().to_sol_string().pad_front(); String::default()),
-            )
-            .as_bytes()
-            .to_vec()
+        if tuple {
+            cx.write(w, "(")?;
+        }
+        self.declarations().write_sol_contexted_and_delimited(
+            Context::ParameterList,
+            Delimiter::Comma,
+            w,
+            cx,
+        )?;
+        if tuple {
+            cx.write(w, ")")?;
+        }
+        if let Some(initial_value) = self.initial_value() {
+            cx.write(w, " = ")?;
+            initial_value.write_sol(w, cx)?;
+        }
+        Ok(())
     }
 }
 
 impl AstSerializer for IfStatement {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("IfStatement");
-        let false_is_some = self.false_body().is_some();
-
-        IF_STATEMENT
-            .replace(
-                IF_STATEMENT_CONDITION_KEY,
-                &self.condition().to_sol_string(),
-            )
-            .replace(
-                IF_STATEMENT_TRUE_BODY_KEY,
-                &self.true_body().to_sol_string().pad_front()
-            )
-            .replace(
-                IF_STATEMENT_ELSE_KEY,
-                &ternary!(false_is_some => ELSE_KEYWORD.pad_front(); String::default()),
-            )
-            .replace(
-                IF_STATEMENT_FALSE_BODY_KEY,
-                &ternary!(false_is_some => self.false_body().to_sol_string().pad_front(); String::default()),
-            )
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "if (")?;
+        self.condition().write_sol(w, cx)?;
+        cx.write(w, ") ")?;
+        self.true_body().write_sol(w, cx)?;
+        if let Some(false_body) = self.false_body() {
+            cx.write(w, " else ")?;
+            false_body.write_sol(w, cx)?;
+        }
+        Ok(())
     }
 }
 
 impl AstSerializer for FalseBody {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("FalseBody");
-        let inner = match self {
-            FalseBody::Block(block) => block.to_sol_vec(),
-            FalseBody::Break(_break) => _break.to_sol_vec().terminate(";").as_bytes().to_vec(),
-            FalseBody::Continue(_continue) => {
-                _continue.to_sol_vec().terminate(";").as_bytes().to_vec()
+        match self {
+            FalseBody::Block(block) => write_mapped(w, cx, block),
+            FalseBody::Break(_break) => write_terminated(w, cx, _break),
+            FalseBody::Continue(_continue) => write_terminated(w, cx, _continue),
+            FalseBody::DoWhileStatement(do_while_statement) => {
+                write_mapped(w, cx, do_while_statement)
+            }
+            FalseBody::EmitStatement(emit_statement) => write_terminated(w, cx, emit_statement),
+            FalseBody::ExpressionStatement(expression_statement) => {
+                write_terminated(w, cx, expression_statement)
             }
-            FalseBody::DoWhileStatement(do_while_statement) => do_while_statement.to_sol_vec(),
-            FalseBody::EmitStatement(emit_statement) => emit_statement
-                .to_sol_vec()
-                .terminate(";")
-                .as_bytes()
-                .to_vec(),
-            FalseBody::ExpressionStatement(expression_statement) => expression_statement
-                .to_sol_vec()
-                .terminate(";")
-                .as_bytes()
-                .to_vec(),
-            FalseBody::ForStatement(for_statement) => for_statement.to_sol_vec(),
-            FalseBody::IfStatement(if_statement) => if_statement.to_sol_vec(),
-            FalseBody::PlaceholderStatement(placeholder_statement) => placeholder_statement
-                .to_sol_vec()
-                .terminate(";")
-                .as_bytes()
-                .to_vec(),
-            FalseBody::Return(_return) => _return.to_sol_vec().terminate(";").as_bytes().to_vec(),
-            FalseBody::RevertStatement(revert_statement) => revert_statement
-                .to_sol_vec()
-                .terminate(";")
-                .as_bytes()
-                .to_vec(),
-            FalseBody::TryStatement(try_statement) => try_statement.to_sol_vec(),
-            FalseBody::UncheckedBlock(unchecked_block) => unchecked_block.to_sol_vec(),
+            FalseBody::ForStatement(for_statement) => write_mapped(w, cx, for_statement),
+            FalseBody::IfStatement(if_statement) => write_mapped(w, cx, if_statement),
+            FalseBody::PlaceholderStatement(placeholder_statement) => {
+                write_terminated(w, cx, placeholder_statement)
+            }
+            FalseBody::Return(_return) => write_terminated(w, cx, _return),
+            FalseBody::RevertStatement(revert_statement) => {
+                write_terminated(w, cx, revert_statement)
+            }
+            FalseBody::Throw(throw) => write_terminated(w, cx, throw),
+            FalseBody::TryStatement(try_statement) => write_mapped(w, cx, try_statement),
+            FalseBody::UncheckedBlock(unchecked_block) => write_mapped(w, cx, unchecked_block),
             FalseBody::VariableDeclarationStatement(variable_declaration_statement) => {
-                variable_declaration_statement
-                    .to_sol_vec()
-                    .terminate(";")
-                    .as_bytes()
-                    .to_vec()
+                write_terminated(w, cx, variable_declaration_statement)
             }
-            FalseBody::WhileStatement(while_statement) => while_statement.to_sol_vec(),
-        };
-
-        inner
+            FalseBody::WhileStatement(while_statement) => write_mapped(w, cx, while_statement),
+            FalseBody::Unknown(unknown) => unknown.write_sol(w, cx),
+        }
     }
 }
 
 impl AstSerializer for PlaceholderStatement {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("PlaceholderStatement");
-        b"_".to_vec()
+        cx.write(w, "_")
     }
 }
 
 impl AstSerializer for RevertStatement {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("RevertStatement");
-        REVERT_STATEMENT
-            .replace(
-                REVERT_STATEMENT_FUNCTION_CALL,
-                &self.error_call().to_sol_string(),
-            )
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "revert ")?;
+        self.error_call().write_sol(w, cx)
+    }
+}
+
+impl AstSerializer for Throw {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        cx.write(w, "throw")
     }
 }
 
 impl AstSerializer for TryStatement {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("TryStatement");
-        TRY_CATCH_STATEMENT
-            .replace(
-                TRY_CATCH_STATEMENT_CLAUSES_KEY,
-                &self.clauses().to_sol_string(),
-            )
-            .replace(
-                TRY_CATCH_STATEMENT_EXPRESSION_KEY,
-                &self.external_call().to_sol_string(),
-            )
-            .as_bytes()
-            .to_vec()
+        // The first clause is the success block, the rest are `catch` clauses
+        let (success, catches) = self
+            .clauses()
+            .split_first()
+            .expect("Expect a success clause in try statement, but....");
+        cx.write(w, "try ")?;
+        self.external_call().write_sol(w, cx)?;
+        if let Some(parameters) = success.parameters() {
+            cx.write(w, " returns (")?;
+            parameters.write_sol(w, cx)?;
+            cx.write(w, ")")?;
+        }
+        cx.write(w, " ")?;
+        success.block().write_sol(w, cx)?;
+        for catch in catches {
+            cx.write(w, " ")?;
+            catch.write_sol(w, cx)?;
+        }
+        Ok(())
     }
 }
 
 impl AstSerializer for TryCatchClause {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("TryCatchClause");
-        CATCH_CLAUSE
-            .replace(CATCH_CLAUSE_PARAMS_KEY, &self.parameters().to_sol_string())
-            .replace(CATCH_CLAUSE_ERROR_KEY, self.error_name())
-            .replace(CATCH_CLAUSE_BODY_KEY, &self.block().to_sol_string())
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "catch ")?;
+        cx.write(w, self.error_name())?;
+        if let Some(parameters) = self.parameters() {
+            cx.write(w, "(")?;
+            parameters.write_sol(w, cx)?;
+            cx.write(w, ") ")?;
+        } else if !self.error_name().is_empty() {
+            cx.write(w, " ")?;
+        }
+        self.block().write_sol(w, cx)
     }
 }
 
 impl AstSerializer for UncheckedBlock {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("UncheckedBlock");
-        UNCHECKED_BLOCK
-            .replace(
-                UNCHECKED_BLOCK_BLOCK_KEY,
-                &format!("{{{}}}", self.statements().to_sol_string()),
-            )
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "unchecked ")?;
+        write_braced(w, cx, self.statements().is_empty(), |w, cx| {
+            self.statements()
+                .write_sol_with_delimiter(Delimiter::NewLine, w, cx)
+        })
     }
 }
 
 impl AstSerializer for WhileStatement {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("WhileStatement");
-        WHILE_STATEMENT
-            .replace(
-                WHILE_STATEMENT_CONDITION_KEY,
-                &self.condition().to_sol_string(),
-            )
-            .replace(WHILE_STATEMENT_BODY_KEY, &self.body().to_sol_string())
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "while (")?;
+        self.condition().write_sol(w, cx)?;
+        cx.write(w, ") ")?;
+        self.body().write_sol(w, cx)
     }
 }
 
 impl AstSerializer for InlineAssembly {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("InlineAssembly");
-        todo!()
+        if let Some(documentation) = self.documentation()
+            && !cx.prints_comments()
+        {
+            write_doc_comment(w, cx, documentation)?;
+        }
+        cx.write(w, "assembly ")?;
+        if let Some(dialect) = self.dialect() {
+            cx.write(w, quote(dialect))?;
+            cx.write(w, " ")?;
+        }
+        if let Some(flags) = self.flags()
+            && !flags.is_empty()
+        {
+            cx.write(w, "(")?;
+            for (i, flag) in flags.iter().enumerate() {
+                if i > 0 {
+                    cx.write(w, ", ")?;
+                }
+                cx.write(w, quote(flag))?;
+            }
+            cx.write(w, ") ")?;
+        }
+        // Older compilers leave out the AST of an empty block
+        match self.ast() {
+            Some(block) => block.write_sol(w, cx),
+            None => cx.write(w, "{}"),
+        }
+    }
+}
+
+impl AstSerializer for YulBlock {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        write_braced(w, cx, self.statements().is_empty(), |w, cx| {
+            self.statements()
+                .write_sol_with_delimiter(Delimiter::NewLine, w, cx)
+        })
+    }
+}
+
+impl AstSerializer for YulStatement {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        match self {
+            YulStatement::YulAssignment(yul_assignment) => write_mapped(w, cx, yul_assignment),
+            YulStatement::YulBlock(yul_block) => write_mapped(w, cx, yul_block),
+            YulStatement::YulBreak(yul_break) => write_mapped(w, cx, yul_break),
+            YulStatement::YulContinue(yul_continue) => write_mapped(w, cx, yul_continue),
+            YulStatement::YulExpressionStatement(yul_expression_statement) => {
+                write_mapped(w, cx, yul_expression_statement)
+            }
+            YulStatement::YulLeave(yul_leave) => write_mapped(w, cx, yul_leave),
+            YulStatement::YulForLoop(yul_for_loop) => write_mapped(w, cx, yul_for_loop),
+            YulStatement::YulFunctionDefinition(yul_function_definition) => {
+                write_mapped(w, cx, yul_function_definition)
+            }
+            YulStatement::YulIf(yul_if) => write_mapped(w, cx, yul_if),
+            YulStatement::YulSwitch(yul_switch) => write_mapped(w, cx, yul_switch),
+            YulStatement::YulVariableDeclaration(yul_variable_declaration) => {
+                write_mapped(w, cx, yul_variable_declaration)
+            }
+        }
+    }
+}
+
+impl AstSerializer for YulAssignment {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        self.variable_names()
+            .write_sol_with_delimiter(Delimiter::Comma, w, cx)?;
+        cx.write(w, " := ")?;
+        self.value().write_sol(w, cx)
+    }
+}
+
+impl AstSerializer for YulBreak {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        cx.write(w, "break")
+    }
+}
+
+impl AstSerializer for YulContinue {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        cx.write(w, "continue")
+    }
+}
+
+impl AstSerializer for YulLeave {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        cx.write(w, "leave")
+    }
+}
+
+impl AstSerializer for YulExpressionStatement {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        self.expression().write_sol(w, cx)
+    }
+}
+
+impl AstSerializer for YulForLoop {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        cx.write(w, "for ")?;
+        write_inline_yul_block(w, cx, self.pre())?;
+        cx.write(w, " ")?;
+        self.condition().write_sol(w, cx)?;
+        cx.write(w, " ")?;
+        write_inline_yul_block(w, cx, self.post())?;
+        cx.write(w, " ")?;
+        self.body().write_sol(w, cx)
+    }
+}
+
+/// `{ ... }` on one line, for the init and post blocks of a Yul for loop.
+fn write_inline_yul_block(
+    w: &mut impl Write,
+    cx: &mut PrintContext,
+    block: &YulBlock,
+) -> io::Result<()> {
+    if block.statements().is_empty() {
+        return cx.write(w, "{}");
+    }
+    cx.write(w, "{ ")?;
+    block
+        .statements()
+        .write_sol_with_delimiter(Delimiter::Space, w, cx)?;
+    cx.write(w, " }")
+}
+
+impl AstSerializer for YulFunctionDefinition {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        cx.write(w, "function ")?;
+        cx.write(w, self.name())?;
+        cx.write(w, "(")?;
+        self.parameters()
+            .write_sol_with_delimiter(Delimiter::Comma, w, cx)?;
+        cx.write(w, ")")?;
+        if let Some(returns) = self.return_variables()
+            && !returns.is_empty()
+        {
+            cx.write(w, " -> ")?;
+            returns.write_sol_with_delimiter(Delimiter::Comma, w, cx)?;
+        }
+        cx.write(w, " ")?;
+        self.body().write_sol(w, cx)
+    }
+}
+
+impl AstSerializer for YulIf {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        cx.write(w, "if ")?;
+        self.condition().write_sol(w, cx)?;
+        cx.write(w, " ")?;
+        self.body().write_sol(w, cx)
+    }
+}
+
+impl AstSerializer for YulSwitch {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        cx.write(w, "switch ")?;
+        self.expression().write_sol(w, cx)?;
+        cx.write(w, "\n")?;
+        self.cases()
+            .write_sol_with_delimiter(Delimiter::NewLine, w, cx)
+    }
+}
+
+impl AstSerializer for YulCase {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        self.value().write_sol(w, cx)?;
+        cx.write(w, " ")?;
+        self.body().write_sol(w, cx)
+    }
+}
+
+impl AstSerializer for CaseValue {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        match self {
+            CaseValue::Default(_) => cx.write(w, "default"),
+            CaseValue::YulLiteral(yul_literal) => {
+                cx.write(w, "case ")?;
+                yul_literal.write_sol(w, cx)
+            }
+        }
+    }
+}
+
+impl AstSerializer for YulVariableDeclaration {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        cx.write(w, "let ")?;
+        self.variables()
+            .write_sol_with_delimiter(Delimiter::Comma, w, cx)?;
+        if let Some(value) = self.value() {
+            cx.write(w, " := ")?;
+            value.write_sol(w, cx)?;
+        }
+        Ok(())
+    }
+}
+
+impl AstSerializer for YulTypedName {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        write_typed(w, cx, &self.name, &self.r#type)
+    }
+}
+
+impl AstSerializer for YulExpression {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        match self {
+            YulExpression::YulFunctionCall(yul_function_call) => {
+                write_mapped(w, cx, yul_function_call)
+            }
+            YulExpression::YulIdentifier(yul_identifier) => write_mapped(w, cx, yul_identifier),
+            YulExpression::YulLiteral(yul_literal) => write_mapped(w, cx, yul_literal),
+        }
     }
 }
 
+impl AstSerializer for YulFunctionCall {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        self.function_name().write_sol(w, cx)?;
+        cx.write(w, "(")?;
+        self.arguments()
+            .write_sol_with_delimiter(Delimiter::Comma, w, cx)?;
+        cx.write(w, ")")
+    }
+}
+
+impl AstSerializer for YulIdentifier {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        cx.write(w, self.name())
+    }
+}
+
+impl AstSerializer for YulLiteral {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        match self {
+            YulLiteral::YulLiteralValue(yul_literal_value) => yul_literal_value.write_sol(w, cx),
+            YulLiteral::YulLiteralHexValue(yul_literal_hex_value) => {
+                yul_literal_hex_value.write_sol(w, cx)
+            }
+        }
+    }
+}
+
+impl AstSerializer for YulLiteralValue {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        match self.kind {
+            yul_literal_value::LiteralKind::String => {
+                write_typed(w, cx, &quote(&self.value), &self.r#type)
+            }
+            _ => write_typed(w, cx, &self.value, &self.r#type),
+        }
+    }
+}
+
+impl AstSerializer for YulLiteralHexValue {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        // `value` is missing when the bytes are not valid UTF-8
+        let value = match (&self.kind, &self.value) {
+            (yul_literal_hex_value::LiteralKind::String, Some(value)) => quote(value),
+            (yul_literal_hex_value::LiteralKind::String, None) => {
+                format!("hex\"{}\"", self.hex_value)
+            }
+            (_, value) => value.clone().unwrap_or_default(),
+        };
+        write_typed(w, cx, &value, &self.r#type)
+    }
+}
+
+/// Yul `value:type`, plain `value` in the untyped EVM dialect.
+fn write_typed(
+    w: &mut impl Write,
+    cx: &mut PrintContext,
+    value: &str,
+    type_name: &str,
+) -> io::Result<()> {
+    cx.write(w, value)?;
+    if !type_name.is_empty() {
+        cx.write(w, ":")?;
+        cx.write(w, type_name)?;
+    }
+    Ok(())
+}
+
 impl AstSerializer for StructDefinition {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("StructDefinition");
-        STRUCT_STATEMENT
-            .replace(STRUCT_STATEMENT_NAME_KEY, self.name())
-            .replace(STRUCT_STATEMENT_MEMBERS_KEY, &{
-                let mut p = self.members().to_sol_string_contexted_and_delimited(
-                    Context::ParameterList,
-                    Delimiter::Terminator,
-                );
-                p.push(';');
-                p
-            })
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "struct ")?;
+        cx.write(w, self.name())?;
+        write_declaration_body(w, cx, self.members().is_empty(), |w, cx| {
+            for (i, member) in self.members().iter().enumerate() {
+                if i > 0 {
+                    cx.write(w, "\n")?;
+                }
+                member.write_sol_contexted(Context::ParameterList, w, cx)?;
+                cx.write(w, ";")?;
+            }
+            Ok(())
+        })
     }
 }
 
 impl AstSerializer for UserDefinedValueTypeDefinition {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("UserDefinedValueTypeDefinition");
-        USER_DEFINED_TYPE_DEFINITION
-            .replace(USER_DEFINED_TYPE_DEFINITION_NAME_KEY, self.name())
-            .replace(
-                USER_DEFINED_TYPE_DEFINITION_TYPE_KEY,
-                &self.underlying_type().to_sol_string(),
-            )
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "type ")?;
+        cx.write(w, self.name())?;
+        cx.write(w, " is ")?;
+        self.underlying_type().write_sol(w, cx)?;
+        cx.write(w, ";")
     }
 }
 
 impl AstSerializer for UsingForDirective {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("UsingForDirective");
-        USING_FOR_DIRECTIVE
-            .replace(
-                USING_FOR_DIRECTIVE_LIBRARY_KEY,
-                &self.library_name().to_sol_string(),
-            )
-            .replace(
-                USING_FOR_DIRECTIVE_TYPE_KEY,
-                &self.type_name().to_sol_string(),
-            )
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "using ")?;
+        match (self.library_name(), self.function_list()) {
+            (Some(library_name), _) => library_name.write_sol(w, cx)?,
+            (None, Some(function_list)) => write_using_functions(w, cx, function_list)?,
+            (None, None) => {}
+        }
+        cx.write(w, " for ")?;
+        match self.type_name() {
+            Some(type_name) => type_name.write_sol(w, cx)?,
+            None => cx.write(w, "*")?,
+        }
+        if *self.global() == Some(true) {
+            cx.write(w, " global")?;
+        }
+        cx.write(w, ";")
+    }
+}
+
+/// `{f, g as +}` of a using directive, each entry either `{"function": path}` or
+/// `{"definition": path, "operator": op}`.
+fn write_using_functions(
+    w: &mut impl Write,
+    cx: &mut PrintContext,
+    function_list: &serde_json::Value,
+) -> io::Result<()> {
+    let entries = function_list
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    cx.write(w, "{")?;
+    for (i, entry) in entries.iter().enumerate() {
+        if i > 0 {
+            cx.write(w, ", ")?;
+        }
+        let path = entry.get("function").or_else(|| entry.get("definition"));
+        let name = path
+            .and_then(|path| path.get("name"))
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        cx.write(w, name)?;
+        if let Some(operator) = entry.get("operator").and_then(serde_json::Value::as_str) {
+            cx.write(w, " as ")?;
+            cx.write(w, operator)?;
+        }
     }
+    cx.write(w, "}")
 }
 
 impl AstSerializer for LibraryName {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("LibraryName");
         match self {
             LibraryName::UserDefinedTypeName(user_defined_type_name) => {
-                user_defined_type_name.to_sol_vec()
+                user_defined_type_name.write_sol(w, cx)
             }
-            LibraryName::IdentifierPath(identifier_path) => identifier_path.to_sol_vec(),
+            LibraryName::IdentifierPath(identifier_path) => identifier_path.write_sol(w, cx),
         }
     }
 }
 
 impl AstSerializer for ModifierDefinition {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("ModifierDefinition");
-        MODIFIER
-            .replace(MODIFIER_NAME_KEY, self.name())
-            .replace(MODIFIER_PARAMETERS_KEY, &self.parameters().to_sol_string())
-            .replace(MODIFIER_OVERRIDE_KEY, &self.overrides().to_sol_string())
-            .replace(MODIFIER_VISIBILITY_KEY, &self.visibility().to_sol_string())
-            .replace(MODIFIER_BODY_KEY, &self.body().to_sol_string())
-            .as_bytes()
-            .to_vec()
+        let mut attributes = vec![];
+        if self._virtual().unwrap_or_default() {
+            attributes.push(Attribute::Keyword("virtual"));
+        }
+        attributes.extend(self.overrides().map(Attribute::Override));
+
+        self.documentation().write_sol(w, cx)?;
+        write_signature(
+            w,
+            cx,
+            &format!("modifier {}", self.name()),
+            self.parameters().parameters(),
+            &attributes,
+            Some(self.body()),
+        )
     }
 }
 
 impl AstSerializer for ImportDirective {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("ImportDirective");
-        let is_aliases = !self.symbol_aliases().is_empty();
-        let aliases = ternary!(is_aliases => format!("{{{}}}", self
-        .symbol_aliases()
-        .to_sol_string_with_delimiter(Delimiter::Comma)); String::default());
-
-        IMPORT_DIRECTIVE
-            .replace(IMPORT_DIRECTIVE_ALIASES_KEY, &aliases)
-            .replace(
-                IMPORT_DIRECTIVE_FROM_KEY,
-                ternary!(is_aliases => FROM_KEYWORD; ""),
-            )
-            .replace(IMPORT_DIRECTIVE_PATH_KEY, self.file())
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "import ")?;
+        if !self.symbol_aliases().is_empty() {
+            cx.write(w, "{")?;
+            self.symbol_aliases()
+                .write_sol_with_delimiter(Delimiter::Comma, w, cx)?;
+            cx.write(w, "} from ")?;
+        }
+        cx.write(w, "\"")?;
+        cx.write(w, self.file())?;
+        cx.write(w, "\"")?;
+        if !self.unit_alias().is_empty() {
+            cx.write(w, " as ")?;
+            cx.write(w, self.unit_alias())?;
+        }
+        cx.write(w, ";")
     }
 }
 
 impl AstSerializer for SymbolAliases {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("SymbolAliases");
-        SYMBOL_ALIASES
-            .replace(SYMBOL_ALIASES_FOREIGN_KEY, &self.foreign().to_sol_string())
-            .replace(
-                SYMBOL_ALIASES_AS_KEY,
-                if self.local().is_some() { " as " } else { "" },
-            )
-            .replace(SYMBOL_ALIASES_LOCAL_KEY, self.local().unwrap_or_default())
-            .as_bytes()
-            .to_vec()
+        self.foreign().write_sol(w, cx)?;
+        if let Some(local) = self.local() {
+            cx.write(w, " as ")?;
+            cx.write(w, local)?;
+        }
+        Ok(())
     }
 }
 
 impl AstSerializer for PragmaDirective {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         //dbg!("PragmaDirective");
-        let pragma_lits = self
-            .literals()
-            .iter()
-            .map(|lit| {
-                if lit.as_str() == "solidity" {
-                    [lit, " "].concat()
-                } else {
-                    lit.to_string()
-                }
-            })
-            .collect::<Vec<_>>()
-            .join("");
-        PRAGMA
-            .replace(PRAGMA_LITERALS_KEY, &pragma_lits)
-            .as_bytes()
-            .to_vec()
+        cx.write(w, "pragma ")?;
+        let is_operator = |literal: &str| literal.starts_with(['^', '~', '<', '>', '=', '|', '-']);
+        let mut previous: Option<&str> = None;
+        for (i, literal) in self.literals().iter().enumerate() {
+            // The pragma name stands apart, then a constraint starts at each operator following
+            // a version, and `||` and `-` are spaced on both sides.
+            let spaced = i == 1
+                || previous.is_some_and(|previous| {
+                    (is_operator(literal) && !is_operator(previous))
+                        || previous == "||"
+                        || previous == "-"
+                });
+            if spaced {
+                cx.write(w, " ")?;
+            }
+            cx.write(w, literal)?;
+            previous = Some(literal);
+        }
+        cx.write(w, ";")
     }
 }
 
@@ -1612,24 +1854,22 @@ impl AstSerializer for PragmaDirective {
 // COMMON
 //
 impl<T: AstSerializer> AstSerializer for Vec<T> {
-    fn to_sol_vec(&self) -> Vec<u8> {
-        self.as_slice().to_sol_vec()
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        self.as_slice().write_sol(w, cx)
     }
 }
 
 impl<T: AstSerializer> AstSerializer for &[T] {
-    fn to_sol_vec(&self) -> Vec<u8> {
-        self.iter().fold(Vec::new(), |mut acc, t| {
-            acc.extend(t.to_sol_vec());
-
-            acc
-        })
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        self.iter().try_for_each(|t| t.write_sol(w, cx))
     }
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum Delimiter {
     Comma,
+    CommaNewLine,
+    Space,
     NewLine,
     DoubleLine,
     Terminator,
@@ -1639,6 +1879,8 @@ impl From<Delimiter> for &[u8] {
     fn from(value: Delimiter) -> Self {
         match value {
             Delimiter::Comma => b", ",
+            Delimiter::CommaNewLine => b",\n",
+            Delimiter::Space => b" ",
             Delimiter::NewLine => b"\n",
             Delimiter::DoubleLine => b"\n\n",
             Delimiter::Terminator => b";",
@@ -1647,45 +1889,65 @@ impl From<Delimiter> for &[u8] {
 }
 
 impl<T: AstSerializer, D: for<'a> Into<&'a [u8]> + Copy> AstSerializerDelimited<'_, D> for Vec<T> {
-    fn to_sol_vec_with_delimiter(&self, d: D) -> Vec<u8> {
-        self.as_slice().to_sol_vec_with_delimiter(d)
+    fn write_sol_with_delimiter(
+        &self,
+        d: D,
+        w: &mut impl Write,
+        cx: &mut PrintContext,
+    ) -> io::Result<()> {
+        self.as_slice().write_sol_with_delimiter(d, w, cx)
     }
 }
 impl<T: AstSerializer, D: for<'a> Into<&'a [u8]> + Copy> AstSerializerDelimited<'_, D> for &[T] {
-    fn to_sol_vec_with_delimiter(&self, d: D) -> Vec<u8> {
-        let limit = self.len();
-        self.iter().enumerate().fold(Vec::new(), |mut acc, (i, t)| {
-            acc.extend(t.to_sol_vec());
-            if i != limit - 1 {
-                acc.extend_from_slice(d.into());
+    fn write_sol_with_delimiter(
+        &self,
+        d: D,
+        w: &mut impl Write,
+        cx: &mut PrintContext,
+    ) -> io::Result<()> {
+        for (i, t) in self.iter().enumerate() {
+            if i > 0 {
+                cx.write(w, d.into())?;
             }
-
-            acc
-        })
+            t.write_sol(w, cx)?;
+        }
+        Ok(())
     }
 }
 
 impl<T: AstSerializerContexted> AstSerializerContexted for Vec<T> {
-    fn to_sol_vec_contexted(&self, context: Context) -> Vec<u8> {
-        self.as_slice().to_sol_vec_contexted(context)
+    fn write_sol_contexted(
+        &self,
+        context: Context,
+        w: &mut impl Write,
+        cx: &mut PrintContext,
+    ) -> io::Result<()> {
+        self.as_slice().write_sol_contexted(context, w, cx)
     }
 }
 
 impl<T: AstSerializerContexted> AstSerializerContexted for &[T] {
-    fn to_sol_vec_contexted(&self, context: Context) -> Vec<u8> {
-        self.iter().fold(Vec::new(), |mut acc, t| {
-            acc.extend(t.to_sol_vec_contexted(context));
-
-            acc
-        })
+    fn write_sol_contexted(
+        &self,
+        context: Context,
+        w: &mut impl Write,
+        cx: &mut PrintContext,
+    ) -> io::Result<()> {
+        self.iter()
+            .try_for_each(|t| t.write_sol_contexted(context, w, cx))
     }
 }
 
 impl<T: AstSerializerContexted> AstSerializerContexted for Option<T> {
-    fn to_sol_vec_contexted(&self, context: Context) -> Vec<u8> {
+    fn write_sol_contexted(
+        &self,
+        context: Context,
+        w: &mut impl Write,
+        cx: &mut PrintContext,
+    ) -> io::Result<()> {
         match self {
-            Some(t) => t.to_sol_vec_contexted(context),
-            None => vec![],
+            Some(t) => t.write_sol_contexted(context, w, cx),
+            None => Ok(()),
         }
     }
 }
@@ -1693,10 +1955,15 @@ impl<T: AstSerializerContexted> AstSerializerContexted for Option<T> {
 impl<T: for<'a> AstSerializerDelimited<'a, D>, D: for<'a> Into<&'a [u8]> + Copy>
     AstSerializerDelimited<'_, D> for Option<T>
 {
-    fn to_sol_vec_with_delimiter(&self, d: D) -> Vec<u8> {
+    fn write_sol_with_delimiter(
+        &self,
+        d: D,
+        w: &mut impl Write,
+        cx: &mut PrintContext,
+    ) -> io::Result<()> {
         match self {
-            Some(t) => t.to_sol_vec_with_delimiter(d),
-            None => vec![],
+            Some(t) => t.write_sol_with_delimiter(d, w, cx),
+            None => Ok(()),
         }
     }
 }
@@ -1704,88 +1971,52 @@ impl<T: for<'a> AstSerializerDelimited<'a, D>, D: for<'a> Into<&'a [u8]> + Copy>
 impl<T: AstSerializerContexted, D: for<'a> Into<&'a [u8]> + Copy>
     AstSerializerContextedAndDelimited<'_, D> for Vec<T>
 {
-    fn to_sol_vec_contexted_and_delimited(&self, context: Context, d: D) -> Vec<u8> {
+    fn write_sol_contexted_and_delimited(
+        &self,
+        context: Context,
+        d: D,
+        w: &mut impl Write,
+        cx: &mut PrintContext,
+    ) -> io::Result<()> {
         self.as_slice()
-            .to_sol_vec_contexted_and_delimited(context, d)
+            .write_sol_contexted_and_delimited(context, d, w, cx)
     }
 }
 impl<T: AstSerializerContexted, D: for<'a> Into<&'a [u8]> + Copy>
     AstSerializerContextedAndDelimited<'_, D> for &[T]
 {
-    fn to_sol_vec_contexted_and_delimited(&self, context: Context, d: D) -> Vec<u8> {
-        let limit = self.len();
-        self.iter().enumerate().fold(Vec::new(), |mut acc, (i, t)| {
-            acc.extend(t.to_sol_vec_contexted(context));
-            if i != limit - 1 {
-                acc.extend_from_slice(d.into());
+    fn write_sol_contexted_and_delimited(
+        &self,
+        context: Context,
+        d: D,
+        w: &mut impl Write,
+        cx: &mut PrintContext,
+    ) -> io::Result<()> {
+        for (i, t) in self.iter().enumerate() {
+            if i > 0 {
+                cx.write(w, d.into())?;
             }
-
-            acc
-        })
+            t.write_sol_contexted(context, w, cx)?;
+        }
+        Ok(())
     }
 }
 
 impl<T: AstSerializer> AstSerializer for Option<T> {
-    fn to_sol_vec(&self) -> Vec<u8> {
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
         match self {
-            Some(t) => t.to_sol_vec(),
-            None => vec![],
+            Some(t) => t.write_sol(w, cx),
+            None => Ok(()),
         }
     }
 }
 
 impl<T: AstSerializer> AstSerializer for &T {
-    fn to_sol_vec(&self) -> Vec<u8> {
-        AstSerializer::to_sol_vec(*self)
+    fn write_sol(&self, w: &mut impl Write, cx: &mut PrintContext) -> io::Result<()> {
+        AstSerializer::write_sol(*self, w, cx)
     }
 }
 
 fn to_string(v: Vec<u8>) -> String {
     String::from_utf8(v).unwrap()
 }
-
-pub trait Padded {
-    fn pad_front(&self) -> String;
-
-    fn terminate(&self, terminator: &str) -> String;
-}
-
-impl Padded for &str {
-    fn pad_front(&self) -> String {
-        " {Padding}".replace("{Padding}", self)
-    }
-
-    fn terminate(&self, terminator: &str) -> String {
-        format!("{self}{terminator}")
-    }
-}
-
-impl Padded for String {
-    fn pad_front(&self) -> String {
-        self.as_str().pad_front()
-    }
-
-    fn terminate(&self, terminator: &str) -> String {
-        self.as_str().terminate(terminator)
-    }
-}
-
-impl Padded for &[u8] {
-    fn pad_front(&self) -> String {
-        format!(" {}", std::str::from_utf8(self).unwrap())
-    }
-
-    fn terminate(&self, terminator: &str) -> String {
-        format!("{}{terminator}", std::str::from_utf8(self).unwrap())
-    }
-}
-
-impl Padded for Vec<u8> {
-    fn pad_front(&self) -> String {
-        self.as_slice().pad_front()
-    }
-
-    fn terminate(&self, terminator: &str) -> String {
-        self.as_slice().terminate(terminator)
-    }
-}
@@ -0,0 +1,93 @@
+use std::ops::Range;
+
+use getters::Getters;
+
+use crate::ast_source::SrcLocation;
+
+/// Output byte ranges of the printed nodes, recorded when the [`PrintContext`] asks for it.
+///
+/// [`PrintContext`]: super::PrintContext
+#[derive(Debug, Clone, Default, PartialEq, Eq, Getters)]
+pub struct SourceMap {
+    /// In pre-order: a node comes before the nodes printed inside it.
+    #[return_type = "&[SourceMapping]"]
+    mappings: Vec<SourceMapping>,
+}
+
+/// Output bytes printed for one node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Getters)]
+pub struct SourceMapping {
+    /// Offset of the first byte in the output.
+    #[copy]
+    start: usize,
+    #[copy]
+    length: usize,
+    /// `None` for node types without an `id`, like the Yul nodes.
+    #[copy]
+    id: Option<isize>,
+    /// `src` of the node in the original file.
+    #[copy]
+    src: SrcLocation,
+}
+
+impl SourceMapping {
+    /// Exclusive end offset in the output.
+    pub fn end(&self) -> usize {
+        self.start + self.length
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end()
+    }
+}
+
+impl SourceMap {
+    /// Innermost node printed at `offset` of the output.
+    pub fn at(&self, offset: usize) -> Option<&SourceMapping> {
+        self.mappings
+            .iter()
+            .rev()
+            .find(|mapping| mapping.range().contains(&offset))
+    }
+
+    /// Every printing of the node with `id`, a node printed twice is mapped twice.
+    pub fn by_id(&self, id: isize) -> impl Iterator<Item = &SourceMapping> {
+        self.mappings
+            .iter()
+            .filter(move |mapping| mapping.id == Some(id))
+    }
+
+    /// Slot for a node starting at `start`, its length set by [`Self::close`].
+    pub(super) fn open(&mut self, start: usize, id: Option<isize>, src: SrcLocation) -> usize {
+        self.mappings.push(SourceMapping {
+            start,
+            length: 0,
+            id,
+            src,
+        });
+        self.mappings.len() - 1
+    }
+
+    pub(super) fn close(&mut self, slot: usize, end: usize) {
+        let mapping = &mut self.mappings[slot];
+        mapping.length = end.saturating_sub(mapping.start);
+    }
+
+    /// Mappings from `from` on, moved to start at offset `base`.
+    pub(super) fn split_off(&mut self, from: usize, base: usize) -> Vec<SourceMapping> {
+        let mut mappings = self.mappings.split_off(from);
+        for mapping in &mut mappings {
+            mapping.start -= base;
+        }
+        mappings
+    }
+
+    /// Appends `mappings` of output written at `base`.
+    pub(super) fn extend_at(&mut self, mappings: &[SourceMapping], base: usize) {
+        self.mappings
+            .extend(mappings.iter().map(|mapping| SourceMapping {
+                start: mapping.start + base,
+                ..*mapping
+            }));
+    }
+}
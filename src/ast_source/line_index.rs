@@ -0,0 +1,186 @@
+use std::{borrow::Cow, ops::RangeInclusive, sync::OnceLock};
+
+use getters::Getters;
+
+use super::SrcLocation;
+
+/// How columns are counted within a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnEncoding {
+    /// Bytes, as in solc offsets.
+    #[default]
+    Utf8,
+    /// UTF-16 code units, as in LSP positions.
+    Utf16,
+}
+
+/// 1-based line and column.
+#[derive(Debug, Clone, Copy, Getters, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LineColumn {
+    #[copy]
+    line: usize,
+    #[copy]
+    column: usize,
+}
+
+impl LineColumn {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+/// Line start offsets of one source file, for converting between byte offsets
+/// and line/column positions.
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Cow<'a, [usize]>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            line_starts: Cow::Owned(line_starts(source)),
+        }
+    }
+
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Position of the byte at `offset`; `offset == len` addresses the end of the file.
+    pub fn line_column(&self, offset: usize, encoding: ColumnEncoding) -> Option<LineColumn> {
+        if offset > self.source.len() || !self.source.is_char_boundary(offset) {
+            return None;
+        }
+
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        let prefix = &self.source[self.line_starts[line - 1]..offset];
+        let column = match encoding {
+            ColumnEncoding::Utf8 => prefix.len(),
+            ColumnEncoding::Utf16 => prefix.encode_utf16().count(),
+        };
+
+        Some(LineColumn::new(line, column + 1))
+    }
+
+    /// Byte offset of `position`, the inverse of [`Self::line_column`].
+    pub fn offset(&self, position: LineColumn, encoding: ColumnEncoding) -> Option<usize> {
+        let line_start = *self.line_starts.get(position.line.checked_sub(1)?)?;
+        // The `\r` of a `\r\n` break is a position of the line
+        let line_end = self
+            .line_starts
+            .get(position.line)
+            .map_or(self.source.len(), |next| next - 1);
+        let line = &self.source[line_start..line_end];
+        let column = position.column.checked_sub(1)?;
+
+        let in_line = match encoding {
+            ColumnEncoding::Utf8 => {
+                (column <= line.len() && line.is_char_boundary(column)).then_some(column)?
+            }
+            ColumnEncoding::Utf16 => {
+                let mut units = 0;
+                let mut bytes = None;
+                for (i, c) in line.char_indices().chain([(line.len(), '\0')]) {
+                    if units == column {
+                        bytes = Some(i);
+                        break;
+                    }
+                    if units > column {
+                        break;
+                    }
+                    units += c.len_utf16();
+                }
+                bytes?
+            }
+        };
+
+        Some(line_start + in_line)
+    }
+
+    /// Start and exclusive end position of `location`.
+    pub fn location_range(
+        &self,
+        location: SrcLocation,
+        encoding: ColumnEncoding,
+    ) -> Option<(LineColumn, LineColumn)> {
+        Some((
+            self.line_column(location.start(), encoding)?,
+            self.line_column(location.end(), encoding)?,
+        ))
+    }
+
+    /// Lines spanned by `location`, counting its last byte rather than its end.
+    pub fn lines_of(&self, location: SrcLocation) -> Option<RangeInclusive<usize>> {
+        let last = location.start() + location.length().saturating_sub(1);
+        if location.end() > self.source.len() {
+            return None;
+        }
+
+        Some(self.line_of(location.start())?..=self.line_of(last)?)
+    }
+
+    /// Text of the 1-based `line`, without its line break.
+    pub fn line_text(&self, line: usize) -> Option<&'a str> {
+        self.lines_text(line..=line)
+    }
+
+    /// Text of the 1-based lines in `lines`, clamped to the file, without the trailing line break,
+    /// `\n` or `\r\n`.
+    pub fn lines_text(&self, lines: RangeInclusive<usize>) -> Option<&'a str> {
+        let first = (*lines.start()).max(1);
+        let last = (*lines.end()).min(self.line_count());
+        if first > last {
+            return None;
+        }
+
+        let from = self.line_starts[first - 1];
+        let to = self
+            .line_starts
+            .get(last)
+            .map_or(self.source.len(), |next| next - 1);
+
+        let text = self.source.get(from..to)?;
+        Some(text.strip_suffix('\r').unwrap_or(text))
+    }
+
+    fn line_of(&self, offset: usize) -> Option<usize> {
+        (offset <= self.source.len()).then(|| self.line_starts.partition_point(|s| *s <= offset))
+    }
+}
+
+fn line_starts(source: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+        .collect()
+}
+
+/// Line starts of a source kept by its owner, computed on first use.
+///
+/// Compares equal to any other, as it only caches what the source already determines.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LineStarts(OnceLock<Vec<usize>>);
+
+impl LineStarts {
+    /// Index of `source`, which must be the source the starts were computed for.
+    pub(crate) fn index<'a>(&'a self, source: &'a str) -> LineIndex<'a> {
+        LineIndex {
+            source,
+            line_starts: Cow::Borrowed(self.0.get_or_init(|| line_starts(source))),
+        }
+    }
+}
+
+impl PartialEq for LineStarts {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for LineStarts {}
@@ -0,0 +1,87 @@
+//! Original source text behind a node's `src`.
+
+mod line_index;
+mod src_location;
+
+use std::ops::RangeInclusive;
+
+pub(crate) use line_index::LineStarts;
+pub use line_index::{ColumnEncoding, LineColumn, LineIndex};
+pub use src_location::SrcLocation;
+
+use crate::ast_specs::{NodeTypeInternalRef, SourceUnit};
+
+/// Anything that can hand out the content of a source file by its `fileIndex`.
+pub trait SourceProvider {
+    fn source(&self, file_index: usize) -> Option<&str>;
+
+    /// Line index of the file, built on every call unless the provider keeps one per file.
+    fn line_index(&self, file_index: usize) -> Option<LineIndex<'_>> {
+        self.source(file_index).map(LineIndex::new)
+    }
+}
+
+/// A source text with the `fileIndex` the `src` of its nodes carry.
+impl<S: AsRef<str>> SourceProvider for (usize, S) {
+    fn source(&self, file_index: usize) -> Option<&str> {
+        (self.0 == file_index).then(|| self.1.as_ref())
+    }
+}
+
+impl SourceProvider for SourceUnit {
+    fn source(&self, file_index: usize) -> Option<&str> {
+        if self.file_index() == Some(file_index) {
+            self.source()
+        } else {
+            None
+        }
+    }
+
+    fn line_index(&self, file_index: usize) -> Option<LineIndex<'_>> {
+        let source = SourceProvider::source(self, file_index)?;
+        Some(self.line_starts().index(source))
+    }
+}
+
+impl<'a> NodeTypeInternalRef<'a> {
+    /// Parsed `src` of the node, `None` for nodes without one.
+    pub fn src_location(&self) -> Option<SrcLocation> {
+        self.try_src()?.parse().ok()
+    }
+
+    /// Source text of the node.
+    pub fn text<'s, S: SourceProvider + ?Sized>(&self, sources: &'s S) -> Option<&'s str> {
+        let location = self.src_location()?;
+        sources
+            .source(location.file_index()?)?
+            .get(location.range())
+    }
+
+    /// Line index of the file the node belongs to.
+    pub fn line_index<'s, S: SourceProvider + ?Sized>(
+        &self,
+        sources: &'s S,
+    ) -> Option<LineIndex<'s>> {
+        sources.line_index(self.src_location()?.file_index()?)
+    }
+
+    /// 1-based lines spanned by the node.
+    pub fn line_range<S: SourceProvider + ?Sized>(
+        &self,
+        sources: &S,
+    ) -> Option<RangeInclusive<usize>> {
+        self.line_index(sources)?.lines_of(self.src_location()?)
+    }
+
+    /// Full lines spanned by the node, extended by `lines` lines before and after.
+    pub fn context<'s, S: SourceProvider + ?Sized>(
+        &self,
+        sources: &'s S,
+        lines: usize,
+    ) -> Option<&'s str> {
+        let index = self.line_index(sources)?;
+        let range = index.lines_of(self.src_location()?)?;
+
+        index.lines_text(range.start().saturating_sub(lines)..=range.end() + lines)
+    }
+}
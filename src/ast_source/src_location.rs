@@ -0,0 +1,96 @@
+use std::{fmt::Display, ops::Range, str::FromStr};
+
+use getters::Getters;
+
+use crate::{AstParserError, ErrorKind};
+
+/// Parsed `src` attribute, `start:length:fileIndex` with byte offsets.
+#[derive(Debug, Clone, Copy, Getters, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SrcLocation {
+    #[copy]
+    start: usize,
+    #[copy]
+    length: usize,
+    /// `None` for `-1`, which solc uses for code without a source file.
+    #[copy]
+    file_index: Option<usize>,
+}
+
+impl SrcLocation {
+    pub fn new(start: usize, length: usize, file_index: Option<usize>) -> Self {
+        Self {
+            start,
+            length,
+            file_index,
+        }
+    }
+
+    /// Exclusive end offset.
+    pub fn end(&self) -> usize {
+        self.start + self.length
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end()
+    }
+
+    pub fn contains_offset(&self, offset: usize) -> bool {
+        self.range().contains(&offset)
+    }
+
+    /// Whether `other` lies within `self`, bounds included. Both must be in the same file.
+    pub fn contains(&self, other: &SrcLocation) -> bool {
+        self.file_index == other.file_index
+            && self.start <= other.start
+            && other.end() <= self.end()
+    }
+
+    /// Whether `self` and `other` share at least one byte of the same file.
+    pub fn overlaps(&self, other: &SrcLocation) -> bool {
+        self.file_index == other.file_index && self.start < other.end() && other.start < self.end()
+    }
+}
+
+impl FromStr for SrcLocation {
+    type Err = AstParserError;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let invalid = || AstParserError::from(ErrorKind::InvalidSrc(src.to_owned()));
+
+        let mut parts = src.split(':');
+        let mut next = || parts.next().ok_or_else(invalid);
+        let start = next()?.parse().map_err(|_| invalid())?;
+        let length = next()?.parse().map_err(|_| invalid())?;
+        let file_index = match next()?.parse::<isize>().map_err(|_| invalid())? {
+            -1 => None,
+            index => Some(usize::try_from(index).map_err(|_| invalid())?),
+        };
+
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            start,
+            length,
+            file_index,
+        })
+    }
+}
+
+impl TryFrom<&str> for SrcLocation {
+    type Error = AstParserError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Display for SrcLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.file_index {
+            Some(file_index) => write!(f, "{}:{}:{}", self.start, self.length, file_index),
+            None => write!(f, "{}:{}:-1", self.start, self.length),
+        }
+    }
+}
@@ -15,7 +15,7 @@ pub struct EventDefinition {
     name: String,
     #[serde(rename = "nameLocation")]
     name_location: Option<String>,
-    parameters: ParameterList,
+    pub(crate) parameters: ParameterList,
     #[return_type = "&str"]
     src: String,
 }
@@ -6,6 +6,8 @@ use serde::Deserialize;
 
 pub use prelude::*;
 
+use super::Unknown;
+
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "nodeType")]
 pub enum BaseNode {
@@ -18,4 +20,6 @@ pub enum BaseNode {
     VariableDeclaration(VariableDeclaration),
     EventDefinition(EventDefinition),
     ModifierDefinition(ModifierDefinition),
+    #[serde(untagged)]
+    Unknown(Unknown),
 }
@@ -9,7 +9,7 @@ use crate::ast_specs::common::{
 pub struct ModifierDefinition {
     #[serde(rename = "baseModifiers")]
     base_modifiers: Option<Vec<isize>>,
-    body: Block,
+    pub(crate) body: Block,
     documentation: Option<StructuredDocumentation>,
     #[copy]
     id: isize,
@@ -18,8 +18,8 @@ pub struct ModifierDefinition {
     name_location: Option<String>,
     #[return_type = "Option<&OverrideSpecifier>"]
     #[use_as_ref]
-    overrides: Option<OverrideSpecifier>,
-    parameters: ParameterList,
+    pub(crate) overrides: Option<OverrideSpecifier>,
+    pub(crate) parameters: ParameterList,
     src: String,
     #[serde(rename = "virtual")]
     _virtual: Option<bool>,
@@ -3,7 +3,7 @@ use serde::Deserialize;
 
 use crate::ast_specs::Statement;
 
-#[derive(Deserialize, Debug, Clone, Getters, PartialEq, Eq)]
+#[derive(Deserialize, Debug, Clone, Getters, PartialEq, Eq, Default)]
 pub struct Block {
     documentation: Option<String>,
     #[copy]
@@ -12,5 +12,14 @@ pub struct Block {
     src: String,
     #[use_as_deref]
     #[return_type = "Option<&[Statement]>"]
-    statements: Option<Vec<Statement>>,
+    pub(crate) statements: Option<Vec<Statement>>,
+}
+
+impl Block {
+    pub fn artificial_new(statements: Vec<Statement>) -> Self {
+        Self {
+            statements: Some(statements),
+            ..Default::default()
+        }
+    }
 }
@@ -63,6 +63,17 @@ pub enum StorageLocation {
     Storage,
 }
 
+impl Display for StorageLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageLocation::Calldata => f.write_str("calldata"),
+            StorageLocation::Default => f.write_str("default"),
+            StorageLocation::Memory => f.write_str("memory"),
+            StorageLocation::Storage => f.write_str("storage"),
+        }
+    }
+}
+
 // #[derive(Deserialize, Debug, Clone)] TODO:
 // pub enum FunctionList {
 //     function {}
@@ -78,6 +89,16 @@ pub enum FunctionCallKind {
     StructConstructorCall,
 }
 
+impl Display for FunctionCallKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FunctionCallKind::FunctionCall => f.write_str("functionCall"),
+            FunctionCallKind::TypeConversion => f.write_str("typeConversion"),
+            FunctionCallKind::StructConstructorCall => f.write_str("structConstructorCall"),
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LiteralKind {
     #[serde(rename = "bool")]
@@ -92,6 +113,18 @@ pub enum LiteralKind {
     UnicodeString,
 }
 
+impl Display for LiteralKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LiteralKind::Bool => f.write_str("bool"),
+            LiteralKind::Number => f.write_str("number"),
+            LiteralKind::String => f.write_str("string"),
+            LiteralKind::HexString => f.write_str("hexString"),
+            LiteralKind::UnicodeString => f.write_str("unicodeString"),
+        }
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StateMutability {
     #[serde(rename = "payable")]
@@ -11,12 +11,12 @@ use super::identifier_path::IdentifierPath;
 pub struct ModifierInvocation {
     #[return_type = "Option<&Vec<Expression>>"]
     #[use_as_ref]
-    arguments: Option<Vec<Expression>>,
+    pub(crate) arguments: Option<Vec<Expression>>,
     #[copy]
     id: isize,
     kind: Option<ModifierKind>,
     #[serde(rename = "modifierName")]
-    modifier_name: ModifierName,
+    pub(crate) modifier_name: ModifierName,
     #[return_type = "&str"]
     src: String,
 }
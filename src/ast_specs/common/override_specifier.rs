@@ -8,7 +8,7 @@ pub struct OverrideSpecifier {
     #[copy]
     id: isize,
     #[return_type = "&[Overrides]"]
-    overrides: Vec<Overrides>,
+    pub(crate) overrides: Vec<Overrides>,
     src: String,
 }
 
@@ -8,7 +8,7 @@ pub struct ParameterList {
     #[copy]
     id: isize,
     #[return_type = "&[VariableDeclaration]"]
-    parameters: Vec<VariableDeclaration>,
+    pub(crate) parameters: Vec<VariableDeclaration>,
     src: String,
 }
 
@@ -1,7 +1,7 @@
 use getters::Getters;
 use serde::Deserialize;
 
-use crate::ast_specs::Expression;
+use crate::ast_specs::{Expression, Unknown};
 
 use super::{
     StateMutability, Visibility, identifier_path::IdentifierPath, parameter_list::ParameterList,
@@ -37,6 +37,8 @@ pub enum TypeName {
     FunctionTypeName(FunctionTypeName),
     Mapping(Mapping),
     UserDefinedTypeName(UserDefinedTypeName),
+    #[serde(untagged)]
+    Unknown(Unknown),
 }
 
 impl_type_conversion!(ArrayTypeName);
@@ -58,6 +60,11 @@ impl TypeName {
             TypeName::FunctionTypeName(ft_name) => ft_name.name(),
             TypeName::Mapping(mapping) => mapping.name(),
             TypeName::UserDefinedTypeName(udt_name) => udt_name.name(),
+            TypeName::Unknown(unknown) => unknown
+                .type_descriptions()
+                .type_string()
+                .unwrap_or(unknown.node_type())
+                .to_owned(),
         }
     }
 }
@@ -67,11 +74,11 @@ pub struct ArrayTypeName {
     #[serde(rename = "baseType")]
     #[use_as_ref]
     #[return_type = "&TypeName"]
-    base_type: Box<TypeName>,
+    pub(crate) base_type: Box<TypeName>,
     #[copy]
     id: isize,
     #[skip_getter]
-    length: Option<Box<Expression>>,
+    pub(crate) length: Option<Box<Expression>>,
     src: String,
     #[serde(rename = "typeDescriptions")]
     type_descriptions: TypeDescriptions,
@@ -88,9 +95,9 @@ pub struct FunctionTypeName {
     #[copy]
     id: isize,
     #[serde(rename = "parameterTypes")]
-    parameter_types: ParameterList,
+    pub(crate) parameter_types: ParameterList,
     #[serde(rename = "returnParameterTypes")]
-    return_parameter_types: ParameterList,
+    pub(crate) return_parameter_types: ParameterList,
     src: String,
     #[serde(rename = "stateMutability")]
     state_mutability: StateMutability,
@@ -145,7 +152,7 @@ pub struct Mapping {
     key_name_location: Option<String>,
     #[serde(rename = "keyType")]
     #[return_type = "&TypeName"]
-    key_type: Box<TypeName>,
+    pub(crate) key_type: Box<TypeName>,
     src: String,
     #[serde(rename = "typeDescriptions")]
     type_descriptions: TypeDescriptions,
@@ -157,7 +164,7 @@ pub struct Mapping {
     value_name_location: Option<String>,
     #[serde(rename = "valueType")]
     #[return_type = "&TypeName"]
-    value_type: Box<TypeName>,
+    pub(crate) value_type: Box<TypeName>,
 }
 
 impl Mapping {
@@ -182,7 +189,7 @@ pub struct UserDefinedTypeName {
     #[serde(rename = "pathNode")]
     #[use_as_ref]
     #[return_type = "Option<&IdentifierPath>"]
-    path_node: Option<IdentifierPath>,
+    pub(crate) path_node: Option<IdentifierPath>,
     #[serde(rename = "referencedDeclaration")]
     #[copy]
     referenced_declaration: isize,
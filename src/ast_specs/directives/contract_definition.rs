@@ -35,7 +35,7 @@ pub struct ContractDefinition {
     #[serde(rename = "nameLocation")]
     name_location: Option<String>,
     #[return_type = "&[BaseNode]"]
-    nodes: Vec<BaseNode>,
+    pub(crate) nodes: Vec<BaseNode>,
     scope: isize,
     src: String,
     #[serde(rename = "usedErrors")]
@@ -11,7 +11,7 @@ pub struct EnumDefinition {
     #[copy]
     id: isize,
     #[return_type = "&[EnumValue]"]
-    members: Vec<EnumValue>,
+    pub(crate) members: Vec<EnumValue>,
     name: String,
     #[serde(rename = "nameLocation")]
     name_location: Option<String>,
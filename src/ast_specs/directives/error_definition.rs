@@ -14,6 +14,6 @@ pub struct ErrorDefinition {
     name: String,
     #[serde(rename = "nameLocation")]
     name_location: Option<String>,
-    parameters: ParameterList,
+    pub(crate) parameters: ParameterList,
     src: String,
 }
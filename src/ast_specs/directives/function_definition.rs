@@ -16,7 +16,7 @@ pub struct FunctionDefinition {
     base_functions: Option<Vec<isize>>,
     #[return_type = "Option<&Block>"]
     #[use_as_ref]
-    body: Option<Block>,
+    pub(crate) body: Option<Block>,
     documentation: Option<StructuredDocumentation>,
     #[serde(rename = "functionSelector")]
     function_selector: Option<String>,
@@ -26,21 +26,21 @@ pub struct FunctionDefinition {
     #[copy]
     kind: FunctionKind,
     #[return_type = "&[ModifierInvocation]"]
-    modifiers: Vec<ModifierInvocation>,
+    pub(crate) modifiers: Vec<ModifierInvocation>,
     #[return_type = "&str"]
     name: String,
     #[serde(rename = "nameLocation")]
     name_location: Option<String>,
     #[return_type = "Option<&OverrideSpecifier>"]
     #[use_as_ref]
-    overrides: Option<OverrideSpecifier>,
+    pub(crate) overrides: Option<OverrideSpecifier>,
     #[return_type = "Option<&ParameterList>"]
     #[use_as_ref]
-    parameters: Option<ParameterList>,
+    pub(crate) parameters: Option<ParameterList>,
     #[serde(rename = "returnParameters")]
     #[return_type = "Option<&ParameterList>"]
     #[use_as_ref]
-    return_parameters: Option<ParameterList>,
+    pub(crate) return_parameters: Option<ParameterList>,
     #[copy]
     scope: isize,
     #[return_type = "&str"]
@@ -17,7 +17,7 @@ pub struct ImportDirective {
     source_unit: isize,
     src: String,
     #[serde(rename = "symbolAliases")]
-    symbol_aliases: Vec<SymbolAliases>,
+    pub(crate) symbol_aliases: Vec<SymbolAliases>,
     #[serde(rename = "unitAlias")]
     unit_alias: String,
 }
@@ -34,7 +34,7 @@ impl ImportDirective {
 
 #[derive(Deserialize, Debug, Clone, Getters, Default, PartialEq, Eq)]
 pub struct SymbolAliases {
-    foreign: Identifier,
+    pub(crate) foreign: Identifier,
     #[use_as_deref]
     #[return_type = "Option<&str>"]
     local: Option<String>,
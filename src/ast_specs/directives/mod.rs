@@ -13,7 +13,7 @@ mod variable_declaration;
 pub use prelude::*;
 use serde::Deserialize;
 
-use super::EventDefinition;
+use super::{EventDefinition, Unknown};
 
 pub type Directives = Vec<Directive>;
 
@@ -31,6 +31,8 @@ pub enum Directive {
     UserDefinedValueTypeDefinition(UserDefinedValueTypeDefinition),
     UsingForDirective(UsingForDirective),
     VariableDeclaration(VariableDeclaration),
+    #[serde(untagged)]
+    Unknown(Unknown),
 }
 
 macro_rules! into_enum {
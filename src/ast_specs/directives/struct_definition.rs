@@ -13,7 +13,7 @@ pub struct StructDefinition {
     id: isize,
     documentation: Option<StructuredDocumentation>,
     #[return_type = "&[VariableDeclaration]"]
-    members: Vec<VariableDeclaration>,
+    pub(crate) members: Vec<VariableDeclaration>,
     #[return_type = "&str"]
     name: String,
     #[serde(rename = "nameLocation")]
@@ -14,5 +14,5 @@ pub struct UserDefinedValueTypeDefinition {
     name_location: Option<String>,
     src: String,
     #[serde(rename = "underlyingType")]
-    underlying_type: TypeName,
+    pub(crate) underlying_type: TypeName,
 }
@@ -8,17 +8,17 @@ pub struct UsingForDirective {
     #[serde(rename = "functionList")]
     #[use_as_ref]
     #[return_type = "Option<&serde_json::Value>"]
-    function_list: Option<serde_json::Value>,
+    pub(crate) function_list: Option<serde_json::Value>,
     global: Option<bool>,
     #[copy]
     id: isize,
     #[serde(rename = "libraryName")]
     #[return_type = "Option<&LibraryName>"]
     #[use_as_ref]
-    library_name: Option<LibraryName>,
+    pub(crate) library_name: Option<LibraryName>,
     src: String,
     #[serde(rename = "typeName")]
     #[return_type = "Option<&TypeName>"]
     #[use_as_ref]
-    type_name: Option<TypeName>,
+    pub(crate) type_name: Option<TypeName>,
 }
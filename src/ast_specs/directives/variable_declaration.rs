@@ -26,7 +26,7 @@ pub struct VariableDeclaration {
     name: String,
     #[serde(rename = "nameLocation")]
     name_location: Option<String>,
-    overrides: Option<OverrideSpecifier>,
+    pub(crate) overrides: Option<OverrideSpecifier>,
     scope: isize,
     src: String,
     #[serde(rename = "stateVariable")]
@@ -37,7 +37,7 @@ pub struct VariableDeclaration {
     #[serde(rename = "typeDescriptions")]
     type_descriptions: TypeDescriptions,
     #[serde(rename = "typeName")]
-    type_name: Option<TypeName>,
-    value: Option<Expression>,
+    pub(crate) type_name: Option<TypeName>,
+    pub(crate) value: Option<Expression>,
     visibility: Visibility,
 }
@@ -10,7 +10,7 @@ pub struct Assignment {
     #[serde(rename = "argumentTypes")]
     #[use_as_deref]
     #[return_type = "Option<&[TypeDescriptions]>"]
-    argument_types: Option<Vec<TypeDescriptions>>,
+    pub(crate) argument_types: Option<Vec<TypeDescriptions>>,
     #[copy]
     id: isize,
     #[serde(rename = "isConstant")]
@@ -23,12 +23,12 @@ pub struct Assignment {
     l_value_requested: bool,
     #[serde(rename = "leftHandSide")]
     #[return_type = "&Expression"]
-    left_hand_side: Box<Expression>,
+    pub(crate) left_hand_side: Box<Expression>,
     #[return_type = "&str"]
     operator: String,
     #[serde(rename = "rightHandSide")]
     #[return_type = "&Expression"]
-    right_hand_side: Box<Expression>,
+    pub(crate) right_hand_side: Box<Expression>,
     src: String,
     #[serde(rename = "typeDescriptions")]
     type_descriptions: TypeDescriptions,
@@ -9,7 +9,7 @@ use super::Expression;
 pub struct BinaryOperation {
     #[serde(rename = "argumentTypes")]
     #[skip_getter]
-    argument_types: Option<Vec<TypeDescriptions>>,
+    pub(crate) argument_types: Option<Vec<TypeDescriptions>>,
     #[serde(rename = "commonType")]
     common_type: TypeDescriptions,
     function: Option<isize>,
@@ -25,12 +25,12 @@ pub struct BinaryOperation {
     l_value_requested: bool,
     #[serde(rename = "leftExpression")]
     #[return_type = "&Expression"]
-    left_expression: Box<Expression>,
+    pub(crate) left_expression: Box<Expression>,
     #[return_type = "&str"]
     operator: String,
     #[serde(rename = "rightExpression")]
     #[return_type = "&Expression"]
-    right_expression: Box<Expression>,
+    pub(crate) right_expression: Box<Expression>,
     src: String,
     #[serde(rename = "typeDescriptions")]
     type_descriptions: TypeDescriptions,
@@ -10,12 +10,12 @@ pub struct Conditional {
     #[serde(rename = "argumentTypes")]
     #[use_as_ref]
     #[return_type = "Option<&Vec<TypeDescriptions>>"]
-    argument_types: Option<Vec<TypeDescriptions>>,
+    pub(crate) argument_types: Option<Vec<TypeDescriptions>>,
     #[return_type = "&Expression"]
-    condition: Box<Expression>,
+    pub(crate) condition: Box<Expression>,
     #[serde(rename = "falseExpression")]
     #[return_type = "&Expression"]
-    false_expression: Box<Expression>,
+    pub(crate) false_expression: Box<Expression>,
     #[copy]
     id: isize,
     #[serde(rename = "isConstant")]
@@ -29,7 +29,7 @@ pub struct Conditional {
     src: String,
     #[serde(rename = "trueExpression")]
     #[return_type = "&Expression"]
-    true_expression: Box<Expression>,
+    pub(crate) true_expression: Box<Expression>,
     #[serde(rename = "typeDescriptions")]
     type_descriptions: TypeDescriptions,
 }
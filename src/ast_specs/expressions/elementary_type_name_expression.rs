@@ -8,7 +8,7 @@ pub struct ElementaryTypeNameExpression {
     #[serde(rename = "argumentTypes")]
     #[use_as_ref]
     #[return_type = "Option<&Vec<TypeDescriptions>>"]
-    argument_types: Option<Vec<TypeDescriptions>>,
+    pub(crate) argument_types: Option<Vec<TypeDescriptions>>,
     #[copy]
     id: isize,
     #[serde(rename = "isConstant")]
@@ -23,7 +23,7 @@ pub struct ElementaryTypeNameExpression {
     #[serde(rename = "typeDescriptions")]
     type_descriptions: TypeDescriptions,
     #[serde(rename = "typeName")]
-    type_name: CompatabilityTypeName,
+    pub(crate) type_name: CompatabilityTypeName,
 }
 
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -10,12 +10,12 @@ pub struct FunctionCall {
     #[serde(rename = "argumentTypes")]
     #[use_as_ref]
     #[return_type = "Option<&Vec<TypeDescriptions>>"]
-    argument_types: Option<Vec<TypeDescriptions>>,
+    pub(crate) argument_types: Option<Vec<TypeDescriptions>>,
     #[use_as_ref]
     #[return_type = "&[Expression]"]
-    arguments: Vec<Expression>,
+    pub(crate) arguments: Vec<Expression>,
     #[return_type = "&Expression"]
-    expression: Box<Expression>,
+    pub(crate) expression: Box<Expression>,
     #[copy]
     id: isize,
     #[serde(rename = "isConstant")]
@@ -10,9 +10,9 @@ pub struct FunctionCallOptions {
     #[serde(rename = "argumentTypes")]
     #[use_as_ref]
     #[return_type = "Option<&Vec<TypeDescriptions>>"]
-    argument_types: Option<Vec<TypeDescriptions>>,
+    pub(crate) argument_types: Option<Vec<TypeDescriptions>>,
     #[return_type = "&Expression"]
-    expression: Box<Expression>,
+    pub(crate) expression: Box<Expression>,
     #[copy]
     id: isize,
     #[serde(rename = "isConstant")]
@@ -25,7 +25,7 @@ pub struct FunctionCallOptions {
     l_value_requested: bool,
     names: Vec<String>,
     #[return_type = "&[Expression]"]
-    options: Vec<Expression>,
+    pub(crate) options: Vec<Expression>,
     src: String,
     #[serde(rename = "typeDescriptions")]
     type_descriptions: TypeDescriptions,
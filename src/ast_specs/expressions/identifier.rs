@@ -10,7 +10,7 @@ pub struct Identifier {
     #[serde(rename = "argumentTypes")]
     #[use_as_ref]
     #[return_type = "Option<&Vec<TypeDescriptions>>"]
-    argument_types: Option<Vec<TypeDescriptions>>,
+    pub(crate) argument_types: Option<Vec<TypeDescriptions>>,
     #[copy]
     id: isize,
     #[return_type = "&str"]
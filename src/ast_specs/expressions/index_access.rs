@@ -10,16 +10,16 @@ pub struct IndexAccess {
     #[serde(rename = "argumentTypes")]
     #[use_as_ref]
     #[return_type = "Option<&Vec<TypeDescriptions>>"]
-    argument_types: Option<Vec<TypeDescriptions>>,
+    pub(crate) argument_types: Option<Vec<TypeDescriptions>>,
     #[serde(rename = "baseExpression")]
     #[return_type = "&Expression"]
-    base_expression: Box<Expression>,
+    pub(crate) base_expression: Box<Expression>,
     #[copy]
     id: isize,
     #[serde(rename = "indexExpression")]
     #[use_as_deref]
     #[return_type = "Option<&Expression>"]
-    index_expression: Option<Box<Expression>>,
+    pub(crate) index_expression: Option<Box<Expression>>,
     #[serde(rename = "isConstant")]
     is_constant: bool,
     #[serde(rename = "isLValue")]
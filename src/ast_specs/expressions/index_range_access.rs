@@ -10,14 +10,14 @@ pub struct IndexRangeAccess {
     #[serde(rename = "argumentTypes")]
     #[return_type = "Option<&Vec<TypeDescriptions>>"]
     #[use_as_ref]
-    argument_types: Option<Vec<TypeDescriptions>>,
+    pub(crate) argument_types: Option<Vec<TypeDescriptions>>,
     #[serde(rename = "baseExpression")]
     #[return_type = "&Expression"]
-    base_expression: Box<Expression>,
+    pub(crate) base_expression: Box<Expression>,
     #[serde(rename = "endExpression")]
     #[return_type = "Option<&Expression>"]
     #[use_as_deref]
-    end_expression: Option<Box<Expression>>,
+    pub(crate) end_expression: Option<Box<Expression>>,
     #[copy]
     id: isize,
     #[serde(rename = "isConstant")]
@@ -32,7 +32,7 @@ pub struct IndexRangeAccess {
     #[serde(rename = "startExpression")]
     #[return_type = "Option<&Expression>"]
     #[use_as_deref]
-    start_expression: Option<Box<Expression>>,
+    pub(crate) start_expression: Option<Box<Expression>>,
     #[serde(rename = "typeDescriptions")]
     type_descriptions: TypeDescriptions,
 }
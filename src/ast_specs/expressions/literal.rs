@@ -8,7 +8,7 @@ pub struct Literal {
     #[use_as_ref]
     #[serde(rename = "argumentTypes")]
     #[return_type = "Option<&Vec<TypeDescriptions>>"]
-    argument_types: Option<Vec<TypeDescriptions>>,
+    pub(crate) argument_types: Option<Vec<TypeDescriptions>>,
     #[serde(rename = "hexValue")]
     hex_value: String,
     #[copy]
@@ -10,9 +10,9 @@ pub struct MemberAccess {
     #[serde(rename = "argumentTypes")]
     #[return_type = "Option<&Vec<TypeDescriptions>>"]
     #[use_as_ref]
-    argument_types: Option<Vec<TypeDescriptions>>,
+    pub(crate) argument_types: Option<Vec<TypeDescriptions>>,
     #[return_type = "&Expression"]
-    expression: Box<Expression>,
+    pub(crate) expression: Box<Expression>,
     #[copy]
     id: isize,
     #[serde(rename = "isConstant")]
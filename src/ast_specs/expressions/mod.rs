@@ -14,7 +14,7 @@ mod prelude;
 mod tuple_expression;
 mod unary_operation;
 
-use crate::ast_specs::{NodeTypeInternalRef, TypeDescriptions, node_type::IntoNTI};
+use crate::ast_specs::{NodeTypeInternalRef, TypeDescriptions, Unknown, node_type::IntoNTI};
 pub use prelude::*;
 use serde::Deserialize;
 
@@ -37,6 +37,8 @@ crate::enum_refs! {
         NewExpression(NewExpression),
         TupleExpression(TupleExpression),
         UnaryOperation(UnaryOperation),
+        #[serde(untagged)]
+        Unknown(Unknown),
     }
 }
 
@@ -89,7 +91,8 @@ ex_delegate_functions!(
     MemberAccess,
     NewExpression,
     TupleExpression,
-    UnaryOperation
+    UnaryOperation,
+    Unknown
 );
 
 impl Expression {
@@ -123,6 +126,7 @@ impl Expression {
             Expression::FunctionCallOptions(fco) => fco.expression().extract_definition(),
             Expression::FunctionCall(fc) => fc.expression().extract_definition(),
             Expression::IndexAccess(ia) => ia.base_expression().extract_definition(),
+            Expression::Unknown(unknown) => unknown.ref_dec_visitor(),
             _ => unimplemented!("{:?}", self),
         }
     }
@@ -143,6 +147,7 @@ impl Expression {
             Expression::NewExpression(_) => todo!(),
             Expression::TupleExpression(_) => todo!(),
             Expression::UnaryOperation(_) => todo!(),
+            Expression::Unknown(_) => false,
         }
     }
 }
@@ -8,7 +8,7 @@ pub struct NewExpression {
     #[serde(rename = "argumentTypes")]
     #[return_type = "Option<&Vec<TypeDescriptions>>"]
     #[use_as_ref]
-    argument_types: Option<Vec<TypeDescriptions>>,
+    pub(crate) argument_types: Option<Vec<TypeDescriptions>>,
     #[copy]
     id: isize,
     #[serde(rename = "isConstant")]
@@ -23,7 +23,7 @@ pub struct NewExpression {
     #[serde(rename = "typeDescriptions")]
     type_descriptions: TypeDescriptions,
     #[serde(rename = "typeName")]
-    type_name: TypeName,
+    pub(crate) type_name: TypeName,
 }
 
 impl NewExpression {
@@ -10,10 +10,10 @@ pub struct TupleExpression {
     #[serde(rename = "argumentTypes")]
     #[use_as_ref]
     #[return_type = "Option<&Vec<TypeDescriptions>>"]
-    argument_types: Option<Vec<TypeDescriptions>>,
+    pub(crate) argument_types: Option<Vec<TypeDescriptions>>,
     #[use_as_ref]
     #[return_type = "&[Option<Expression>]"]
-    components: Vec<Option<Expression>>,
+    pub(crate) components: Vec<Option<Expression>>,
     #[copy]
     id: isize,
     #[serde(rename = "isConstant")]
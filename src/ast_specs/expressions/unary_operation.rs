@@ -10,7 +10,7 @@ pub struct UnaryOperation {
     #[serde(rename = "argumentTypes")]
     #[use_as_ref]
     #[return_type = "Option<&Vec<TypeDescriptions>>"]
-    argument_types: Option<Vec<TypeDescriptions>>,
+    pub(crate) argument_types: Option<Vec<TypeDescriptions>>,
     function: Option<isize>,
     #[copy]
     id: isize,
@@ -28,7 +28,7 @@ pub struct UnaryOperation {
     prefix: bool,
     src: String,
     #[serde(rename = "subExpression")]
-    sub_expression: Box<Expression>,
+    pub(crate) sub_expression: Box<Expression>,
     #[serde(rename = "typeDescriptions")]
     type_descriptions: TypeDescriptions,
 }
@@ -12,6 +12,9 @@ use yul_statements::yul_block::YulBlock;
 pub struct InlineAssembly {
     #[serde(rename = "AST")]
     pub ast: Option<YulBlock>,
+    /// `"evmasm"` in `assembly "evmasm" { ... }`, not part of the compiler output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dialect: Option<String>,
     pub documentation: Option<String>,
     #[serde(rename = "evmVersion")]
     pub evm_version: Option<EvmVersion>,
@@ -12,6 +12,7 @@ pub struct YulFunctionDefinition {
     #[serde(rename = "nativeSrc")]
     pub native_src: Option<String>,
     pub parameters: Option<Vec<YulTypedName>>,
+    #[serde(rename = "returnVariables")]
     pub return_variables: Option<Vec<YulTypedName>>,
     pub src: String,
 }
@@ -8,5 +8,6 @@ pub mod node_type; // TODO: move to suas
 mod prelude;
 pub mod source_unit;
 pub mod statements;
+pub mod unknown;
 
 pub use prelude::*;
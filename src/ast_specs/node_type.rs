@@ -2,7 +2,7 @@ use serde::Deserialize;
 
 use super::{
     BaseNode, Body, Directive, Expression, FalseBody, InitializationExpression, LibraryName,
-    ModifierName, Overrides, SourceUnit, Statement, SymbolAliases, TypeName,
+    ModifierName, Overrides, SourceUnit, Statement, SymbolAliases, TypeName, Unknown,
     base_nodes::{EventDefinition, ModifierDefinition},
     common::{
         ArrayTypeName, Block, ElementaryTypeName, FunctionTypeName, IdentifierPath,
@@ -47,8 +47,8 @@ use super::{
     },
     statements::{
         Break, Continue, DoWhileStatement, EmitStatement, ExpressionStatement, ForStatement,
-        IfStatement, PlaceholderStatement, Return, RevertStatement, TryCatchClause, TryStatement,
-        UncheckedBlock, VariableDeclarationStatement, WhileStatement,
+        IfStatement, PlaceholderStatement, Return, RevertStatement, Throw, TryCatchClause,
+        TryStatement, UncheckedBlock, VariableDeclarationStatement, WhileStatement,
     },
 };
 use crate::{ast_specs::inline_assembly::InlineAssembly, check_node_type};
@@ -86,7 +86,7 @@ macro_rules! global_nodes_logic {
                     $(
                         stringify!($variant) => NodeType::$variant,
                     )*
-                    _ => unreachable!("Should be valid node type, but: {}", value),
+                    _ => NodeType::Unknown,
                 }
             }
         }
@@ -147,6 +147,29 @@ macro_rules! global_nodes_logic {
                     }
                 )*
             }
+
+            /// Whether both refer to the same node in memory, where `==` compares values.
+            pub fn ptr_eq(self, other: NodeTypeInternalRef<'_>) -> bool {
+                match (self, other) {
+                    $(
+                        (NodeTypeInternalRef::$variant(l), NodeTypeInternalRef::$variant(r)) => {
+                            std::ptr::eq(l, r)
+                        }
+                    )*
+                    _ => false,
+                }
+            }
+
+            /// Like [`Self::src`], but `None` for node types without an `src`.
+            pub fn try_src(&self) -> Option<&'a str> {
+                match self {
+                    $(
+                        $(#[cfg(not($no_src))])?
+                        NodeTypeInternalRef::$variant(v) => Some(v.src()),
+                    )*
+                    _ => None,
+                }
+            }
         }
 
         impl NodeTypeInternalRef<'_> {
@@ -157,13 +180,17 @@ macro_rules! global_nodes_logic {
                     }
                 )*
 
-                pub fn id(&self) -> isize {
+                /// `None` for node types without an `id` (Yul nodes, symbol aliases, type
+                /// descriptions, ...), see
+                /// [`AstPath::node_id`](crate::ast_visitor::AstPath::node_id) for an id every
+                /// node has.
+                pub fn id(&self) -> Option<isize> {
                     match self {
                         $(
                             $(#[cfg(not($no_id))])?
-                            NodeTypeInternalRef::$variant(val) => val.id(),
+                            NodeTypeInternalRef::$variant(val) => Some(val.id()),
                         )*
-                        _ => unreachable!("has not id field"),
+                        _ => None,
                     }
                 }
 
@@ -245,7 +272,7 @@ global_nodes_logic! {
     Block,
     Break,
     Conditional,
-    Continue #[no_src: true],
+    Continue,
     ContractDefinition,
     DoWhileStatement,
     ElementaryTypeName,
@@ -280,12 +307,13 @@ global_nodes_logic! {
     ParameterList,
     PlaceholderStatement,
     PragmaDirective,
-    Return #[no_src: true],
+    Return,
     RevertStatement,
     SourceUnit,
     StructDefinition,
     StructuredDocumentation,
     SymbolAliases #[no_src: true] #[no_id: true],
+    Throw,
     TryCatchClause,
     TryStatement,
     TupleExpression,
@@ -314,7 +342,8 @@ global_nodes_logic! {
     YulLiteralValue #[no_src: true] #[no_id: true],
     YulSwitch #[no_src: true] #[no_id: true],
     YulTypedName #[no_src: true] #[no_id: true],
-    YulVariableDeclaration #[no_src: true] #[no_id: true]
+    YulVariableDeclaration #[no_src: true] #[no_id: true],
+    Unknown #[has_refs: true]
 }
 
 macro_rules! enums_into_node_internal {
@@ -355,13 +384,13 @@ macro_rules! enums_into_node_internal {
 
 enums_into_node_internal! {
     Directive: (
-        EventDefinition, ContractDefinition, EnumDefinition, ErrorDefinition, FunctionDefinition, ImportDirective, PragmaDirective, StructDefinition, UserDefinedValueTypeDefinition, UsingForDirective, VariableDeclaration
+        EventDefinition, ContractDefinition, EnumDefinition, ErrorDefinition, FunctionDefinition, ImportDirective, PragmaDirective, StructDefinition, UserDefinedValueTypeDefinition, UsingForDirective, VariableDeclaration, Unknown
     );
     BaseNode: (
-        EnumDefinition, ErrorDefinition, FunctionDefinition, StructDefinition, UserDefinedValueTypeDefinition, UsingForDirective, VariableDeclaration, EventDefinition, ModifierDefinition
+        EnumDefinition, ErrorDefinition, FunctionDefinition, StructDefinition, UserDefinedValueTypeDefinition, UsingForDirective, VariableDeclaration, EventDefinition, ModifierDefinition, Unknown
     );
     Statement: (
-        Block, Break, Continue, DoWhileStatement, EmitStatement, ExpressionStatement, ForStatement, IfStatement, PlaceholderStatement, Return, RevertStatement, TryStatement, UncheckedBlock, VariableDeclarationStatement, WhileStatement, InlineAssembly
+        Block, Break, Continue, DoWhileStatement, EmitStatement, ExpressionStatement, ForStatement, IfStatement, PlaceholderStatement, Return, RevertStatement, Throw, TryStatement, UncheckedBlock, VariableDeclarationStatement, WhileStatement, InlineAssembly, Unknown
     );
     ModifierName: (
         Identifier, IdentifierPath
@@ -370,22 +399,22 @@ enums_into_node_internal! {
         UserDefinedTypeName, IdentifierPath
     );
     TypeName: (
-        ArrayTypeName, ElementaryTypeName, FunctionTypeName, Mapping, UserDefinedTypeName
+        ArrayTypeName, ElementaryTypeName, FunctionTypeName, Mapping, UserDefinedTypeName, Unknown
     );
     LibraryName: (
         UserDefinedTypeName, IdentifierPath
     );
     Expression: (
-        Assignment, BinaryOperation, Conditional, ElementaryTypeNameExpression, FunctionCall, FunctionCallOptions, Identifier, IndexAccess, IndexRangeAccess, Literal, MemberAccess, NewExpression, TupleExpression, UnaryOperation
+        Assignment, BinaryOperation, Conditional, ElementaryTypeNameExpression, FunctionCall, FunctionCallOptions, Identifier, IndexAccess, IndexRangeAccess, Literal, MemberAccess, NewExpression, TupleExpression, UnaryOperation, Unknown
     );
     Body: (
-        Block, Break, Continue, DoWhileStatement, EmitStatement, ExpressionStatement, ForStatement, IfStatement, InlineAssembly, PlaceholderStatement, Return, RevertStatement, TryStatement, UncheckedBlock, VariableDeclarationStatement, WhileStatement
+        Block, Break, Continue, DoWhileStatement, EmitStatement, ExpressionStatement, ForStatement, IfStatement, InlineAssembly, PlaceholderStatement, Return, RevertStatement, Throw, TryStatement, UncheckedBlock, VariableDeclarationStatement, WhileStatement, Unknown
     );
     InitializationExpression: (
         ExpressionStatement, VariableDeclarationStatement
     );
     FalseBody: (
-        Block, Break, Continue, DoWhileStatement, EmitStatement, ExpressionStatement, ForStatement, IfStatement, PlaceholderStatement, Return, RevertStatement, TryStatement, UncheckedBlock, VariableDeclarationStatement, WhileStatement
+        Block, Break, Continue, DoWhileStatement, EmitStatement, ExpressionStatement, ForStatement, IfStatement, PlaceholderStatement, Return, RevertStatement, Throw, TryStatement, UncheckedBlock, VariableDeclarationStatement, WhileStatement, Unknown
     );
     YulStatement: (
         YulAssignment, YulBlock, YulBreak, YulContinue, YulExpressionStatement, YulLeave, YulForLoop, YulFunctionDefinition, YulIf, YulSwitch, YulVariableDeclaration
@@ -395,6 +424,38 @@ enums_into_node_internal! {
     );
 }
 
+impl<'a> NodeTypeInternalRef<'a> {
+    /// Like [`Self::try_src`], also for the Yul nodes and `ExternalReference`, which carry an
+    /// `src` but are listed without one.
+    pub fn own_src(self) -> Option<&'a str> {
+        if let Some(src) = self.try_src() {
+            return Some(src);
+        }
+        let src = match self {
+            NodeTypeInternalRef::ExternalReference(node) => &node.src,
+            NodeTypeInternalRef::YulAssignment(node) => &node.src,
+            NodeTypeInternalRef::YulBlock(node) => &node.src,
+            NodeTypeInternalRef::YulBreak(node) => &node.src,
+            NodeTypeInternalRef::YulCase(node) => &node.src,
+            NodeTypeInternalRef::YulContinue(node) => &node.src,
+            NodeTypeInternalRef::YulExpressionStatement(node) => &node.src,
+            NodeTypeInternalRef::YulForLoop(node) => &node.src,
+            NodeTypeInternalRef::YulFunctionCall(node) => &node.src,
+            NodeTypeInternalRef::YulFunctionDefinition(node) => &node.src,
+            NodeTypeInternalRef::YulIdentifier(node) => &node.src,
+            NodeTypeInternalRef::YulIf(node) => &node.src,
+            NodeTypeInternalRef::YulLeave(node) => &node.src,
+            NodeTypeInternalRef::YulLiteralHexValue(node) => &node.src,
+            NodeTypeInternalRef::YulLiteralValue(node) => &node.src,
+            NodeTypeInternalRef::YulSwitch(node) => &node.src,
+            NodeTypeInternalRef::YulTypedName(node) => &node.src,
+            NodeTypeInternalRef::YulVariableDeclaration(node) => &node.src,
+            _ => return None,
+        };
+        Some(src)
+    }
+}
+
 pub type NTI = NodeTypeInternal;
 pub type NTIref<'a> = NodeTypeInternalRef<'a>;
 
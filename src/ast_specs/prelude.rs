@@ -5,3 +5,4 @@ pub use super::expressions::*;
 pub use super::node_type::*;
 pub use super::source_unit::SourceUnit;
 pub use super::statements::*;
+pub use super::unknown::{Unknown, is_lenient, lenient};
@@ -1,8 +1,10 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, path::Path};
 
 use getters::Getters;
 use serde::Deserialize;
 
+use crate::ast_source::{LineStarts, SrcLocation};
+
 use super::{
     Directive,
     directives::{ContractDefinition, Directives},
@@ -20,9 +22,22 @@ pub struct SourceUnit {
     license: Option<String>,
     #[use_as_ref]
     #[return_type = "&[Directive]"]
-    nodes: Directives,
+    pub(crate) nodes: Directives,
     #[return_type = "&str"]
     src: String,
+    /// Original file content, not part of the compiler output.
+    #[serde(skip)]
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    source: Option<String>,
+    #[serde(skip)]
+    #[skip_getter]
+    line_starts: LineStarts,
+    /// Version of the compiler that produced the AST, when the loader knows it.
+    #[serde(skip)]
+    #[use_as_deref]
+    #[return_type = "Option<&str>"]
+    compiler_version: Option<String>,
 }
 
 impl SourceUnit {
@@ -54,4 +69,35 @@ impl SourceUnit {
     pub fn debug_nodes<'a>(&'a self) -> &'a [Directive] {
         &self.nodes
     }
+
+    /// Index of this file in the compiler's source list, taken from `src`.
+    pub fn file_index(&self) -> Option<usize> {
+        self.src.parse::<SrcLocation>().ok()?.file_index()
+    }
+
+    pub fn set_source(&mut self, source: String) {
+        self.source = Some(source);
+        self.line_starts = LineStarts::default();
+    }
+
+    /// Line starts of [`Self::source`], computed once.
+    pub(crate) fn line_starts(&self) -> &LineStarts {
+        &self.line_starts
+    }
+
+    pub fn with_source(mut self, source: String) -> Self {
+        self.set_source(source);
+        self
+    }
+
+    pub fn set_compiler_version(&mut self, compiler_version: Option<String>) {
+        self.compiler_version = compiler_version;
+    }
+
+    /// Reads the content of `absolutePath` resolved against `root`.
+    pub fn load_source<P: AsRef<Path>>(&mut self, root: P) -> std::io::Result<()> {
+        let source = std::fs::read_to_string(root.as_ref().join(&self.absolute_path))?;
+        self.set_source(source);
+        Ok(())
+    }
 }
@@ -1,11 +1,11 @@
 use serde::Deserialize;
 
-use crate::ast_specs::{common::Block, inline_assembly::InlineAssembly};
+use crate::ast_specs::{Unknown, common::Block, inline_assembly::InlineAssembly};
 
 use super::{
     Break, Continue, DoWhileStatement, EmitStatement, ExpressionStatement, ForStatement,
-    IfStatement, PlaceholderStatement, Return, RevertStatement, TryStatement, UncheckedBlock,
-    VariableDeclarationStatement, WhileStatement,
+    IfStatement, PlaceholderStatement, Return, RevertStatement, Throw, TryStatement,
+    UncheckedBlock, VariableDeclarationStatement, WhileStatement,
 };
 
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -23,8 +23,11 @@ pub enum Body {
     PlaceholderStatement(PlaceholderStatement),
     Return(Return),
     RevertStatement(RevertStatement),
+    Throw(Throw),
     TryStatement(TryStatement),
     UncheckedBlock(UncheckedBlock),
     VariableDeclarationStatement(VariableDeclarationStatement),
     WhileStatement(WhileStatement),
+    #[serde(untagged)]
+    Unknown(Unknown),
 }
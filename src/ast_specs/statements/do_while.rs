@@ -8,9 +8,9 @@ use super::Body;
 #[derive(Deserialize, Debug, Clone, Getters, PartialEq, Eq)]
 pub struct DoWhileStatement {
     #[return_type = "&Body"]
-    body: Box<Body>,
+    pub(crate) body: Box<Body>,
     #[return_type = "&Option<Expression>"]
-    condition: Option<Expression>,
+    pub(crate) condition: Option<Expression>,
     documentation: Option<String>,
     #[copy]
     id: isize,
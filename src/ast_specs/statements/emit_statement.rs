@@ -7,7 +7,7 @@ use crate::ast_specs::expressions::FunctionCall;
 pub struct EmitStatement {
     documentation: Option<String>,
     #[serde(rename = "eventCall")]
-    event_call: FunctionCall,
+    pub(crate) event_call: FunctionCall,
     #[copy]
     id: isize,
     src: String,
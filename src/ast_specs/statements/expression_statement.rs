@@ -7,7 +7,7 @@ use crate::ast_specs::Expression;
 pub struct ExpressionStatement {
     documentation: Option<String>,
     #[return_type = "&Option<Expression>"]
-    expression: Option<Expression>,
+    pub(crate) expression: Option<Expression>,
     #[copy]
     id: isize,
     src: String,
@@ -1,6 +1,6 @@
 use serde::Deserialize;
 
-use crate::ast_specs::common::Block;
+use crate::ast_specs::{Unknown, common::Block};
 
 use super::{Statement, prelude::*};
 
@@ -19,10 +19,13 @@ pub enum FalseBody {
     PlaceholderStatement(PlaceholderStatement),
     Return(Return),
     RevertStatement(RevertStatement),
+    Throw(Throw),
     TryStatement(TryStatement),
     UncheckedBlock(UncheckedBlock),
     VariableDeclarationStatement(VariableDeclarationStatement),
     WhileStatement(WhileStatement),
+    #[serde(untagged)]
+    Unknown(Unknown),
 }
 
 impl FalseBody {
@@ -53,6 +56,7 @@ impl From<FalseBody> for Statement {
             FalseBody::RevertStatement(revert_statement) => {
                 Statement::RevertStatement(revert_statement)
             }
+            FalseBody::Throw(throw) => Statement::Throw(throw),
             FalseBody::TryStatement(try_statement) => Statement::TryStatement(try_statement),
             FalseBody::UncheckedBlock(unchecked_block) => {
                 Statement::UncheckedBlock(unchecked_block)
@@ -63,6 +67,7 @@ impl From<FalseBody> for Statement {
             FalseBody::WhileStatement(while_statement) => {
                 Statement::WhileStatement(while_statement)
             }
+            FalseBody::Unknown(unknown) => Statement::Unknown(unknown),
         }
     }
 }
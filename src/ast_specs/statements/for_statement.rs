@@ -7,21 +7,21 @@ use super::{Body, ExpressionStatement, VariableDeclarationStatement};
 
 #[derive(Deserialize, Debug, Clone, Getters, PartialEq, Eq)]
 pub struct ForStatement {
-    body: Box<Body>,
+    pub(crate) body: Box<Body>,
     #[return_type = "Option<&Expression>"]
     #[use_as_ref]
-    condition: Option<Expression>,
+    pub(crate) condition: Option<Expression>,
     documentation: Option<String>,
     #[copy]
     id: isize,
     #[serde(rename = "initializationExpression")]
     #[return_type = "Option<&InitializationExpression>"]
     #[use_as_ref]
-    initialization_expression: Option<InitializationExpression>,
+    pub(crate) initialization_expression: Option<InitializationExpression>,
     #[serde(rename = "loopExpression")]
     #[return_type = "Option<&ExpressionStatement>"]
     #[use_as_ref]
-    loop_expression: Option<ExpressionStatement>,
+    pub(crate) loop_expression: Option<ExpressionStatement>,
     src: String,
 }
 
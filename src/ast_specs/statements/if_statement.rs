@@ -9,15 +9,15 @@ use super::FalseBody;
 pub struct IfStatement {
     #[use_as_ref]
     #[return_type = "Option<&Expression>"]
-    condition: Option<Expression>,
+    pub(crate) condition: Option<Expression>,
     documentation: Option<String>,
     #[serde(rename = "falseBody")]
     #[return_type = "Option<&FalseBody>"]
     #[use_as_deref]
-    false_body: Option<Box<FalseBody>>,
+    pub(crate) false_body: Option<Box<FalseBody>>,
     #[copy]
     id: isize,
     src: String,
     #[serde(rename = "trueBody")]
-    true_body: Box<FalseBody>, // TODO: Made it right
+    pub(crate) true_body: Box<FalseBody>, // TODO: Made it right
 }
@@ -11,6 +11,7 @@ mod placeholder_statement;
 mod prelude;
 mod return_statement;
 mod revert_statement;
+mod throw_statement;
 mod try_statement;
 mod unchecked_block;
 mod variable_declaration_statement;
@@ -20,7 +21,7 @@ use serde::Deserialize;
 
 pub use prelude::*;
 
-use super::{common::Block, inline_assembly::InlineAssembly};
+use super::{Unknown, common::Block, inline_assembly::InlineAssembly};
 
 crate::enum_refs! {
     #[derive_owned(Deserialize)]
@@ -39,10 +40,13 @@ crate::enum_refs! {
         PlaceholderStatement(PlaceholderStatement),
         Return(Return),
         RevertStatement(RevertStatement),
+        Throw(Throw),
         TryStatement(TryStatement),
         UncheckedBlock(UncheckedBlock),
         VariableDeclarationStatement(VariableDeclarationStatement),
         WhileStatement(WhileStatement),
+        #[serde(untagged)]
+        Unknown(Unknown),
     }
 }
 
@@ -100,8 +104,8 @@ macro_rules! external_to_st {
 
 macro_rules! combo_st {
     ($($variant:ident),*) => {
-        st_delegate_functions!($($variant),*);
-        external_to_st!($($variant),*);
+        st_delegate_functions!($($variant,)* Unknown);
+        external_to_st!($($variant,)* Unknown);
     };
 }
 
@@ -118,6 +122,7 @@ combo_st!(
     PlaceholderStatement,
     Return,
     RevertStatement,
+    Throw,
     TryStatement,
     UncheckedBlock,
     VariableDeclarationStatement,
@@ -10,6 +10,7 @@ pub use super::if_statement::IfStatement;
 pub use super::placeholder_statement::PlaceholderStatement;
 pub use super::return_statement::Return;
 pub use super::revert_statement::RevertStatement;
+pub use super::throw_statement::Throw;
 pub use super::try_statement::{TryCatchClause, TryStatement};
 pub use super::unchecked_block::UncheckedBlock;
 pub use super::variable_declaration_statement::VariableDeclarationStatement;
@@ -8,7 +8,7 @@ pub struct Return {
     documentation: Option<String>,
     #[return_type = "Option<&Expression>"]
     #[use_as_ref]
-    expression: Option<Expression>,
+    pub(crate) expression: Option<Expression>,
     #[serde(rename = "functionReturnParameters")]
     #[copy]
     function_return_parameters: Option<isize>,
@@ -7,7 +7,7 @@ use crate::ast_specs::expressions::FunctionCall;
 pub struct RevertStatement {
     documentation: Option<String>,
     #[serde(rename = "errorCall")]
-    error_call: FunctionCall,
+    pub(crate) error_call: FunctionCall,
     #[copy]
     id: isize,
     src: String,
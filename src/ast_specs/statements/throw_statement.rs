@@ -0,0 +1,12 @@
+use getters::Getters;
+use serde::Deserialize;
+
+/// `throw;`, which solc accepted before 0.5.
+#[derive(Deserialize, Debug, Clone, Getters, PartialEq, Eq)]
+pub struct Throw {
+    documentation: Option<String>,
+    #[copy]
+    id: isize,
+    #[return_type = "&str"]
+    src: String,
+}
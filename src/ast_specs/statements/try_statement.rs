@@ -9,10 +9,10 @@ use crate::ast_specs::{
 #[derive(Deserialize, Debug, Clone, Getters, PartialEq, Eq)]
 pub struct TryStatement {
     #[return_type = "&[TryCatchClause]"]
-    clauses: Vec<TryCatchClause>,
+    pub(crate) clauses: Vec<TryCatchClause>,
     documentation: Option<String>,
     #[serde(rename = "externalCall")]
-    external_call: FunctionCall,
+    pub(crate) external_call: FunctionCall,
     #[copy]
     id: isize,
     src: String,
@@ -20,13 +20,13 @@ pub struct TryStatement {
 
 #[derive(Deserialize, Debug, Clone, Getters, PartialEq, Eq)]
 pub struct TryCatchClause {
-    block: Block,
+    pub(crate) block: Block,
     #[serde(rename = "errorName")]
     error_name: String,
     #[copy]
     id: isize,
     #[return_type = "Option<&ParameterList>"]
     #[use_as_ref]
-    parameters: Option<ParameterList>,
+    pub(crate) parameters: Option<ParameterList>,
     src: String,
 }
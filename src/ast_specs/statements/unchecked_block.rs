@@ -10,5 +10,5 @@ pub struct UncheckedBlock {
     id: isize,
     src: String,
     #[return_type = "&[Statement]"]
-    statements: Vec<Statement>,
+    pub(crate) statements: Vec<Statement>,
 }
@@ -8,10 +8,10 @@ use super::Body;
 #[derive(Deserialize, Debug, Clone, Getters, PartialEq, Eq)]
 pub struct WhileStatement {
     #[return_type = "&Body"]
-    body: Box<Body>,
+    pub(crate) body: Box<Body>,
     #[return_type = "Option<&Expression>"]
     #[use_as_ref]
-    condition: Option<Expression>,
+    pub(crate) condition: Option<Expression>,
     documentation: Option<String>,
     #[copy]
     id: isize,
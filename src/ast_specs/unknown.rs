@@ -0,0 +1,99 @@
+use std::cell::Cell;
+
+use getters::Getters;
+use serde::{Deserialize, Deserializer, de::Error};
+use serde_json::Value;
+
+use super::{NodeType, common::TypeDescriptions};
+
+thread_local! {
+    static LENIENT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` in lenient mode: nodes with a `nodeType` this crate does not know
+/// deserialize into [`Unknown`] instead of failing the whole document.
+///
+/// The mode is per thread, so loaders must be called from within `f`.
+pub fn lenient<T, F: FnOnce() -> T>(f: F) -> T {
+    let _restore = Restore(LENIENT.with(|lenient| lenient.replace(true)));
+    f()
+}
+
+/// Restores the previous mode when [`lenient`] returns or `f` unwinds.
+struct Restore(bool);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        LENIENT.with(|lenient| lenient.set(self.0));
+    }
+}
+
+pub fn is_lenient() -> bool {
+    LENIENT.with(Cell::get)
+}
+
+/// Node of a type unknown to this crate, kept verbatim.
+///
+/// Only produced in [`lenient`] mode, in place of an `Expression`, `Statement`,
+/// `Body`, `FalseBody`, `TypeName`, `BaseNode` or `Directive`.
+#[derive(Debug, Clone, Getters, PartialEq, Eq)]
+pub struct Unknown {
+    #[return_type = "&str"]
+    node_type: String,
+    #[copy]
+    id: isize,
+    #[return_type = "&str"]
+    src: String,
+    type_descriptions: TypeDescriptions,
+    raw: Value,
+}
+
+impl Unknown {
+    pub fn ref_dec_visitor(&self) -> Option<isize> {
+        self.raw
+            .get("referencedDeclaration")
+            .and_then(Value::as_i64)
+            .map(|id| id as isize)
+    }
+}
+
+impl<'de> Deserialize<'de> for Unknown {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = Value::deserialize(deserializer)?;
+
+        let node_type = raw
+            .get("nodeType")
+            .and_then(Value::as_str)
+            .ok_or_else(|| D::Error::missing_field("nodeType"))?;
+        if NodeType::from(node_type) != NodeType::Unknown {
+            return Err(D::Error::custom(format!("malformed `{node_type}` node")));
+        }
+        if !is_lenient() {
+            return Err(D::Error::custom(format!("unknown nodeType `{node_type}`")));
+        }
+
+        let id = raw
+            .get("id")
+            .and_then(Value::as_i64)
+            .ok_or_else(|| D::Error::missing_field("id"))?;
+        let src = raw
+            .get("src")
+            .and_then(Value::as_str)
+            .ok_or_else(|| D::Error::missing_field("src"))?;
+        let type_descriptions = raw
+            .get("typeDescriptions")
+            .and_then(|value| TypeDescriptions::deserialize(value).ok())
+            .unwrap_or_default();
+
+        Ok(Self {
+            node_type: node_type.to_owned(),
+            id: id as isize,
+            src: src.to_owned(),
+            type_descriptions,
+            raw,
+        })
+    }
+}
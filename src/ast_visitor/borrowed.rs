@@ -0,0 +1,200 @@
+//! Safe counterparts of the `filter_ref_*` and `step_back*` methods of [`AstVisitor`].
+//!
+//! Every query borrows the root it runs on, so the returned [`NodeTypeInternalRef`]s live as long
+//! as that root and need no lifetime transmutes. Results come in the same order as the owned
+//! `filter_by_*` methods.
+//!
+//! [`AstVisitor`]: super::AstVisitor
+
+use crate::{
+    AstParserResult,
+    ast_specs::{NodeType, NodeTypeInternalRef},
+};
+
+use super::{Located, Selector, Visit, VisitControl, Walk, path::locate};
+
+pub trait AstRefVisitor<'root> {
+    /// Borrowed [`filter_by_node_type`](super::AstVisitor::filter_by_node_type).
+    fn refs_by_node_type<N: Into<NodeType>>(
+        &'root self,
+        node_type: N,
+    ) -> Vec<NodeTypeInternalRef<'root>>;
+
+    fn refs_by_node_types<const A: usize, N: Into<NodeType>>(
+        &'root self,
+        node_types: [N; A],
+    ) -> Vec<NodeTypeInternalRef<'root>> {
+        node_types
+            .into_iter()
+            .flat_map(|n| self.refs_by_node_type(n))
+            .collect()
+    }
+
+    /// Borrowed [`filter_by_reference_id`](super::AstVisitor::filter_by_reference_id).
+    fn refs_by_reference_id(&'root self, id: isize) -> Vec<NodeTypeInternalRef<'root>>;
+
+    /// Borrowed [`filter_by_id`](super::AstVisitor::filter_by_id).
+    fn ref_by_id(&'root self, id: isize) -> Option<NodeTypeInternalRef<'root>>;
+
+    /// Closest node above `target`, like [`step_back`](super::AstVisitor::step_back).
+    fn parent_ref<'a, N: Into<NodeTypeInternalRef<'a>>>(
+        &'root self,
+        target: N,
+    ) -> Option<NodeTypeInternalRef<'root>>;
+
+    /// Closest node of type `to` above `from`, like
+    /// [`step_back_until`](super::AstVisitor::step_back_until).
+    fn ancestor_ref<'a, N: Into<NodeTypeInternalRef<'a>>>(
+        &'root self,
+        from: N,
+        to: NodeType,
+    ) -> Option<NodeTypeInternalRef<'root>>;
+
+    /// Nodes matching a [`Selector`] query, parents before their children.
+    fn refs_by_selector(
+        &'root self,
+        selector: &str,
+    ) -> AstParserResult<Vec<NodeTypeInternalRef<'root>>>;
+
+    /// Nodes of one type with their paths, parents before their children.
+    fn located_by_node_type<N: Into<NodeType>>(&'root self, node_type: N) -> Vec<Located<'root>>;
+
+    /// Like [`Self::refs_by_selector`], with the path of every match.
+    fn located_by_selector(&'root self, selector: &str) -> AstParserResult<Vec<Located<'root>>>;
+}
+
+impl<'root, T: Walk<'root> + ?Sized> AstRefVisitor<'root> for T {
+    fn refs_by_node_type<N: Into<NodeType>>(
+        &'root self,
+        node_type: N,
+    ) -> Vec<NodeTypeInternalRef<'root>> {
+        let node_type = node_type.into();
+        collect(self, |node| NodeType::from(node) == node_type)
+    }
+
+    fn refs_by_reference_id(&'root self, id: isize) -> Vec<NodeTypeInternalRef<'root>> {
+        collect(self, |node| node.reference_id() == Some(id))
+    }
+
+    fn ref_by_id(&'root self, id: isize) -> Option<NodeTypeInternalRef<'root>> {
+        let mut finder = Find {
+            predicate: |node: NodeTypeInternalRef<'_>| node.id() == Some(id),
+            found: None,
+        };
+        let _ = self.walk(&mut finder);
+        finder.found
+    }
+
+    fn parent_ref<'a, N: Into<NodeTypeInternalRef<'a>>>(
+        &'root self,
+        target: N,
+    ) -> Option<NodeTypeInternalRef<'root>> {
+        ancestors_of(self, target.into())?.pop()
+    }
+
+    fn ancestor_ref<'a, N: Into<NodeTypeInternalRef<'a>>>(
+        &'root self,
+        from: N,
+        to: NodeType,
+    ) -> Option<NodeTypeInternalRef<'root>> {
+        ancestors_of(self, from.into())?
+            .into_iter()
+            .rev()
+            .find(|node| NodeType::from(*node) == to)
+    }
+
+    fn refs_by_selector(
+        &'root self,
+        selector: &str,
+    ) -> AstParserResult<Vec<NodeTypeInternalRef<'root>>> {
+        Ok(Selector::parse(selector)?.select(self))
+    }
+
+    fn located_by_node_type<N: Into<NodeType>>(&'root self, node_type: N) -> Vec<Located<'root>> {
+        let node_type = node_type.into();
+        locate(self, |node, _| NodeType::from(node) == node_type)
+    }
+
+    fn located_by_selector(&'root self, selector: &str) -> AstParserResult<Vec<Located<'root>>> {
+        Ok(Selector::parse(selector)?.select_located(self))
+    }
+}
+
+/// Nodes matching `predicate`, each pushed after its children.
+fn collect<'root, T, P>(root: &'root T, predicate: P) -> Vec<NodeTypeInternalRef<'root>>
+where
+    T: Walk<'root> + ?Sized,
+    P: FnMut(NodeTypeInternalRef<'root>) -> bool,
+{
+    let mut collector = Collect {
+        predicate,
+        found: vec![],
+    };
+    let _ = root.walk(&mut collector);
+    collector.found
+}
+
+/// Path from `root` down to `target` itself, not just an equal node, without the node.
+fn ancestors_of<'root, T: Walk<'root> + ?Sized>(
+    root: &'root T,
+    target: NodeTypeInternalRef<'_>,
+) -> Option<Vec<NodeTypeInternalRef<'root>>> {
+    let mut path = Path {
+        target,
+        stack: vec![],
+        found: false,
+    };
+    let _ = root.walk(&mut path);
+    path.found.then_some(path.stack)
+}
+
+struct Collect<'root, P> {
+    predicate: P,
+    found: Vec<NodeTypeInternalRef<'root>>,
+}
+
+impl<'root, P: FnMut(NodeTypeInternalRef<'root>) -> bool> Visit<'root> for Collect<'root, P> {
+    fn leave_node(&mut self, node: NodeTypeInternalRef<'root>) -> VisitControl {
+        if (self.predicate)(node) {
+            self.found.push(node);
+        }
+        VisitControl::Continue
+    }
+}
+
+struct Find<'root, P> {
+    predicate: P,
+    found: Option<NodeTypeInternalRef<'root>>,
+}
+
+impl<'root, P: FnMut(NodeTypeInternalRef<'root>) -> bool> Visit<'root> for Find<'root, P> {
+    fn visit_node(&mut self, node: NodeTypeInternalRef<'root>) -> VisitControl {
+        if (self.predicate)(node) {
+            self.found = Some(node);
+            return VisitControl::Stop;
+        }
+        VisitControl::Continue
+    }
+}
+
+struct Path<'root, 'a> {
+    target: NodeTypeInternalRef<'a>,
+    stack: Vec<NodeTypeInternalRef<'root>>,
+    found: bool,
+}
+
+impl<'root> Visit<'root> for Path<'root, '_> {
+    fn visit_node(&mut self, node: NodeTypeInternalRef<'root>) -> VisitControl {
+        if node.ptr_eq(self.target) {
+            self.found = true;
+            return VisitControl::Stop;
+        }
+        self.stack.push(node);
+        VisitControl::Continue
+    }
+
+    fn leave_node(&mut self, node: NodeTypeInternalRef<'root>) -> VisitControl {
+        self.stack.pop();
+        VisitControl::Continue
+    }
+}
@@ -0,0 +1,149 @@
+//! Tree rewriting over the owned AST.
+//!
+//! [`AstFold`] has a `fold_*` method for every node type of the `ast_visitor!` tables, struct and
+//! enum alike. Each takes the node by value and returns its replacement, and the default rebuilds
+//! the node from its folded children. Overriding the method of an enum, like
+//! [`AstFold::fold_statement`], lets a transformer swap one variant for another, and new nodes
+//! can be built with the `artificial_new` constructors.
+//!
+//! [`AstFold`]: super::AstFold
+
+use super::AstFold;
+
+macro_rules! ast_fold {
+    (
+        $(
+            $target:ident: [
+                $(
+                    $inner:ident
+                ),*
+            ];
+        )*
+    ) => {
+        paste::paste! {
+            /// Rewrites a tree with [`Fold::fold_with`].
+            pub trait AstFold {
+                $(
+                    fn [<fold_ $target:snake>](&mut self, node: $target) -> $target {
+                        node.fold_children(self)
+                    }
+                )*
+
+                ast_fold_enums!();
+            }
+
+            $(
+                impl Fold for $target {
+                    fn fold_with<F: AstFold + ?Sized>(self, folder: &mut F) -> Self {
+                        folder.[<fold_ $target:snake>](self)
+                    }
+
+                    #[allow(unused_mut)]
+                    fn fold_children<F: AstFold + ?Sized>(self, folder: &mut F) -> Self {
+                        let mut node = self;
+                        $(
+                            node.$inner = node.$inner.fold_with(folder);
+                        )*
+                        node
+                    }
+                }
+            )*
+        }
+    };
+    (
+        $(
+            $target:ident: (
+                $(
+                    $variant:ident
+                ),*
+            );
+        )*
+    ) => {
+        // The enum methods of `AstFold`, for the struct table, which declares the trait, to
+        // expand. The enum table has to come first.
+        macro_rules! ast_fold_enums {
+            () => {
+                ast_fold!(!!!enums!!! $($target),*);
+            };
+        }
+
+        paste::paste! {
+            $(
+                impl Fold for $target {
+                    fn fold_with<F: AstFold + ?Sized>(self, folder: &mut F) -> Self {
+                        folder.[<fold_ $target:snake>](self)
+                    }
+
+                    fn fold_children<F: AstFold + ?Sized>(self, folder: &mut F) -> Self {
+                        match self {
+                            $(
+                                $target::$variant(i) => $target::$variant(i.fold_with(folder)),
+                            )*
+                        }
+                    }
+                }
+            )*
+        }
+    };
+    // Enum methods of `AstFold`, see `ast_fold_enums`
+    (!!!enums!!! $($target:ident),*) => {
+        paste::paste! {
+            $(
+                fn [<fold_ $target:snake>](&mut self, node: $target) -> $target {
+                    node.fold_children(self)
+                }
+            )*
+        }
+    };
+    (!!!placeholder!!! $target: ident) => {
+        impl Fold for $target {
+            fn fold_with<F: AstFold + ?Sized>(self, folder: &mut F) -> Self {
+                self
+            }
+
+            fn fold_children<F: AstFold + ?Sized>(self, folder: &mut F) -> Self {
+                self
+            }
+        }
+    };
+}
+
+pub(super) use ast_fold;
+
+pub trait Fold: Sized {
+    /// Hands `self` to the matching `fold_*` method of `folder`.
+    fn fold_with<F: AstFold + ?Sized>(self, folder: &mut F) -> Self;
+
+    /// Rebuilds `self` from its children, each handed to `folder`.
+    fn fold_children<F: AstFold + ?Sized>(self, folder: &mut F) -> Self;
+}
+
+impl<T: Fold> Fold for Option<T> {
+    fn fold_with<F: AstFold + ?Sized>(self, folder: &mut F) -> Self {
+        self.map(|t| t.fold_with(folder))
+    }
+
+    fn fold_children<F: AstFold + ?Sized>(self, folder: &mut F) -> Self {
+        self.map(|t| t.fold_children(folder))
+    }
+}
+
+impl<T: Fold> Fold for Vec<T> {
+    fn fold_with<F: AstFold + ?Sized>(self, folder: &mut F) -> Self {
+        self.into_iter().map(|t| t.fold_with(folder)).collect()
+    }
+
+    fn fold_children<F: AstFold + ?Sized>(self, folder: &mut F) -> Self {
+        self.into_iter().map(|t| t.fold_children(folder)).collect()
+    }
+}
+
+impl<T: Fold> Fold for Box<T> {
+    fn fold_with<F: AstFold + ?Sized>(self, folder: &mut F) -> Self {
+        Box::new((*self).fold_with(folder))
+    }
+
+    fn fold_children<F: AstFold + ?Sized>(self, folder: &mut F) -> Self {
+        Box::new((*self).fold_children(folder))
+    }
+}
@@ -0,0 +1,181 @@
+//! Lookup tables built by one walk over a root.
+//!
+//! [`AstRefVisitor::ref_by_id`] and [`AstRefVisitor::parent_ref`] walk the tree on every call,
+//! which adds up when they run in a loop. [`AstIndex`] walks it once and answers the same
+//! questions from hash maps.
+//!
+//! [`AstRefVisitor::ref_by_id`]: super::AstRefVisitor::ref_by_id
+//! [`AstRefVisitor::parent_ref`]: super::AstRefVisitor::parent_ref
+
+use std::collections::{BTreeSet, HashMap, hash_map::Entry};
+
+use crate::ast_specs::{
+    NodeType, NodeTypeInternalRef,
+    directives::{ContractDefinition, FunctionDefinition},
+};
+
+use super::{AstPath, VisitControl, VisitPath, Walk, walk_with_path};
+
+/// Unit a node was found in, counted in walk order, and the node's id.
+type Key = (usize, isize);
+
+/// Nodes of a [`SourceUnit`](crate::ast_specs::SourceUnit) or a
+/// [`Workspace`](crate::ast_framework::Workspace) by id.
+///
+/// Nodes without an `id`, like Yul nodes or `SymbolAliases`, are keyed by their synthetic id,
+/// see [`AstPath::node_id`].
+///
+/// Units of separate compilations may reuse node ids. Such ids are reported by
+/// [`AstIndex::conflicts`] and resolve to no node, while the nodes around them still find
+/// their parents and enclosing definitions.
+#[derive(Debug, Clone, Default)]
+pub struct AstIndex<'root> {
+    keys: HashMap<isize, Key>,
+    conflicts: BTreeSet<isize>,
+    nodes: HashMap<Key, NodeTypeInternalRef<'root>>,
+    parents: HashMap<Key, Key>,
+    contracts: HashMap<Key, &'root ContractDefinition>,
+    functions: HashMap<Key, &'root FunctionDefinition>,
+    references: HashMap<isize, Vec<NodeTypeInternalRef<'root>>>,
+}
+
+impl<'root> AstIndex<'root> {
+    pub fn new<T: Walk<'root> + ?Sized>(root: &'root T) -> Self {
+        let mut builder = Builder::default();
+        let _ = walk_with_path(root, &mut builder);
+        builder.index
+    }
+
+    /// Node with the given id, like [`filter_ref_by_id`](super::AstVisitor::filter_ref_by_id).
+    pub fn node(&self, id: isize) -> Option<NodeTypeInternalRef<'root>> {
+        self.nodes.get(&self.key(id)?).copied()
+    }
+
+    /// Node directly above the one with the given id, like
+    /// [`step_back`](super::AstVisitor::step_back).
+    pub fn parent(&self, id: isize) -> Option<NodeTypeInternalRef<'root>> {
+        let parent = self.parents.get(&self.key(id)?)?;
+        self.nodes.get(parent).copied()
+    }
+
+    /// Closest node of type `to` above the one with the given id, like
+    /// [`step_back_until`](super::AstVisitor::step_back_until).
+    pub fn ancestor(&self, id: isize, to: NodeType) -> Option<NodeTypeInternalRef<'root>> {
+        let mut current = self.key(id)?;
+        while let Some(parent) = self.parents.get(&current) {
+            let node = self.nodes[parent];
+            if NodeType::from(node) == to {
+                return Some(node);
+            }
+            current = *parent;
+        }
+        None
+    }
+
+    /// Contract the node with the given id is declared in.
+    pub fn enclosing_contract(&self, id: isize) -> Option<&'root ContractDefinition> {
+        self.contracts.get(&self.key(id)?).copied()
+    }
+
+    /// Function the node with the given id is part of.
+    pub fn enclosing_function(&self, id: isize) -> Option<&'root FunctionDefinition> {
+        self.functions.get(&self.key(id)?).copied()
+    }
+
+    /// Nodes referencing the declaration with the given id, in walk order. Empty if the id is
+    /// one of the [conflicts](Self::conflicts).
+    pub fn references(&self, declaration: isize) -> &[NodeTypeInternalRef<'root>] {
+        if self.conflicts.contains(&declaration) {
+            return &[];
+        }
+        self.references
+            .get(&declaration)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Ids found in more than one unit, in ascending order.
+    pub fn conflicts(&self) -> impl Iterator<Item = isize> + '_ {
+        self.conflicts.iter().copied()
+    }
+
+    /// Every indexed node, in no particular order.
+    pub fn nodes(&self) -> impl Iterator<Item = NodeTypeInternalRef<'root>> + '_ {
+        self.nodes.values().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Key of the one node with the given id.
+    fn key(&self, id: isize) -> Option<Key> {
+        if self.conflicts.contains(&id) {
+            return None;
+        }
+        self.keys.get(&id).copied()
+    }
+}
+
+#[derive(Default)]
+struct Builder<'root> {
+    index: AstIndex<'root>,
+    /// Number of source units visited so far.
+    unit: usize,
+    /// Keys of the nodes on the path.
+    keys: Vec<Key>,
+}
+
+impl<'root> VisitPath<'root> for Builder<'root> {
+    fn visit_node(
+        &mut self,
+        node: NodeTypeInternalRef<'root>,
+        path: &AstPath<'root>,
+    ) -> VisitControl {
+        let index = &mut self.index;
+        if let Some(declaration) = node.reference_id() {
+            index.references.entry(declaration).or_default().push(node);
+        }
+
+        if node.is_source_unit() {
+            self.unit += 1;
+        }
+        let id = path.node_id(node);
+        let key = (self.unit, id);
+        match index.keys.entry(id) {
+            Entry::Vacant(entry) => {
+                entry.insert(key);
+            }
+            Entry::Occupied(entry) if *entry.get() != key => {
+                index.conflicts.insert(id);
+            }
+            Entry::Occupied(_) => {}
+        }
+
+        index.nodes.insert(key, node);
+        if let Some(parent) = self.keys.last() {
+            index.parents.insert(key, *parent);
+        }
+        if let Some(contract) = path.contract() {
+            index.contracts.insert(key, contract);
+        }
+        if let Some(function) = path.function() {
+            index.functions.insert(key, function);
+        }
+        self.keys.push(key);
+        VisitControl::Continue
+    }
+
+    fn leave_node(
+        &mut self,
+        node: NodeTypeInternalRef<'root>,
+        path: &AstPath<'root>,
+    ) -> VisitControl {
+        self.keys.pop();
+        VisitControl::Continue
+    }
+}
@@ -0,0 +1,146 @@
+//! Lazy iteration over the owned AST.
+//!
+//! [`Nodes`] keeps a stack of the nodes it still has to yield and expands a node only when it
+//! reaches it. Memory grows with depth times fan-out rather than with the size of the unit, and
+//! dropping the iterator early, as `find` and `take` do, skips the rest of the tree.
+
+use crate::ast_specs::{NodeType, NodeTypeInternalRef};
+
+use super::{Visit, VisitControl, Walk};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TraversalOrder {
+    /// Parents before their children.
+    #[default]
+    PreOrder,
+    /// Children before their parents, the order of `filter_by_node_type`.
+    PostOrder,
+}
+
+/// Iterator over nodes and everything below them, see [`AstIter`].
+#[derive(Debug, Clone)]
+pub struct Nodes<'root> {
+    order: TraversalOrder,
+    /// Pending nodes, next one last, flagged once their children are on the stack.
+    stack: Vec<(NodeTypeInternalRef<'root>, bool)>,
+}
+
+impl<'root> Nodes<'root> {
+    fn new<T: Walk<'root> + ?Sized>(root: &'root T, order: TraversalOrder) -> Self {
+        let mut stack = vec![];
+        let _ = root.walk(&mut Pusher {
+            stack: &mut stack,
+            skip_root: false,
+        });
+        stack.reverse();
+        Self { order, stack }
+    }
+
+    fn push_children(&mut self, node: NodeTypeInternalRef<'root>) {
+        let start = self.stack.len();
+        let _ = node.walk(&mut Pusher {
+            stack: &mut self.stack,
+            skip_root: true,
+        });
+        // First child on top
+        self.stack[start..].reverse();
+    }
+}
+
+impl<'root> Iterator for Nodes<'root> {
+    type Item = NodeTypeInternalRef<'root>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, expanded) = self.stack.pop()?;
+            match self.order {
+                TraversalOrder::PreOrder => {
+                    self.push_children(node);
+                    return Some(node);
+                }
+                TraversalOrder::PostOrder if expanded => return Some(node),
+                TraversalOrder::PostOrder => {
+                    self.stack.push((node, true));
+                    self.push_children(node);
+                }
+            }
+        }
+    }
+}
+
+/// Pushes the outermost nodes below the walked value, or below the walked node with `skip_root`.
+struct Pusher<'p, 'root> {
+    stack: &'p mut Vec<(NodeTypeInternalRef<'root>, bool)>,
+    skip_root: bool,
+}
+
+impl<'root> Visit<'root> for Pusher<'_, 'root> {
+    fn visit_node(&mut self, node: NodeTypeInternalRef<'root>) -> VisitControl {
+        if self.skip_root {
+            self.skip_root = false;
+            return VisitControl::Continue;
+        }
+        self.stack.push((node, false));
+        VisitControl::SkipChildren
+    }
+}
+
+pub trait AstIter<'root> {
+    /// Every node, in pre-order.
+    fn iter_nodes(&'root self) -> Nodes<'root>;
+
+    /// Every node, in the given order.
+    fn iter_nodes_in(&'root self, order: TraversalOrder) -> Nodes<'root>;
+
+    /// Nodes of one type, in pre-order.
+    fn iter_by_node_type<N: Into<NodeType>>(
+        &'root self,
+        node_type: N,
+    ) -> impl Iterator<Item = NodeTypeInternalRef<'root>> + 'root;
+}
+
+impl<'root, T: Walk<'root> + ?Sized> AstIter<'root> for T {
+    fn iter_nodes(&'root self) -> Nodes<'root> {
+        Nodes::new(self, TraversalOrder::PreOrder)
+    }
+
+    fn iter_nodes_in(&'root self, order: TraversalOrder) -> Nodes<'root> {
+        Nodes::new(self, order)
+    }
+
+    fn iter_by_node_type<N: Into<NodeType>>(
+        &'root self,
+        node_type: N,
+    ) -> impl Iterator<Item = NodeTypeInternalRef<'root>> + 'root {
+        let node_type = node_type.into();
+        self.iter_nodes()
+            .filter(move |node| NodeType::from(*node) == node_type)
+    }
+}
+
+impl<'root> NodeTypeInternalRef<'root> {
+    /// Nodes directly below this one.
+    pub fn children(self) -> Vec<NodeTypeInternalRef<'root>> {
+        let mut children = vec![];
+        let _ = self.walk(&mut Pusher {
+            stack: &mut children,
+            skip_root: true,
+        });
+        children.into_iter().map(|(child, _)| child).collect()
+    }
+
+    /// Every node below this one, in pre-order.
+    pub fn descendants(self) -> Nodes<'root> {
+        self.descendants_in(TraversalOrder::PreOrder)
+    }
+
+    /// Every node below this one, in the given order.
+    pub fn descendants_in(self, order: TraversalOrder) -> Nodes<'root> {
+        let mut nodes = Nodes {
+            order,
+            stack: vec![],
+        };
+        nodes.push_children(self);
+        nodes
+    }
+}
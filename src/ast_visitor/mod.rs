@@ -1,4 +1,15 @@
 #![warn(clippy::all)]
+mod borrowed;
+mod fold;
+mod index;
+mod iter;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod path;
+mod pattern;
+mod selector;
+mod visit;
+
 use std::{collections::HashMap, fmt::Debug, mem::transmute};
 
 use crate::ast_specs::{
@@ -33,7 +44,7 @@ use crate::ast_specs::{
 };
 
 use super::ast_specs::{
-    BaseNode, Directive, Expression, SourceUnit, Statement,
+    BaseNode, Directive, Expression, SourceUnit, Statement, Unknown,
     base_nodes::{EventDefinition, ModifierDefinition},
     common::{
         ArrayTypeName, Block, ElementaryTypeName, FunctionTypeName, IdentifierPath, LibraryName,
@@ -54,11 +65,24 @@ use super::ast_specs::{
     statements::{
         Body, Break, Continue, DoWhileStatement, EmitStatement, ExpressionStatement, FalseBody,
         ForStatement, IfStatement, InitializationExpression, PlaceholderStatement, Return,
-        RevertStatement, TryCatchClause, TryStatement, UncheckedBlock,
+        RevertStatement, Throw, TryCatchClause, TryStatement, UncheckedBlock,
         VariableDeclarationStatement, WhileStatement,
     },
 };
 
+pub use borrowed::AstRefVisitor;
+pub use fold::Fold;
+use fold::ast_fold;
+pub use index::AstIndex;
+pub use iter::{AstIter, Nodes, TraversalOrder};
+#[cfg(feature = "rayon")]
+pub use parallel::ParAstRefVisitor;
+pub use path::{AstPath, Located, VisitPath, walk_with_path};
+pub use pattern::{Bindings, Pattern, PatternMatch};
+pub use selector::Selector;
+use visit::ast_walk;
+pub use visit::{VisitControl, Walk, WalkMut};
+
 pub trait AstVisitor {
     fn filter_by_node_type<N: Into<NodeType>>(&self, node_type: N) -> Vec<NodeTypeInternal>;
 
@@ -72,56 +96,31 @@ pub trait AstVisitor {
             .collect()
     }
 
-    /// # Safety
-    /// This function performs lifetime transmutation and is unsafe.
-    /// It requires that:
-    /// 1. The root AST node is immutable and never moved/dropped for the duration of all references
-    /// 2. All references point to data within the root AST's memory
-    /// 3. The original lifetime 'a and target lifetime 'b must both be valid for the root AST
-    ///
-    /// This is used internally by the AST visitor to convert reference lifetimes while traversing the tree.
-    /// Violating these requirements will lead to undefined behavior.
-    unsafe fn filter_ref_by_node_type<'a, N: Into<NodeType>>(
+    /// Same as [`AstRefVisitor::refs_by_node_type`], the results borrow from `self`.
+    fn filter_ref_by_node_type<'a, N: Into<NodeType>>(
         &'a self,
         node_type: N,
     ) -> Vec<NodeTypeInternalRef<'a>>;
 
-    unsafe fn filter_ref_by_node_types<'a, const A: usize, N: Into<NodeType>>(
-        &self,
+    fn filter_ref_by_node_types<'a, const A: usize, N: Into<NodeType>>(
+        &'a self,
         node_types: [N; A],
     ) -> Vec<NodeTypeInternalRef<'a>> {
         node_types
             .into_iter()
-            .flat_map(|n| unsafe { self.filter_ref_by_node_type(n) })
-            .map(|n| unsafe { dark_magic(n) })
+            .flat_map(|n| self.filter_ref_by_node_type(n))
             .collect()
     }
 
     fn filter_by_reference_id(&self, id: isize) -> Vec<NodeTypeInternal>;
 
-    /// # Safety
-    /// This function performs lifetime transmutation and is unsafe.
-    /// It requires that:
-    /// 1. The root AST node is immutable and never moved/dropped for the duration of all references
-    /// 2. All references point to data within the root AST's memory
-    /// 3. The original lifetime 'a and target lifetime 'b must both be valid for the root AST
-    ///
-    /// This is used internally by the AST visitor to convert reference lifetimes while traversing the tree.
-    /// Violating these requirements will lead to undefined behavior.
-    unsafe fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>>;
+    /// Same as [`AstRefVisitor::refs_by_reference_id`], the results borrow from `self`.
+    fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>>;
 
     fn filter_by_id(&self, id: isize) -> Option<NodeTypeInternal>;
 
-    /// # Safety
-    /// This function performs lifetime transmutation and is unsafe.
-    /// It requires that:
-    /// 1. The root AST node is immutable and never moved/dropped for the duration of all references
-    /// 2. All references point to data within the root AST's memory
-    /// 3. The original lifetime 'a and target lifetime 'b must both be valid for the root AST
-    ///
-    /// This is used internally by the AST visitor to convert reference lifetimes while traversing the tree.
-    /// Violating these requirements will lead to undefined behavior.
-    unsafe fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>>;
+    /// Same as [`AstRefVisitor::ref_by_id`], the result borrows from `self`.
+    fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>>;
 
     fn childrens_id(&self) -> Vec<isize>;
 
@@ -150,6 +149,7 @@ pub trait AstVisitor {
 ///
 /// This is used internally by the AST visitor to convert reference lifetimes while traversing the tree.
 /// Violating these requirements will lead to undefined behavior.
+#[deprecated(note = "borrowed queries no longer need it, use `AstRefVisitor`")]
 pub unsafe fn dark_magic<'a, 'b>(input: NodeTypeInternalRef<'a>) -> NodeTypeInternalRef<'b> {
     unsafe { transmute(input) }
 }
@@ -182,20 +182,8 @@ macro_rules! ast_visitor {
 
 
 
-                unsafe fn filter_ref_by_node_type<'a, N: Into<NodeType>>(&'a self, node_type: N) -> Vec<NodeTypeInternalRef<'a>> {
-                    let node_type: NodeType = node_type.into();
-
-                    let mut result = vec![];
-                    $(
-
-                        unsafe {
-                            result.extend(self.$inner().filter_ref_by_node_type(node_type).into_iter().map(|el| dark_magic(el)));
-                        }
-                    )*
-                    if node_type == NodeType::$target {
-                        result.push(NodeTypeInternalRef::$target(self));
-                    }
-                    result
+                fn filter_ref_by_node_type<'a, N: Into<NodeType>>(&'a self, node_type: N) -> Vec<NodeTypeInternalRef<'a>> {
+                    self.refs_by_node_type(node_type)
                 }
 
                 fn filter_by_reference_id(&self, id: isize) -> Vec<NodeTypeInternal> {
@@ -214,22 +202,8 @@ macro_rules! ast_visitor {
                     result
                 }
 
-                unsafe fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {
-                    let mut result = vec![];
-                    $(
-                        unsafe {
-                            result.extend(self.$inner().filter_ref_by_reference_id(id).into_iter().map(|el| dark_magic(el)));
-                        }
-                    )*
-                    $(
-                        #[cfg($has_refs)]
-                        if let Some(ref_dec) = self.ref_dec_visitor(){
-                            if id == ref_dec {
-                                result.push(NodeTypeInternalRef::$target(self));
-                            }
-                        }
-                    )?
-                    result
+                fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {
+                    self.refs_by_reference_id(id)
                 }
 
                 fn filter_by_id(&self, id: isize) -> Option<NodeTypeInternal> {
@@ -247,21 +221,8 @@ macro_rules! ast_visitor {
                     None
                 }
 
-                unsafe fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {
-                    $(
-                        unsafe {
-                            if let Some(node) = self.$inner().filter_ref_by_id(id).map(|el| dark_magic(el)) {
-                                return Some(node);
-                            }
-                        }
-                    )*
-
-                    $(#[cfg(not($no_id))])?
-                    if id == self.id() {
-                        return Some(NodeTypeInternalRef::$target(self));
-                    }
-
-                    None
+                fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {
+                    self.ref_by_id(id)
                 }
 
                 fn childrens_id(&self) -> Vec<isize> {
@@ -292,29 +253,7 @@ macro_rules! ast_visitor {
                     &'b self,
                     target: N,
                 ) -> Option<NodeTypeInternalRef<'b>> {
-                    $(
-                        if self.$inner().is_node(target) {
-                            return Some(self.into());
-                        }
-                    )else*
-                    $(
-                        {
-                            let bind = self.$inner();
-                            let tmp_x = bind.step_back(target);
-                            if tmp_x.is_some() {
-                                // TODO: USAGE OF BLACK MAGIC IS ILLIGAL
-                                let tmp_b: Option<NodeTypeInternalRef<'b>> = unsafe {
-                                    std::mem::transmute::<Option<NodeTypeInternalRef<'_>>, Option<NodeTypeInternalRef<'b>>>(
-                                        tmp_x,
-                                    )
-                                };
-
-                                return tmp_b;
-                            }
-                        }
-                    )*
-
-                    None
+                    self.parent_ref(target)
                 }
 
                 fn step_back_until<'a, 'b, N: Into<NodeTypeInternalRef<'a>> + Copy>(
@@ -322,18 +261,7 @@ macro_rules! ast_visitor {
                     from: N,
                     to: NodeType,
                 ) -> Option<NodeTypeInternalRef<'b>> {
-                    let mut current = self.step_back(from)?;
-                    loop {
-                        if NodeType::from(current) == to {
-                            return Some(current);
-                        }
-                        // Move up the tree
-                        match self.step_back(current) {
-                            Some(parent) => current = parent,
-                            None => break,
-                        }
-                    }
-                    None
+                    self.ancestor_ref(from, to)
                 }
 
                 fn is_node<'a, N: Into<NodeTypeInternalRef<'a>> + Copy>(&self, target: N) -> bool {
@@ -343,6 +271,18 @@ macro_rules! ast_visitor {
                 }
             }
         )*
+
+        ast_walk! {
+            $(
+                $target: [$($inner),*];
+            )*
+        }
+
+        ast_fold! {
+            $(
+                $target: [$($inner),*];
+            )*
+        }
     };
     (
         $(
@@ -368,12 +308,12 @@ macro_rules! ast_visitor {
                     }
                 }
 
-                unsafe fn filter_ref_by_node_type<'a, N: Into<NodeType>>(&'a self, node_type: N) -> Vec<NodeTypeInternalRef<'a>> {
+                fn filter_ref_by_node_type<'a, N: Into<NodeType>>(&'a self, node_type: N) -> Vec<NodeTypeInternalRef<'a>> {
                     let node_type: NodeType = node_type.into();
 
                     match self {
                         $(
-                            $target::$variant(i) => unsafe {i.filter_ref_by_node_type(node_type) },
+                            $target::$variant(i) => i.filter_ref_by_node_type(node_type),
                         )*
                     }
                 }
@@ -386,10 +326,10 @@ macro_rules! ast_visitor {
                     }
                 }
 
-                unsafe fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {
+                fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {
                     match self {
                         $(
-                            $target::$variant(i) => unsafe { i.filter_ref_by_reference_id(id) },
+                            $target::$variant(i) => i.filter_ref_by_reference_id(id),
                         )*
                     }
                 }
@@ -402,10 +342,10 @@ macro_rules! ast_visitor {
                     }
                 }
 
-                unsafe fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {
+                fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {
                     match self {
                         $(
-                            $target::$variant(i) => unsafe { i.filter_ref_by_id(id) },
+                            $target::$variant(i) => i.filter_ref_by_id(id),
                         )*
                     }
                 }
@@ -458,20 +398,32 @@ macro_rules! ast_visitor {
                 }
             }
         )*
+
+        ast_walk! {
+            $(
+                $target: ($($variant),*);
+            )*
+        }
+
+        ast_fold! {
+            $(
+                $target: ($($variant),*);
+            )*
+        }
     };
     (!!!placeholder!!! $target: ident) => {
         impl AstVisitor for $target {
             fn filter_by_node_type<N: Into<NodeType>>(&self, node_type: N) -> Vec<NodeTypeInternal> {vec![]}
 
-            unsafe fn filter_ref_by_node_type<'a, N: Into<NodeType>>(&'a self, node_type: N) -> Vec<NodeTypeInternalRef<'a>> {vec![]}
+            fn filter_ref_by_node_type<'a, N: Into<NodeType>>(&'a self, node_type: N) -> Vec<NodeTypeInternalRef<'a>> {vec![]}
 
             fn filter_by_reference_id(&self, id: isize) -> Vec<NodeTypeInternal> {vec![]}
 
-            unsafe fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {vec![]}
+            fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {vec![]}
 
             fn filter_by_id(&self,id:isize) -> Option<NodeTypeInternal> {None}
 
-            unsafe fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {None}
+            fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {None}
 
             fn childrens_id(&self) -> Vec<isize> {vec![]}
 
@@ -494,6 +446,9 @@ macro_rules! ast_visitor {
 
             fn is_node<'a, N: Into<NodeTypeInternalRef<'a>> + Copy>(&self, target: N) -> bool {false}
         }
+
+        ast_walk!(!!!placeholder!!! $target);
+        ast_fold!(!!!placeholder!!! $target);
     };
 }
 
@@ -505,13 +460,13 @@ ast_visitor!(!!!placeholder!!! JSON);
 
 ast_visitor! {
     Directive: (
-        EventDefinition, ContractDefinition, EnumDefinition, ErrorDefinition, FunctionDefinition, ImportDirective, PragmaDirective, StructDefinition, UserDefinedValueTypeDefinition, UsingForDirective, VariableDeclaration
+        EventDefinition, ContractDefinition, EnumDefinition, ErrorDefinition, FunctionDefinition, ImportDirective, PragmaDirective, StructDefinition, UserDefinedValueTypeDefinition, UsingForDirective, VariableDeclaration, Unknown
     );
     BaseNode: (
-        EnumDefinition, ErrorDefinition, FunctionDefinition, StructDefinition, UserDefinedValueTypeDefinition, UsingForDirective, VariableDeclaration, EventDefinition, ModifierDefinition
+        EnumDefinition, ErrorDefinition, FunctionDefinition, StructDefinition, UserDefinedValueTypeDefinition, UsingForDirective, VariableDeclaration, EventDefinition, ModifierDefinition, Unknown
     );
     Statement: (
-        Block, Break, Continue, DoWhileStatement, EmitStatement, ExpressionStatement, ForStatement, IfStatement, PlaceholderStatement, Return, RevertStatement, TryStatement, UncheckedBlock, VariableDeclarationStatement, WhileStatement, InlineAssembly
+        Block, Break, Continue, DoWhileStatement, EmitStatement, ExpressionStatement, ForStatement, IfStatement, PlaceholderStatement, Return, RevertStatement, Throw, TryStatement, UncheckedBlock, VariableDeclarationStatement, WhileStatement, InlineAssembly, Unknown
     );
     ModifierName: (
         Identifier, IdentifierPath
@@ -520,16 +475,16 @@ ast_visitor! {
         UserDefinedTypeName, IdentifierPath
     );
     TypeName: (
-        ArrayTypeName, ElementaryTypeName, FunctionTypeName, Mapping, UserDefinedTypeName
+        ArrayTypeName, ElementaryTypeName, FunctionTypeName, Mapping, UserDefinedTypeName, Unknown
     );
     LibraryName: (
         UserDefinedTypeName, IdentifierPath
     );
     Expression: (
-        Assignment, BinaryOperation, Conditional, ElementaryTypeNameExpression, FunctionCall, FunctionCallOptions, Identifier, IndexAccess, IndexRangeAccess, Literal, MemberAccess, NewExpression, TupleExpression, UnaryOperation
+        Assignment, BinaryOperation, Conditional, ElementaryTypeNameExpression, FunctionCall, FunctionCallOptions, Identifier, IndexAccess, IndexRangeAccess, Literal, MemberAccess, NewExpression, TupleExpression, UnaryOperation, Unknown
     );
     Body: (
-        Block, Break, Continue, DoWhileStatement, EmitStatement, ExpressionStatement, ForStatement, IfStatement, InlineAssembly, PlaceholderStatement, Return, RevertStatement, TryStatement, UncheckedBlock, VariableDeclarationStatement, WhileStatement
+        Block, Break, Continue, DoWhileStatement, EmitStatement, ExpressionStatement, ForStatement, IfStatement, InlineAssembly, PlaceholderStatement, Return, RevertStatement, Throw, TryStatement, UncheckedBlock, VariableDeclarationStatement, WhileStatement, Unknown
     );
     CompatabilityTypeName: (
         ElementaryTypeName, Name
@@ -538,7 +493,7 @@ ast_visitor! {
         ExpressionStatement, VariableDeclarationStatement
     );
     FalseBody: (
-        Block, Break, Continue, DoWhileStatement, EmitStatement, ExpressionStatement, ForStatement, IfStatement, PlaceholderStatement, Return, RevertStatement, TryStatement, UncheckedBlock, VariableDeclarationStatement, WhileStatement
+        Block, Break, Continue, DoWhileStatement, EmitStatement, ExpressionStatement, ForStatement, IfStatement, PlaceholderStatement, Return, RevertStatement, Throw, TryStatement, UncheckedBlock, VariableDeclarationStatement, WhileStatement, Unknown
     );
     ExternalReferenceCompatible: (
         ExternalReference, ExternalReferenceOld
@@ -585,6 +540,7 @@ ast_visitor! {
     PlaceholderStatement: [];
     Return: [expression];
     RevertStatement: [error_call];
+    Throw: [];
     TryStatement: [clauses, external_call];
     UncheckedBlock: [statements];
     VariableDeclarationStatement: [initial_value, declarations];
@@ -632,6 +588,7 @@ ast_visitor! {
     #[no_id=true] YulLiteralValue: [];
     #[no_id=true] YulLiteralHexValue: [];
     #[no_id=true] YulTypedName: [];
+    #[has_refs=true] Unknown: [];
 }
 
 impl<T: AstVisitor + Debug> AstVisitor for Option<T> {
@@ -644,12 +601,12 @@ impl<T: AstVisitor + Debug> AstVisitor for Option<T> {
         }
     }
 
-    unsafe fn filter_ref_by_node_type<'a, N: Into<NodeType>>(
+    fn filter_ref_by_node_type<'a, N: Into<NodeType>>(
         &'a self,
         node_type: N,
     ) -> Vec<NodeTypeInternalRef<'a>> {
         match self {
-            Some(t) => unsafe { t.filter_ref_by_node_type(node_type) },
+            Some(t) => t.filter_ref_by_node_type(node_type),
             None => vec![],
         }
     }
@@ -661,9 +618,9 @@ impl<T: AstVisitor + Debug> AstVisitor for Option<T> {
         }
     }
 
-    unsafe fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {
+    fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {
         match self {
-            Some(t) => unsafe { t.filter_ref_by_reference_id(id) },
+            Some(t) => t.filter_ref_by_reference_id(id),
             None => vec![],
         }
     }
@@ -675,9 +632,9 @@ impl<T: AstVisitor + Debug> AstVisitor for Option<T> {
         }
     }
 
-    unsafe fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {
+    fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {
         match self {
-            Some(t) => unsafe { t.filter_ref_by_id(id) },
+            Some(t) => t.filter_ref_by_id(id),
             None => None,
         }
     }
@@ -734,17 +691,15 @@ impl<T: AstVisitor> AstVisitor for Vec<T> {
             .collect()
     }
 
-    unsafe fn filter_ref_by_node_type<'a, N: Into<NodeType>>(
+    fn filter_ref_by_node_type<'a, N: Into<NodeType>>(
         &'a self,
         node_type: N,
     ) -> Vec<NodeTypeInternalRef<'a>> {
         let node_type: NodeType = node_type.into();
 
-        unsafe {
-            self.iter()
-                .flat_map(|node| node.filter_ref_by_node_type(node_type))
-        }
-        .collect()
+        self.iter()
+            .flat_map(|node| node.filter_ref_by_node_type(node_type))
+            .collect()
     }
 
     fn filter_by_reference_id(&self, id: isize) -> Vec<NodeTypeInternal> {
@@ -753,9 +708,9 @@ impl<T: AstVisitor> AstVisitor for Vec<T> {
             .collect()
     }
 
-    unsafe fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {
+    fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {
         self.iter()
-            .flat_map(|node| unsafe { node.filter_ref_by_reference_id(id) })
+            .flat_map(|node| node.filter_ref_by_reference_id(id))
             .collect()
     }
 
@@ -763,9 +718,8 @@ impl<T: AstVisitor> AstVisitor for Vec<T> {
         self.iter().find_map(|node| node.filter_by_id(id))
     }
 
-    unsafe fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {
-        self.iter()
-            .find_map(|node| unsafe { node.filter_ref_by_id(id) })
+    fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {
+        self.iter().find_map(|node| node.filter_ref_by_id(id))
     }
 
     fn childrens_id(&self) -> Vec<isize> {
@@ -805,14 +759,14 @@ impl<T: AstVisitor> AstVisitor for &[T] {
             .collect()
     }
 
-    unsafe fn filter_ref_by_node_type<'a, N: Into<NodeType>>(
+    fn filter_ref_by_node_type<'a, N: Into<NodeType>>(
         &'a self,
         node_type: N,
     ) -> Vec<NodeTypeInternalRef<'a>> {
         let node_type: NodeType = node_type.into();
 
         self.iter()
-            .flat_map(|node| unsafe { node.filter_ref_by_node_type(node_type) })
+            .flat_map(|node| node.filter_ref_by_node_type(node_type))
             .collect()
     }
 
@@ -822,9 +776,9 @@ impl<T: AstVisitor> AstVisitor for &[T] {
             .collect()
     }
 
-    unsafe fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {
+    fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {
         self.iter()
-            .flat_map(|node| unsafe { node.filter_ref_by_reference_id(id) })
+            .flat_map(|node| node.filter_ref_by_reference_id(id))
             .collect()
     }
 
@@ -832,9 +786,8 @@ impl<T: AstVisitor> AstVisitor for &[T] {
         self.iter().find_map(|node| node.filter_by_id(id))
     }
 
-    unsafe fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {
-        self.iter()
-            .find_map(|node| unsafe { node.filter_ref_by_id(id) })
+    fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {
+        self.iter().find_map(|node| node.filter_ref_by_id(id))
     }
 
     fn childrens_id(&self) -> Vec<isize> {
@@ -874,14 +827,14 @@ impl<T: AstVisitor> AstVisitor for [T] {
             .collect()
     }
 
-    unsafe fn filter_ref_by_node_type<'a, N: Into<NodeType>>(
+    fn filter_ref_by_node_type<'a, N: Into<NodeType>>(
         &'a self,
         node_type: N,
     ) -> Vec<NodeTypeInternalRef<'a>> {
         let node_type: NodeType = node_type.into();
 
         self.iter()
-            .flat_map(|node| unsafe { node.filter_ref_by_node_type(node_type) })
+            .flat_map(|node| node.filter_ref_by_node_type(node_type))
             .collect()
     }
 
@@ -891,9 +844,9 @@ impl<T: AstVisitor> AstVisitor for [T] {
             .collect()
     }
 
-    unsafe fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {
+    fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {
         self.iter()
-            .flat_map(|node| unsafe { node.filter_ref_by_reference_id(id) })
+            .flat_map(|node| node.filter_ref_by_reference_id(id))
             .collect()
     }
 
@@ -901,9 +854,8 @@ impl<T: AstVisitor> AstVisitor for [T] {
         self.iter().find_map(|node| node.filter_by_id(id))
     }
 
-    unsafe fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {
-        self.iter()
-            .find_map(|node| unsafe { node.filter_ref_by_id(id) })
+    fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {
+        self.iter().find_map(|node| node.filter_ref_by_id(id))
     }
 
     fn childrens_id(&self) -> Vec<isize> {
@@ -941,29 +893,29 @@ impl<T: AstVisitor> AstVisitor for &T {
         (*self).filter_by_node_type(node_type)
     }
 
-    unsafe fn filter_ref_by_node_type<'a, N: Into<NodeType>>(
+    fn filter_ref_by_node_type<'a, N: Into<NodeType>>(
         &'a self,
         node_type: N,
     ) -> Vec<NodeTypeInternalRef<'a>> {
         let node_type: NodeType = node_type.into();
 
-        unsafe { (*self).filter_ref_by_node_type(node_type) }
+        (*self).filter_ref_by_node_type(node_type)
     }
 
     fn filter_by_reference_id(&self, id: isize) -> Vec<NodeTypeInternal> {
         (*self).filter_by_reference_id(id)
     }
 
-    unsafe fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {
-        unsafe { (*self).filter_ref_by_reference_id(id) }
+    fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {
+        (*self).filter_ref_by_reference_id(id)
     }
 
     fn filter_by_id(&self, id: isize) -> Option<NodeTypeInternal> {
         (*self).filter_by_id(id)
     }
 
-    unsafe fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {
-        unsafe { (*self).filter_ref_by_id(id) }
+    fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {
+        (*self).filter_ref_by_id(id)
     }
 
     fn childrens_id(&self) -> Vec<isize> {
@@ -1001,29 +953,29 @@ impl<T: AstVisitor> AstVisitor for Box<T> {
         self.as_ref().filter_by_node_type(node_type)
     }
 
-    unsafe fn filter_ref_by_node_type<'a, N: Into<NodeType>>(
+    fn filter_ref_by_node_type<'a, N: Into<NodeType>>(
         &'a self,
         node_type: N,
     ) -> Vec<NodeTypeInternalRef<'a>> {
         let node_type: NodeType = node_type.into();
 
-        unsafe { self.as_ref().filter_ref_by_node_type(node_type) }
+        self.as_ref().filter_ref_by_node_type(node_type)
     }
 
     fn filter_by_reference_id(&self, id: isize) -> Vec<NodeTypeInternal> {
         self.as_ref().filter_by_reference_id(id)
     }
 
-    unsafe fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {
-        unsafe { self.as_ref().filter_ref_by_reference_id(id) }
+    fn filter_ref_by_reference_id<'a>(&'a self, id: isize) -> Vec<NodeTypeInternalRef<'a>> {
+        self.as_ref().filter_ref_by_reference_id(id)
     }
 
     fn filter_by_id(&self, id: isize) -> Option<NodeTypeInternal> {
         self.as_ref().filter_by_id(id)
     }
 
-    unsafe fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {
-        unsafe { self.as_ref().filter_ref_by_id(id) }
+    fn filter_ref_by_id<'a>(&'a self, id: isize) -> Option<NodeTypeInternalRef<'a>> {
+        self.as_ref().filter_ref_by_id(id)
     }
 
     fn childrens_id(&self) -> Vec<isize> {
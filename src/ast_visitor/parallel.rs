@@ -0,0 +1,216 @@
+//! Parallel queries, behind the `rayon` feature.
+//!
+//! A root is cut into tasks at its source units and contracts: every source unit and contract is
+//! a task of its own node, and every other directive or contract member is a task of its whole
+//! subtree. The tasks run on the rayon thread pool and their results are joined in walk order, so
+//! a parallel query returns the same nodes in the same order on every run, parents before their
+//! children, like [`Selector::select_located`](super::Selector::select_located).
+
+use rayon::prelude::*;
+
+use crate::{
+    AstParserResult,
+    ast_framework::Workspace,
+    ast_specs::{
+        Directive, NodeType, NodeTypeInternalRef, SourceUnit, directives::ContractDefinition,
+    },
+};
+
+use super::{
+    AstPath, Located, Pattern, PatternMatch, Selector, VisitControl, VisitPath,
+    path::walk_with_path_from,
+};
+
+pub trait ParAstRefVisitor<'root> {
+    /// Runs `f` on every node in parallel, like a detector returning its findings, and joins the
+    /// results in walk order.
+    fn par_flat_map_nodes<R, I, F>(&'root self, f: F) -> Vec<R>
+    where
+        R: Send,
+        I: IntoIterator<Item = R>,
+        F: Fn(NodeTypeInternalRef<'root>, &AstPath<'root>) -> I + Sync;
+
+    /// Parallel [`refs_by_node_type`](super::AstRefVisitor::refs_by_node_type).
+    fn par_refs_by_node_type<N: Into<NodeType>>(
+        &'root self,
+        node_type: N,
+    ) -> Vec<NodeTypeInternalRef<'root>>;
+
+    /// Parallel [`refs_by_reference_id`](super::AstRefVisitor::refs_by_reference_id).
+    fn par_refs_by_reference_id(&'root self, id: isize) -> Vec<NodeTypeInternalRef<'root>>;
+
+    /// Parallel [`refs_by_selector`](super::AstRefVisitor::refs_by_selector).
+    fn par_refs_by_selector(
+        &'root self,
+        selector: &str,
+    ) -> AstParserResult<Vec<NodeTypeInternalRef<'root>>>;
+
+    /// Parallel [`located_by_node_type`](super::AstRefVisitor::located_by_node_type).
+    fn par_located_by_node_type<N: Into<NodeType>>(
+        &'root self,
+        node_type: N,
+    ) -> Vec<Located<'root>>;
+
+    /// Parallel [`located_by_selector`](super::AstRefVisitor::located_by_selector).
+    fn par_located_by_selector(&'root self, selector: &str)
+    -> AstParserResult<Vec<Located<'root>>>;
+
+    /// Parallel [`Pattern::find`].
+    fn par_find_pattern(&'root self, pattern: &Pattern) -> Vec<PatternMatch<'root>>;
+}
+
+impl<'root, T: ParallelRoot<'root> + ?Sized> ParAstRefVisitor<'root> for T {
+    fn par_flat_map_nodes<R, I, F>(&'root self, f: F) -> Vec<R>
+    where
+        R: Send,
+        I: IntoIterator<Item = R>,
+        F: Fn(NodeTypeInternalRef<'root>, &AstPath<'root>) -> I + Sync,
+    {
+        let mut tasks = vec![];
+        self.split(&mut tasks);
+        tasks
+            .par_iter()
+            .flat_map_iter(|task| {
+                let mut mapper = Mapper {
+                    f: &f,
+                    found: vec![],
+                };
+                match task.whole {
+                    true => {
+                        let _ = walk_with_path_from(task.node, &task.path, &mut mapper);
+                    }
+                    false => mapper.found.extend(f(task.node, &task.path)),
+                }
+                mapper.found
+            })
+            .collect()
+    }
+
+    fn par_refs_by_node_type<N: Into<NodeType>>(
+        &'root self,
+        node_type: N,
+    ) -> Vec<NodeTypeInternalRef<'root>> {
+        let node_type = node_type.into();
+        self.par_flat_map_nodes(|node, _| (NodeType::from(node) == node_type).then_some(node))
+    }
+
+    fn par_refs_by_reference_id(&'root self, id: isize) -> Vec<NodeTypeInternalRef<'root>> {
+        self.par_flat_map_nodes(|node, _| (node.reference_id() == Some(id)).then_some(node))
+    }
+
+    fn par_refs_by_selector(
+        &'root self,
+        selector: &str,
+    ) -> AstParserResult<Vec<NodeTypeInternalRef<'root>>> {
+        let selector = Selector::parse(selector)?;
+        Ok(self.par_flat_map_nodes(|node, path| {
+            selector.matches(node, path.ancestors()).then_some(node)
+        }))
+    }
+
+    fn par_located_by_node_type<N: Into<NodeType>>(
+        &'root self,
+        node_type: N,
+    ) -> Vec<Located<'root>> {
+        let node_type = node_type.into();
+        self.par_flat_map_nodes(|node, path| {
+            (NodeType::from(node) == node_type).then(|| Located::new(node, path.clone()))
+        })
+    }
+
+    fn par_located_by_selector(
+        &'root self,
+        selector: &str,
+    ) -> AstParserResult<Vec<Located<'root>>> {
+        let selector = Selector::parse(selector)?;
+        Ok(self.par_flat_map_nodes(|node, path| {
+            selector
+                .matches(node, path.ancestors())
+                .then(|| Located::new(node, path.clone()))
+        }))
+    }
+
+    fn par_find_pattern(&'root self, pattern: &Pattern) -> Vec<PatternMatch<'root>> {
+        self.par_flat_map_nodes(|node, path| pattern.match_at(node, path))
+    }
+}
+
+/// Roots [`ParAstRefVisitor`] can split, not nameable outside the crate.
+pub trait ParallelRoot<'root> {
+    /// Pushes the tasks of `self` in walk order.
+    fn split(&'root self, tasks: &mut Vec<Task<'root>>);
+}
+
+impl<'root> ParallelRoot<'root> for Workspace {
+    fn split(&'root self, tasks: &mut Vec<Task<'root>>) {
+        for (position, unit) in self.units().enumerate() {
+            split(unit.into(), AstPath::at(position), tasks);
+        }
+    }
+}
+
+impl<'root> ParallelRoot<'root> for SourceUnit {
+    fn split(&'root self, tasks: &mut Vec<Task<'root>>) {
+        split(self.into(), AstPath::default(), tasks);
+    }
+}
+
+impl<'root> ParallelRoot<'root> for Directive {
+    fn split(&'root self, tasks: &mut Vec<Task<'root>>) {
+        split(self.into(), AstPath::default(), tasks);
+    }
+}
+
+impl<'root> ParallelRoot<'root> for ContractDefinition {
+    fn split(&'root self, tasks: &mut Vec<Task<'root>>) {
+        split(self.into(), AstPath::default(), tasks);
+    }
+}
+
+/// Node found at `path`, alone or with everything below it.
+pub struct Task<'root> {
+    node: NodeTypeInternalRef<'root>,
+    path: AstPath<'root>,
+    whole: bool,
+}
+
+fn split<'root>(
+    node: NodeTypeInternalRef<'root>,
+    path: AstPath<'root>,
+    tasks: &mut Vec<Task<'root>>,
+) {
+    let whole = !matches!(
+        node,
+        NodeTypeInternalRef::SourceUnit(_) | NodeTypeInternalRef::ContractDefinition(_)
+    );
+    tasks.push(Task {
+        node,
+        path: path.clone(),
+        whole,
+    });
+    if !whole {
+        for (position, child) in node.children().into_iter().enumerate() {
+            split(child, path.child(node, position), tasks);
+        }
+    }
+}
+
+struct Mapper<'f, F, R> {
+    f: &'f F,
+    found: Vec<R>,
+}
+
+impl<'root, F, I, R> VisitPath<'root> for Mapper<'_, F, R>
+where
+    I: IntoIterator<Item = R>,
+    F: Fn(NodeTypeInternalRef<'root>, &AstPath<'root>) -> I,
+{
+    fn visit_node(
+        &mut self,
+        node: NodeTypeInternalRef<'root>,
+        path: &AstPath<'root>,
+    ) -> VisitControl {
+        self.found.extend((self.f)(node, path));
+        VisitControl::Continue
+    }
+}
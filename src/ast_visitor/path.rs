@@ -0,0 +1,344 @@
+//! Ancestor stacks carried along a walk.
+//!
+//! [`walk_with_path`] runs a [`VisitPath`] with the [`AstPath`] of every node, the nodes above it
+//! from the root down. Queries that return [`Located`] nodes keep that path, so a finding can say
+//! where it is without searching the tree again.
+//!
+//! A path also gives nodes without an `id`, like Yul nodes, `SymbolAliases`, `TypeDescriptions`
+//! and `ExternalReference`, a synthetic one, see [`AstPath::node_id`].
+
+use std::{fmt, ops::ControlFlow};
+
+use crate::ast_specs::{
+    NodeType, NodeTypeInternalRef, SourceUnit,
+    base_nodes::ModifierDefinition,
+    directives::{ContractDefinition, FunctionDefinition, FunctionKind},
+    statements::UncheckedBlock,
+};
+
+use super::{Visit, VisitControl, Walk};
+
+/// Nodes above a node, outermost first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AstPath<'root> {
+    ancestors: Vec<NodeTypeInternalRef<'root>>,
+    /// Position of every ancestor among the children of the one before it.
+    positions: Vec<usize>,
+    position: usize,
+}
+
+impl<'root> AstPath<'root> {
+    pub fn ancestors(&self) -> &[NodeTypeInternalRef<'root>] {
+        &self.ancestors
+    }
+
+    /// Position of the node among the children of its parent, in walk order.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// `id` of `node`, or its [synthetic id](Self::synthetic_id) if it has none. `node` must be
+    /// the node this path leads to.
+    pub fn node_id(&self, node: NodeTypeInternalRef<'_>) -> isize {
+        node.id().unwrap_or_else(|| self.synthetic_id(node))
+    }
+
+    /// Id made from the `src` of `node` and its path from the closest ancestor with an `id`.
+    ///
+    /// It stays the same across runs over the same AST. Synthetic ids are below
+    /// `-isize::MAX / 2`, far from the ids solc assigns and the negative ids of builtins.
+    pub fn synthetic_id(&self, node: NodeTypeInternalRef<'_>) -> isize {
+        let mut hasher = Fnv1a::default();
+        let anchor = self
+            .ancestors
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, ancestor)| Some((index, ancestor.id()?)));
+        let steps = match anchor {
+            Some((index, id)) => {
+                hasher.write(&id.to_le_bytes());
+                index + 1
+            }
+            None => 0,
+        };
+        for (ancestor, position) in self.ancestors.iter().zip(&self.positions).skip(steps) {
+            hasher.write(format!("{:?}", NodeType::from(*ancestor)).as_bytes());
+            hasher.write(&position.to_le_bytes());
+        }
+        hasher.write(format!("{:?}", NodeType::from(node)).as_bytes());
+        hasher.write(&self.position.to_le_bytes());
+        hasher.write(node.own_src().unwrap_or_default().as_bytes());
+
+        let span = (isize::MAX / 2) as u64;
+        -((hasher.0 % span) as isize) - isize::MAX / 2
+    }
+
+    /// Path of a root at `position` among the roots of a walk.
+    pub(super) fn at(position: usize) -> Self {
+        Self {
+            position,
+            ..Self::default()
+        }
+    }
+
+    /// Path of the child at `position` of `node`, the node this path leads to.
+    pub(super) fn child(&self, node: NodeTypeInternalRef<'root>, position: usize) -> Self {
+        let mut path = self.clone();
+        path.ancestors.push(node);
+        path.positions.push(self.position);
+        path.position = position;
+        path
+    }
+
+    pub fn parent(&self) -> Option<NodeTypeInternalRef<'root>> {
+        self.ancestors.last().copied()
+    }
+
+    /// Innermost ancestor of the given type.
+    pub fn closest<N: Into<NodeType>>(&self, node_type: N) -> Option<NodeTypeInternalRef<'root>> {
+        let node_type = node_type.into();
+        self.ancestors
+            .iter()
+            .rev()
+            .find(|node| NodeType::from(**node) == node_type)
+            .copied()
+    }
+
+    pub fn source_unit(&self) -> Option<&'root SourceUnit> {
+        self.ancestors.iter().find_map(|node| match node {
+            NodeTypeInternalRef::SourceUnit(unit) => Some(*unit),
+            _ => None,
+        })
+    }
+
+    pub fn contract(&self) -> Option<&'root ContractDefinition> {
+        self.ancestors.iter().rev().find_map(|node| match node {
+            NodeTypeInternalRef::ContractDefinition(contract) => Some(*contract),
+            _ => None,
+        })
+    }
+
+    pub fn function(&self) -> Option<&'root FunctionDefinition> {
+        self.ancestors.iter().rev().find_map(|node| match node {
+            NodeTypeInternalRef::FunctionDefinition(function) => Some(*function),
+            _ => None,
+        })
+    }
+
+    pub fn modifier(&self) -> Option<&'root ModifierDefinition> {
+        self.ancestors.iter().rev().find_map(|node| match node {
+            NodeTypeInternalRef::ModifierDefinition(modifier) => Some(*modifier),
+            _ => None,
+        })
+    }
+
+    /// Innermost `for`, `while` or `do while` statement.
+    pub fn enclosing_loop(&self) -> Option<NodeTypeInternalRef<'root>> {
+        self.ancestors.iter().rev().copied().find(|node| {
+            matches!(
+                node,
+                NodeTypeInternalRef::ForStatement(_)
+                    | NodeTypeInternalRef::WhileStatement(_)
+                    | NodeTypeInternalRef::DoWhileStatement(_)
+            )
+        })
+    }
+
+    pub fn unchecked(&self) -> Option<&'root UncheckedBlock> {
+        self.ancestors.iter().rev().find_map(|node| match node {
+            NodeTypeInternalRef::UncheckedBlock(block) => Some(*block),
+            _ => None,
+        })
+    }
+}
+
+/// Reads like "in `Vault.withdraw`, inside `for` loop, inside `unchecked` block".
+impl fmt::Display for AstPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let callable = match (self.function(), self.modifier()) {
+            (Some(function), _) => Some(match function.kind() {
+                FunctionKind::Function | FunctionKind::FreeFunction => function.name().to_owned(),
+                kind => kind.to_string(),
+            }),
+            (None, Some(modifier)) => Some(modifier.name().to_owned()),
+            (None, None) => None,
+        };
+        match (self.contract(), callable) {
+            (Some(contract), Some(callable)) => write!(f, "in `{}.{callable}`", contract.name())?,
+            (Some(contract), None) => write!(f, "in `{}`", contract.name())?,
+            (None, Some(callable)) => write!(f, "in `{callable}`")?,
+            (None, None) => write!(f, "at file level")?,
+        }
+        match self.enclosing_loop() {
+            Some(NodeTypeInternalRef::ForStatement(_)) => write!(f, ", inside `for` loop")?,
+            Some(NodeTypeInternalRef::WhileStatement(_)) => write!(f, ", inside `while` loop")?,
+            Some(_) => write!(f, ", inside `do while` loop")?,
+            None => {}
+        }
+        if self.unchecked().is_some() {
+            write!(f, ", inside `unchecked` block")?;
+        }
+        Ok(())
+    }
+}
+
+/// Node returned by a query, with the path it was found at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Located<'root> {
+    node: NodeTypeInternalRef<'root>,
+    path: AstPath<'root>,
+}
+
+impl<'root> Located<'root> {
+    pub(crate) fn new(node: NodeTypeInternalRef<'root>, path: AstPath<'root>) -> Self {
+        Self { node, path }
+    }
+
+    pub fn node(&self) -> NodeTypeInternalRef<'root> {
+        self.node
+    }
+
+    pub fn path(&self) -> &AstPath<'root> {
+        &self.path
+    }
+
+    /// `id` of the node, synthetic for node types without one.
+    pub fn id(&self) -> isize {
+        self.path.node_id(self.node)
+    }
+}
+
+/// Callbacks for [`walk_with_path`].
+pub trait VisitPath<'ast> {
+    fn visit_node(
+        &mut self,
+        node: NodeTypeInternalRef<'ast>,
+        path: &AstPath<'ast>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_node(
+        &mut self,
+        node: NodeTypeInternalRef<'ast>,
+        path: &AstPath<'ast>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+}
+
+/// Walks `root` like [`Walk::walk`], handing every hook the path of its node.
+pub fn walk_with_path<'ast, T, V>(root: &'ast T, visitor: &mut V) -> ControlFlow<()>
+where
+    T: Walk<'ast> + ?Sized,
+    V: VisitPath<'ast> + ?Sized,
+{
+    root.walk(&mut PathTracker {
+        visitor,
+        path: AstPath::default(),
+        children: vec![0],
+    })
+}
+
+/// Like [`walk_with_path`], for a `node` found at `path` by an earlier walk.
+pub(super) fn walk_with_path_from<'ast, V>(
+    node: NodeTypeInternalRef<'ast>,
+    path: &AstPath<'ast>,
+    visitor: &mut V,
+) -> ControlFlow<()>
+where
+    V: VisitPath<'ast> + ?Sized,
+{
+    node.walk(&mut PathTracker {
+        visitor,
+        path: path.clone(),
+        children: vec![path.position],
+    })
+}
+
+/// Nodes below `root` for which `predicate` holds, parents before their children.
+pub(crate) fn locate<'root, T, P>(root: &'root T, predicate: P) -> Vec<Located<'root>>
+where
+    T: Walk<'root> + ?Sized,
+    P: FnMut(NodeTypeInternalRef<'root>, &AstPath<'root>) -> bool,
+{
+    let mut locator = Locator {
+        predicate,
+        found: vec![],
+    };
+    let _ = walk_with_path(root, &mut locator);
+    locator.found
+}
+
+struct PathTracker<'v, 'ast, V: ?Sized> {
+    visitor: &'v mut V,
+    path: AstPath<'ast>,
+    /// Children seen so far of every node on the path, and of the root.
+    children: Vec<usize>,
+}
+
+impl<'ast, V: VisitPath<'ast> + ?Sized> Visit<'ast> for PathTracker<'_, 'ast, V> {
+    fn visit_node(&mut self, node: NodeTypeInternalRef<'ast>) -> VisitControl {
+        let siblings = self
+            .children
+            .last_mut()
+            .expect("root counter is never popped");
+        self.path.position = *siblings;
+        *siblings += 1;
+        let control = self.visitor.visit_node(node, &self.path);
+        // `leave_node` runs unless the walk stops
+        self.path.ancestors.push(node);
+        self.path.positions.push(self.path.position);
+        self.children.push(0);
+        control
+    }
+
+    fn leave_node(&mut self, node: NodeTypeInternalRef<'ast>) -> VisitControl {
+        self.path.ancestors.pop();
+        self.children.pop();
+        if let Some(position) = self.path.positions.pop() {
+            self.path.position = position;
+        }
+        self.visitor.leave_node(node, &self.path)
+    }
+}
+
+/// 64-bit FNV-1a, unlike the std hashers the same in every run.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+struct Locator<'root, P> {
+    predicate: P,
+    found: Vec<Located<'root>>,
+}
+
+impl<'root, P> VisitPath<'root> for Locator<'root, P>
+where
+    P: FnMut(NodeTypeInternalRef<'root>, &AstPath<'root>) -> bool,
+{
+    fn visit_node(
+        &mut self,
+        node: NodeTypeInternalRef<'root>,
+        path: &AstPath<'root>,
+    ) -> VisitControl {
+        if (self.predicate)(node, path) {
+            self.found.push(Located::new(node, path.clone()));
+        }
+        VisitControl::Continue
+    }
+}
@@ -0,0 +1,275 @@
+//! Code patterns with metavariables, in the spirit of semgrep.
+//!
+//! A pattern is an expression or a statement where identifiers starting with `$`, like `$ADDR`,
+//! stand for any expression. `$S;` stands for any statement. Nodes match when they have the same node
+//! type, the same syntactic attributes and children that match in turn, so ids, `src` and
+//! `typeDescriptions` are never compared. A metavariable used twice must bind equal subtrees.
+//!
+//! ```text
+//! $ADDR.call{value: $V}($DATA)
+//! require($X == msg.sender)
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{
+    AstParserResult,
+    ast_parse::SolidityParser,
+    ast_specs::{Expression, NodeType, NodeTypeInternalRef, Statement},
+};
+
+use super::{
+    AstPath, VisitControl, VisitPath, Walk,
+    selector::{attribute, attribute_names},
+    walk_with_path,
+};
+
+/// Attributes set by the type checker, which a parsed pattern cannot know.
+const SEMANTIC_ATTRIBUTES: &[(NodeType, &str)] = &[
+    (NodeType::FunctionCall, "kind"),
+    (NodeType::FunctionCall, "tryCall"),
+];
+
+pub type Bindings<'root> = HashMap<String, NodeTypeInternalRef<'root>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    root: PatternRoot,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PatternRoot {
+    Expression(Box<Expression>),
+    Statement(Box<Statement>),
+}
+
+/// Node a [`Pattern`] matched, with its path and what its metavariables stand for.
+#[derive(Debug, Clone)]
+pub struct PatternMatch<'root> {
+    node: NodeTypeInternalRef<'root>,
+    path: AstPath<'root>,
+    bindings: Bindings<'root>,
+}
+
+impl<'root> PatternMatch<'root> {
+    pub fn node(&self) -> NodeTypeInternalRef<'root> {
+        self.node
+    }
+
+    pub fn path(&self) -> &AstPath<'root> {
+        &self.path
+    }
+
+    pub fn bindings(&self) -> &Bindings<'root> {
+        &self.bindings
+    }
+
+    /// Node bound to `metavariable`, with or without its `$`.
+    pub fn binding(&self, metavariable: &str) -> Option<NodeTypeInternalRef<'root>> {
+        match metavariable.starts_with('$') {
+            true => self.bindings.get(metavariable).copied(),
+            false => self.bindings.get(&format!("${metavariable}")).copied(),
+        }
+    }
+}
+
+impl Pattern {
+    /// Parses a statement if `source` ends with `;` or `}`, an expression otherwise.
+    pub fn parse(source: &str) -> AstParserResult<Self> {
+        let parser = SolidityParser::new();
+        let trimmed = source.trim_end();
+        if trimmed.ends_with(';') || trimmed.ends_with('}') {
+            parser.parse_statement(source).map(Self::from)
+        } else {
+            parser.parse_expression(source).map(Self::from)
+        }
+    }
+
+    /// Matches of the pattern below `root`, parents before their children.
+    pub fn find<'root, T: Walk<'root> + ?Sized>(&self, root: &'root T) -> Vec<PatternMatch<'root>> {
+        let mut finder = Finder {
+            pattern: self,
+            found: vec![],
+        };
+        let _ = walk_with_path(root, &mut finder);
+        finder.found
+    }
+
+    /// Bindings if `node` itself matches the pattern.
+    pub fn matches<'root>(&self, node: NodeTypeInternalRef<'root>) -> Option<Bindings<'root>> {
+        let mut bindings = Bindings::new();
+        match_node(self.root(), node, Some(&mut bindings)).then_some(bindings)
+    }
+
+    /// Match of the pattern at `node`, which `path` leads to.
+    pub(super) fn match_at<'root>(
+        &self,
+        node: NodeTypeInternalRef<'root>,
+        path: &AstPath<'root>,
+    ) -> Option<PatternMatch<'root>> {
+        let bindings = self.matches(node)?;
+        Some(PatternMatch {
+            node,
+            path: path.clone(),
+            bindings,
+        })
+    }
+
+    fn root(&self) -> NodeTypeInternalRef<'_> {
+        match &self.root {
+            PatternRoot::Expression(expression) => expression.as_ref().into(),
+            PatternRoot::Statement(statement) => statement.as_ref().into(),
+        }
+    }
+}
+
+impl From<Expression> for Pattern {
+    fn from(value: Expression) -> Self {
+        Self {
+            root: PatternRoot::Expression(Box::new(value)),
+        }
+    }
+}
+
+impl From<Statement> for Pattern {
+    fn from(value: Statement) -> Self {
+        Self {
+            root: PatternRoot::Statement(Box::new(value)),
+        }
+    }
+}
+
+struct Finder<'p, 'root> {
+    pattern: &'p Pattern,
+    found: Vec<PatternMatch<'root>>,
+}
+
+impl<'root> VisitPath<'root> for Finder<'_, 'root> {
+    fn visit_node(
+        &mut self,
+        node: NodeTypeInternalRef<'root>,
+        path: &AstPath<'root>,
+    ) -> VisitControl {
+        self.found.extend(self.pattern.match_at(node, path));
+        VisitControl::Continue
+    }
+}
+
+/// Nodes a metavariable stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Metavariable {
+    /// `$X`, any expression.
+    Expression,
+    /// `$S;`, any statement.
+    Statement,
+}
+
+impl Metavariable {
+    fn accepts(self, node: NodeTypeInternalRef<'_>) -> bool {
+        use NodeTypeInternalRef as N;
+        match self {
+            Metavariable::Expression => matches!(
+                node,
+                N::Assignment(_)
+                    | N::BinaryOperation(_)
+                    | N::Conditional(_)
+                    | N::ElementaryTypeNameExpression(_)
+                    | N::FunctionCall(_)
+                    | N::FunctionCallOptions(_)
+                    | N::Identifier(_)
+                    | N::IndexAccess(_)
+                    | N::IndexRangeAccess(_)
+                    | N::Literal(_)
+                    | N::MemberAccess(_)
+                    | N::NewExpression(_)
+                    | N::TupleExpression(_)
+                    | N::UnaryOperation(_)
+            ),
+            Metavariable::Statement => matches!(
+                node,
+                N::Block(_)
+                    | N::Break(_)
+                    | N::Continue(_)
+                    | N::DoWhileStatement(_)
+                    | N::EmitStatement(_)
+                    | N::ExpressionStatement(_)
+                    | N::ForStatement(_)
+                    | N::IfStatement(_)
+                    | N::InlineAssembly(_)
+                    | N::PlaceholderStatement(_)
+                    | N::Return(_)
+                    | N::RevertStatement(_)
+                    | N::Throw(_)
+                    | N::TryStatement(_)
+                    | N::UncheckedBlock(_)
+                    | N::VariableDeclarationStatement(_)
+                    | N::WhileStatement(_)
+            ),
+        }
+    }
+}
+
+/// Name and kind of the metavariable `node` is, if any.
+fn metavariable<'p>(node: NodeTypeInternalRef<'p>) -> Option<(&'p str, Metavariable)> {
+    match node {
+        NodeTypeInternalRef::Identifier(identifier) if identifier.name().starts_with('$') => {
+            Some((identifier.name(), Metavariable::Expression))
+        }
+        NodeTypeInternalRef::ExpressionStatement(statement) => match &statement.expression {
+            Some(Expression::Identifier(identifier)) if identifier.name().starts_with('$') => {
+                Some((identifier.name(), Metavariable::Statement))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether `target` matches `pattern`. Without `bindings` the pattern has no metavariables and
+/// the two nodes must be equal up to ids, `src` and type descriptions.
+fn match_node<'root>(
+    pattern: NodeTypeInternalRef<'_>,
+    target: NodeTypeInternalRef<'root>,
+    mut bindings: Option<&mut Bindings<'root>>,
+) -> bool {
+    if let Some(bindings) = bindings.as_deref_mut()
+        && let Some((name, kind)) = metavariable(pattern)
+    {
+        if !kind.accepts(target) {
+            return false;
+        }
+        return match bindings.get(name) {
+            Some(bound) => match_node(*bound, target, None),
+            None => {
+                bindings.insert(name.to_owned(), target);
+                true
+            }
+        };
+    }
+
+    let node_type = NodeType::from(pattern);
+    if node_type != NodeType::from(target) {
+        return false;
+    }
+    let same_attributes = attribute_names(pattern)
+        .iter()
+        .filter(|name| !SEMANTIC_ATTRIBUTES.contains(&(node_type, **name)))
+        .all(|name| attribute(pattern, name) == attribute(target, name));
+    if !same_attributes {
+        return false;
+    }
+
+    let pattern_children = syntactic_children(pattern);
+    let target_children = syntactic_children(target);
+    pattern_children.len() == target_children.len()
+        && pattern_children
+            .into_iter()
+            .zip(target_children)
+            .all(|(pattern, target)| match_node(pattern, target, bindings.as_deref_mut()))
+}
+
+fn syntactic_children(node: NodeTypeInternalRef<'_>) -> Vec<NodeTypeInternalRef<'_>> {
+    let mut children = node.children();
+    children.retain(|child| !child.is_type_descriptions());
+    children
+}
@@ -0,0 +1,413 @@
+//! CSS-like queries over the AST.
+//!
+//! A selector is a list of node types joined by combinators, `A B` for a `B` anywhere below an
+//! `A` and `A > B` for a `B` directly below an `A`. `*` matches any node type. Each node type can
+//! be narrowed by attribute predicates named like the JSON keys of solc: `[name=transfer]`,
+//! `[visibility!=internal]`, or `[constant]` for an attribute that is set and not `false`. Values
+//! may be quoted. Several selectors can be separated by commas.
+//!
+//! ```text
+//! ContractDefinition[contractKind=contract] FunctionDefinition[visibility=external] FunctionCall[kind=functionCall]
+//! ```
+
+use crate::{
+    AstParserError, AstParserResult, ErrorKind,
+    ast_specs::{NodeType, NodeTypeInternalRef},
+};
+
+use super::{
+    Walk,
+    path::{Located, locate},
+};
+
+/// Parsed query, see the [module docs](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    alternatives: Vec<Complex>,
+}
+
+/// Compound selectors with the combinator in front of every one but the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Complex {
+    compounds: Vec<Compound>,
+    combinators: Vec<Combinator>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Compound {
+    /// `None` for `*`.
+    node_type: Option<NodeType>,
+    predicates: Vec<Predicate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Predicate {
+    Set(String),
+    Eq(String, String),
+    Ne(String, String),
+}
+
+impl Selector {
+    pub fn parse(selector: &str) -> AstParserResult<Self> {
+        SelectorParser {
+            selector,
+            position: 0,
+        }
+        .selector()
+    }
+
+    /// Matching nodes below `root`, parents before their children.
+    pub fn select<'root, T: Walk<'root> + ?Sized>(
+        &self,
+        root: &'root T,
+    ) -> Vec<NodeTypeInternalRef<'root>> {
+        self.select_located(root)
+            .iter()
+            .map(Located::node)
+            .collect()
+    }
+
+    /// Like [`Self::select`], with the path of every match.
+    pub fn select_located<'root, T: Walk<'root> + ?Sized>(
+        &self,
+        root: &'root T,
+    ) -> Vec<Located<'root>> {
+        locate(root, |node, path| self.matches(node, path.ancestors()))
+    }
+
+    /// Whether `node`, below `ancestors` from the outermost one down, matches.
+    pub fn matches(
+        &self,
+        node: NodeTypeInternalRef<'_>,
+        ancestors: &[NodeTypeInternalRef<'_>],
+    ) -> bool {
+        self.alternatives
+            .iter()
+            .any(|complex| complex.matches(complex.compounds.len() - 1, node, ancestors))
+    }
+}
+
+impl Complex {
+    /// Whether `node` matches the compound at `index`, and its ancestors the ones before it.
+    fn matches(
+        &self,
+        index: usize,
+        node: NodeTypeInternalRef<'_>,
+        ancestors: &[NodeTypeInternalRef<'_>],
+    ) -> bool {
+        if !self.compounds[index].matches(node) {
+            return false;
+        }
+        if index == 0 {
+            return true;
+        }
+        match self.combinators[index - 1] {
+            Combinator::Child => ancestors
+                .split_last()
+                .is_some_and(|(parent, above)| self.matches(index - 1, *parent, above)),
+            Combinator::Descendant => (0..ancestors.len())
+                .rev()
+                .any(|i| self.matches(index - 1, ancestors[i], &ancestors[..i])),
+        }
+    }
+}
+
+impl Compound {
+    fn matches(&self, node: NodeTypeInternalRef<'_>) -> bool {
+        if self
+            .node_type
+            .is_some_and(|node_type| NodeType::from(node) != node_type)
+        {
+            return false;
+        }
+        self.predicates.iter().all(|predicate| match predicate {
+            Predicate::Set(name) => attribute(node, name).is_some_and(|value| value != "false"),
+            Predicate::Eq(name, value) => attribute(node, name).as_ref() == Some(value),
+            Predicate::Ne(name, value) => attribute(node, name).as_ref() != Some(value),
+        })
+    }
+}
+
+struct SelectorParser<'s> {
+    selector: &'s str,
+    position: usize,
+}
+
+impl<'s> SelectorParser<'s> {
+    fn rest(&self) -> &'s str {
+        &self.selector[self.position..]
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let rest = self.rest();
+        let skipped = rest.len() - rest.trim_start().len();
+        self.position += skipped;
+        skipped > 0
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        let found = self.rest().starts_with(token);
+        if found {
+            self.position += token.len();
+        }
+        found
+    }
+
+    fn error(&self, message: &str) -> AstParserError {
+        ErrorKind::InvalidSelector {
+            selector: self.selector.to_owned(),
+            offset: self.position,
+            message: message.to_owned(),
+        }
+        .into()
+    }
+
+    fn selector(&mut self) -> AstParserResult<Selector> {
+        let mut alternatives = vec![self.complex()?];
+        while self.eat(",") {
+            alternatives.push(self.complex()?);
+        }
+        if !self.rest().is_empty() {
+            return Err(self.error("expected `,` or end of selector"));
+        }
+        Ok(Selector { alternatives })
+    }
+
+    fn complex(&mut self) -> AstParserResult<Complex> {
+        self.skip_whitespace();
+        let mut complex = Complex {
+            compounds: vec![self.compound()?],
+            combinators: vec![],
+        };
+        loop {
+            let spaced = self.skip_whitespace();
+            let combinator = if self.eat(">") {
+                self.skip_whitespace();
+                Combinator::Child
+            } else if spaced && !self.rest().is_empty() && !self.rest().starts_with(',') {
+                Combinator::Descendant
+            } else {
+                return Ok(complex);
+            };
+            complex.combinators.push(combinator);
+            complex.compounds.push(self.compound()?);
+        }
+    }
+
+    fn compound(&mut self) -> AstParserResult<Compound> {
+        let node_type = if self.eat("*") {
+            None
+        } else {
+            let start = self.position;
+            let name = self.word();
+            let node_type = NodeType::from(name);
+            if name.is_empty() {
+                return Err(self.error("expected a node type or `*`"));
+            }
+            if node_type == NodeType::Unknown && name != "Unknown" {
+                self.position = start;
+                return Err(self.error(&format!("unknown node type `{name}`")));
+            }
+            Some(node_type)
+        };
+
+        let mut predicates = vec![];
+        while self.eat("[") {
+            self.skip_whitespace();
+            let name = self.word().to_owned();
+            if name.is_empty() {
+                return Err(self.error("expected an attribute name"));
+            }
+            self.skip_whitespace();
+            let predicate = if self.eat("!=") {
+                Predicate::Ne(name, self.value()?)
+            } else if self.eat("=") {
+                Predicate::Eq(name, self.value()?)
+            } else {
+                Predicate::Set(name)
+            };
+            self.skip_whitespace();
+            if !self.eat("]") {
+                return Err(self.error("expected `]`"));
+            }
+            predicates.push(predicate);
+        }
+        Ok(Compound {
+            node_type,
+            predicates,
+        })
+    }
+
+    fn word(&mut self) -> &'s str {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        let start = self.position;
+        self.position += len;
+        &self.selector[start..self.position]
+    }
+
+    fn value(&mut self) -> AstParserResult<String> {
+        self.skip_whitespace();
+        for quote in ['"', '\''] {
+            if self.rest().starts_with(quote) {
+                let Some(len) = self.rest()[1..].find(quote) else {
+                    return Err(self.error("unterminated string"));
+                };
+                let value = self.rest()[1..=len].to_owned();
+                self.position += len + 2;
+                return Ok(value);
+            }
+        }
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c == ']' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("expected a value"));
+        }
+        self.position += len;
+        Ok(rest[..len].to_owned())
+    }
+}
+
+/// Attribute value as a selector compares it.
+trait AttributeValue {
+    fn attribute(&self) -> Option<String>;
+}
+
+macro_rules! display_attribute {
+    ($($target:ty),*) => {
+        $(
+            impl AttributeValue for $target {
+                fn attribute(&self) -> Option<String> {
+                    Some(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+display_attribute!(
+    str,
+    String,
+    bool,
+    isize,
+    crate::ast_specs::common::FunctionCallKind,
+    crate::ast_specs::common::LiteralKind,
+    crate::ast_specs::common::Mutability,
+    crate::ast_specs::common::StateMutability,
+    crate::ast_specs::common::StorageLocation,
+    crate::ast_specs::common::Visibility,
+    crate::ast_specs::directives::ContractKind,
+    crate::ast_specs::directives::FunctionKind
+);
+
+impl<T: AttributeValue> AttributeValue for Option<T> {
+    fn attribute(&self) -> Option<String> {
+        self.as_ref()?.attribute()
+    }
+}
+
+impl AttributeValue for Vec<String> {
+    fn attribute(&self) -> Option<String> {
+        Some(self.join(","))
+    }
+}
+
+impl<T: AttributeValue + ?Sized> AttributeValue for &T {
+    fn attribute(&self) -> Option<String> {
+        (**self).attribute()
+    }
+}
+
+/// Attributes by JSON key, each read through the getter of the node type.
+macro_rules! node_attributes {
+    (
+        $(
+            $variant:ident {
+                $($name:literal => $getter:ident),*
+            }
+        )*
+    ) => {
+        fn typed_attribute(node: NodeTypeInternalRef<'_>, name: &str) -> Option<String> {
+            match node {
+                $(
+                    NodeTypeInternalRef::$variant(node) => match name {
+                        $(
+                            $name => node.$getter().attribute(),
+                        )*
+                        _ => None,
+                    },
+                )*
+                _ => None,
+            }
+        }
+
+        /// Keys [`typed_attribute`] knows for the type of `node`.
+        pub(super) fn attribute_names(node: NodeTypeInternalRef<'_>) -> &'static [&'static str] {
+            match node {
+                $(
+                    NodeTypeInternalRef::$variant(_) => &[$($name),*],
+                )*
+                _ => &[],
+            }
+        }
+    };
+}
+
+node_attributes! {
+    ContractDefinition {
+        "name" => name, "contractKind" => contract_kind, "abstract" => _abstract,
+        "fullyImplemented" => fully_implemented
+    }
+    FunctionDefinition {
+        "name" => name, "kind" => kind, "visibility" => visibility,
+        "stateMutability" => state_mutability, "virtual" => _virtual, "implemented" => implemented
+    }
+    ModifierDefinition { "name" => name, "visibility" => visibility, "virtual" => _virtual }
+    EventDefinition { "name" => name, "anonymous" => anonymous }
+    ErrorDefinition { "name" => name }
+    StructDefinition { "name" => name, "visibility" => visibility }
+    EnumDefinition { "name" => name }
+    EnumValue { "name" => name }
+    UserDefinedValueTypeDefinition { "name" => name }
+    VariableDeclaration {
+        "name" => name, "visibility" => visibility, "constant" => constant,
+        "mutability" => mutability, "stateVariable" => state_variable,
+        "storageLocation" => storage_location
+    }
+    ImportDirective { "file" => file, "absolutePath" => absolute_path }
+    FunctionCall { "kind" => kind, "tryCall" => try_call, "names" => names }
+    FunctionCallOptions { "names" => names }
+    Literal { "kind" => kind, "value" => value }
+    Identifier { "name" => name }
+    IdentifierPath { "name" => name }
+    MemberAccess { "memberName" => member_name }
+    BinaryOperation { "operator" => operator }
+    UnaryOperation { "operator" => operator, "prefix" => prefix }
+    Assignment { "operator" => operator }
+    TupleExpression { "isInlineArray" => is_inline_array }
+    ElementaryTypeName { "name" => name, "stateMutability" => state_mutability }
+    FunctionTypeName { "visibility" => visibility, "stateMutability" => state_mutability }
+    YulIdentifier { "name" => name }
+    YulFunctionDefinition { "name" => name }
+}
+
+/// Value of the attribute `name` of `node`, `None` if the node type has no such attribute or
+/// it is unset.
+pub(super) fn attribute(node: NodeTypeInternalRef<'_>, name: &str) -> Option<String> {
+    match name {
+        "id" => node.id().attribute(),
+        "src" => node.try_src().attribute(),
+        "referencedDeclaration" => node.reference_id().attribute(),
+        _ => typed_attribute(node, name),
+    }
+}
@@ -0,0 +1,248 @@
+//! Callback traversal over the typed AST.
+//!
+//! [`Visit`] and [`VisitMut`] have a `visit_*` hook, called when the walk enters a node, and a
+//! `leave_*` hook, called once its children are done, for every node type of the `ast_visitor!`
+//! child tables, and generic `visit_node` and `leave_node` hooks around them for any node. The
+//! hooks return a [`VisitControl`] and default to continuing, so an implementation only
+//! overrides the node types it cares about. A walk is started with [`Walk::walk`] or
+//! [`WalkMut::walk_mut`] on any node, and children are walked in the order of the child tables.
+//!
+//! [`Visit`]: super::Visit
+//! [`VisitMut`]: super::VisitMut
+
+use std::ops::ControlFlow;
+
+use super::{Visit, VisitMut};
+
+/// What the walk does after a hook returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VisitControl {
+    /// Walk into the children of the node.
+    #[default]
+    Continue,
+    /// Skip the children of the node. Its `leave_*` hooks still run.
+    SkipChildren,
+    /// End the walk. No further hooks run.
+    Stop,
+}
+
+impl VisitControl {
+    pub(crate) fn flow(self) -> ControlFlow<()> {
+        match self {
+            VisitControl::Stop => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    }
+}
+
+pub trait Walk<'ast> {
+    /// Walks `self` and everything below it, `Break` if a hook returned [`VisitControl::Stop`].
+    fn walk<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) -> ControlFlow<()>;
+}
+
+pub trait WalkMut {
+    /// Walks `self` and everything below it, `Break` if a hook returned [`VisitControl::Stop`].
+    fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> ControlFlow<()>;
+}
+
+macro_rules! ast_walk {
+    (
+        $(
+            $target:ident: [
+                $(
+                    $inner:ident
+                ),*
+            ];
+        )*
+    ) => {
+        paste::paste! {
+            /// Read-only callbacks for [`Walk::walk`].
+            pub trait Visit<'ast> {
+                /// Runs for every node, before its `visit_*` hook.
+                fn visit_node(&mut self, node: NodeTypeInternalRef<'ast>) -> VisitControl {
+                    VisitControl::Continue
+                }
+
+                /// Runs for every node, after its `leave_*` hook.
+                fn leave_node(&mut self, node: NodeTypeInternalRef<'ast>) -> VisitControl {
+                    VisitControl::Continue
+                }
+
+                $(
+                    fn [<visit_ $target:snake>](&mut self, node: &'ast $target) -> VisitControl {
+                        VisitControl::Continue
+                    }
+
+                    fn [<leave_ $target:snake>](&mut self, node: &'ast $target) -> VisitControl {
+                        VisitControl::Continue
+                    }
+                )*
+            }
+
+            /// Callbacks for [`WalkMut::walk_mut`] that may edit nodes in place.
+            pub trait VisitMut {
+                /// Runs for every node, before its `visit_*` hook, with a read-only view of it.
+                fn visit_node(&mut self, node: NodeTypeInternalRef<'_>) -> VisitControl {
+                    VisitControl::Continue
+                }
+
+                /// Runs for every node, after its `leave_*` hook, with a read-only view of it.
+                fn leave_node(&mut self, node: NodeTypeInternalRef<'_>) -> VisitControl {
+                    VisitControl::Continue
+                }
+
+                $(
+                    fn [<visit_ $target:snake>](&mut self, node: &mut $target) -> VisitControl {
+                        VisitControl::Continue
+                    }
+
+                    fn [<leave_ $target:snake>](&mut self, node: &mut $target) -> VisitControl {
+                        VisitControl::Continue
+                    }
+                )*
+            }
+
+            impl<'ast> NodeTypeInternalRef<'ast> {
+                /// Walks the node behind the reference, see [`Walk::walk`].
+                pub fn walk<V: Visit<'ast> + ?Sized>(self, visitor: &mut V) -> std::ops::ControlFlow<()> {
+                    match self {
+                        $(
+                            NodeTypeInternalRef::$target(node) => node.walk(visitor),
+                        )*
+                        // Never reached by a walk from their parents, so only the generic
+                        // hooks run
+                        NodeTypeInternalRef::InheritanceSpecifier(_)
+                        | NodeTypeInternalRef::StructuredDocumentation(_) => {
+                            visitor.visit_node(self).flow()?;
+                            visitor.leave_node(self).flow()
+                        }
+                    }
+                }
+            }
+
+            $(
+                #[allow(unused_mut)]
+                impl<'ast> Walk<'ast> for $target {
+                    fn walk<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) -> std::ops::ControlFlow<()> {
+                        let node = NodeTypeInternalRef::$target(self);
+                        let generic = visitor.visit_node(node);
+                        generic.flow()?;
+                        let typed = visitor.[<visit_ $target:snake>](self);
+                        typed.flow()?;
+                        if generic == VisitControl::Continue && typed == VisitControl::Continue {
+                            $(
+                                self.$inner.walk(visitor)?;
+                            )*
+                        }
+                        visitor.[<leave_ $target:snake>](self).flow()?;
+                        visitor.leave_node(node).flow()
+                    }
+                }
+
+                impl WalkMut for $target {
+                    fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> std::ops::ControlFlow<()> {
+                        let generic = visitor.visit_node(NodeTypeInternalRef::$target(self));
+                        generic.flow()?;
+                        let typed = visitor.[<visit_ $target:snake>](self);
+                        typed.flow()?;
+                        if generic == VisitControl::Continue && typed == VisitControl::Continue {
+                            $(
+                                self.$inner.walk_mut(visitor)?;
+                            )*
+                        }
+                        visitor.[<leave_ $target:snake>](self).flow()?;
+                        visitor.leave_node(NodeTypeInternalRef::$target(self)).flow()
+                    }
+                }
+            )*
+        }
+    };
+    (
+        $(
+            $target:ident: (
+                $(
+                    $variant:ident
+                ),*
+            );
+        )*
+    ) => {
+        $(
+            impl<'ast> Walk<'ast> for $target {
+                fn walk<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) -> std::ops::ControlFlow<()> {
+                    match self {
+                        $(
+                            $target::$variant(i) => i.walk(visitor),
+                        )*
+                    }
+                }
+            }
+
+            impl WalkMut for $target {
+                fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> std::ops::ControlFlow<()> {
+                    match self {
+                        $(
+                            $target::$variant(i) => i.walk_mut(visitor),
+                        )*
+                    }
+                }
+            }
+        )*
+    };
+    (!!!placeholder!!! $target: ident) => {
+        impl<'ast> Walk<'ast> for $target {
+            fn walk<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) -> std::ops::ControlFlow<()> {
+                std::ops::ControlFlow::Continue(())
+            }
+        }
+
+        impl WalkMut for $target {
+            fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> std::ops::ControlFlow<()> {
+                std::ops::ControlFlow::Continue(())
+            }
+        }
+    };
+}
+
+pub(super) use ast_walk;
+
+impl<'ast, T: Walk<'ast>> Walk<'ast> for Option<T> {
+    fn walk<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) -> ControlFlow<()> {
+        match self {
+            Some(t) => t.walk(visitor),
+            None => ControlFlow::Continue(()),
+        }
+    }
+}
+
+impl<'ast, T: Walk<'ast>> Walk<'ast> for Vec<T> {
+    fn walk<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) -> ControlFlow<()> {
+        self.iter().try_for_each(|node| node.walk(visitor))
+    }
+}
+
+impl<'ast, T: Walk<'ast>> Walk<'ast> for Box<T> {
+    fn walk<V: Visit<'ast> + ?Sized>(&'ast self, visitor: &mut V) -> ControlFlow<()> {
+        self.as_ref().walk(visitor)
+    }
+}
+
+impl<T: WalkMut> WalkMut for Option<T> {
+    fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> ControlFlow<()> {
+        match self {
+            Some(t) => t.walk_mut(visitor),
+            None => ControlFlow::Continue(()),
+        }
+    }
+}
+
+impl<T: WalkMut> WalkMut for Vec<T> {
+    fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> ControlFlow<()> {
+        self.iter_mut().try_for_each(|node| node.walk_mut(visitor))
+    }
+}
+
+impl<T: WalkMut> WalkMut for Box<T> {
+    fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) -> ControlFlow<()> {
+        self.as_mut().walk_mut(visitor)
+    }
+}
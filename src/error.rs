@@ -1,13 +1,51 @@
+use std::fmt;
+
+use crate::ast_framework::SolcDiagnostic;
+
 pub type AstParserResult<T> = std::result::Result<T, AstParserError>;
 
 #[derive(Debug, Clone)]
-pub struct AstParserError(ErrorKind);
+pub struct AstParserError(Box<ErrorKind>);
 
 #[derive(Debug, Clone)]
 pub enum ErrorKind {
     JsonParsingError {
+        /// Rust type that rejected the node.
         struct_name: Option<String>,
         inner: Option<String>,
+        /// RFC 6901 pointer to the rejected value inside the loaded document.
+        pointer: Option<String>,
+        node_type: Option<String>,
+        id: Option<isize>,
+        src: Option<String>,
+        file: Option<String>,
+    },
+    Io {
+        kind: std::io::ErrorKind,
+        inner: String,
+    },
+    /// solc exited without producing any output.
+    Solc {
+        status: Option<i32>,
+        stderr: String,
+    },
+    /// solc reported at least one error; holds every diagnostic it emitted.
+    Compilation(Vec<SolcDiagnostic>),
+    /// `src` that is not `start:length:fileIndex`.
+    InvalidSrc(String),
+    /// Source rejected by [`crate::ast_parse`]; `line` and `column` are 1-based.
+    SyntaxError {
+        message: String,
+        file: Option<String>,
+        offset: usize,
+        line: usize,
+        column: usize,
+    },
+    /// Query rejected by [`crate::ast_visitor::Selector::parse`]; `offset` is a byte offset.
+    InvalidSelector {
+        selector: String,
+        offset: usize,
+        message: String,
     },
     NodeTypeInternalCast, /* {
                               expr: String,
@@ -17,6 +55,125 @@ pub enum ErrorKind {
 
 impl AstParserError {
     pub fn result_node_type_internal_cast<T>() -> AstParserResult<T> {
-        Err(AstParserError(ErrorKind::NodeTypeInternalCast))
+        Err(ErrorKind::NodeTypeInternalCast.into())
+    }
+
+    pub fn json_parsing<S: Into<String>, I: ToString>(struct_name: S, inner: I) -> Self {
+        ErrorKind::JsonParsingError {
+            struct_name: Some(struct_name.into()),
+            inner: Some(inner.to_string()),
+            pointer: None,
+            node_type: None,
+            id: None,
+            src: None,
+            file: None,
+        }
+        .into()
+    }
+
+    /// Records the file the failing document was read from.
+    pub fn with_file<P: AsRef<std::path::Path>>(mut self, path: P) -> Self {
+        if let ErrorKind::JsonParsingError { file, .. } = self.0.as_mut() {
+            *file = Some(path.as_ref().display().to_string());
+        }
+        self
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.0
+    }
+}
+
+impl From<ErrorKind> for AstParserError {
+    fn from(value: ErrorKind) -> Self {
+        AstParserError(Box::new(value))
     }
 }
+
+impl From<std::io::Error> for AstParserError {
+    fn from(value: std::io::Error) -> Self {
+        ErrorKind::Io {
+            kind: value.kind(),
+            inner: value.to_string(),
+        }
+        .into()
+    }
+}
+
+impl fmt::Display for AstParserError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.as_ref() {
+            ErrorKind::JsonParsingError {
+                struct_name,
+                inner,
+                pointer,
+                node_type,
+                id,
+                src,
+                file,
+            } => {
+                write!(f, "failed to parse")?;
+                if let Some(struct_name) = struct_name {
+                    write!(f, " `{struct_name}`")?;
+                }
+                if let Some(file) = file {
+                    write!(f, " in {file}")?;
+                }
+                if let Some(pointer) = pointer {
+                    write!(f, " at `{pointer}`")?;
+                }
+                if let Some(node_type) = node_type {
+                    write!(f, " (nodeType: {node_type}")?;
+                    if let Some(id) = id {
+                        write!(f, ", id: {id}")?;
+                    }
+                    if let Some(src) = src {
+                        write!(f, ", src: {src}")?;
+                    }
+                    write!(f, ")")?;
+                }
+                if let Some(inner) = inner {
+                    write!(f, ": {inner}")?;
+                }
+                Ok(())
+            }
+            ErrorKind::Io { inner, .. } => write!(f, "io error: {inner}"),
+            ErrorKind::Solc { status, stderr } => {
+                write!(f, "solc failed")?;
+                if let Some(status) = status {
+                    write!(f, " with exit code {status}")?;
+                }
+                write!(f, ": {}", stderr.trim_end())
+            }
+            ErrorKind::Compilation(diagnostics) => {
+                write!(f, "compilation failed")?;
+                for diagnostic in diagnostics.iter().filter(|d| d.is_error()) {
+                    write!(f, "\n{diagnostic}")?;
+                }
+                Ok(())
+            }
+            ErrorKind::InvalidSrc(src) => write!(f, "invalid src `{src}`"),
+            ErrorKind::SyntaxError {
+                message,
+                file,
+                line,
+                column,
+                ..
+            } => {
+                write!(f, "syntax error")?;
+                if let Some(file) = file {
+                    write!(f, " in {file}")?;
+                }
+                write!(f, " at {line}:{column}: {message}")
+            }
+            ErrorKind::InvalidSelector {
+                selector,
+                offset,
+                message,
+            } => write!(f, "invalid selector `{selector}` at {offset}: {message}"),
+            ErrorKind::NodeTypeInternalCast => write!(f, "node type mismatch on cast"),
+        }
+    }
+}
+
+impl std::error::Error for AstParserError {}
@@ -2,7 +2,10 @@
 #![allow(dead_code, unused_variables)]
 #![allow(clippy::too_many_arguments)]
 pub mod ast_framework;
+pub mod ast_normalize;
+pub mod ast_parse;
 pub mod ast_serialize;
+pub mod ast_source;
 pub mod ast_specs;
 pub mod ast_visitor;
 pub mod error;
@@ -11,11 +14,8 @@ pub mod utils;
 pub mod zero_cost;
 // pub mod ast_descriptor; TODO:
 
-use std::fmt::Debug;
-
 use ast_specs::SourceUnit;
 pub use error::*;
-use serde::Deserialize;
 
 #[macro_export]
 macro_rules! unwrap_node_type {
@@ -233,16 +233,10 @@ macro_rules! cast_node_type {
 //     let x = cast_node_type!(x; ContractDefinition; name, to_owned);
 // }
 
-#[derive(Debug, Deserialize)]
-struct FoundryWrapper {
-    ast: SourceUnit,
-}
-
-pub fn cast_to_source_unit<R>(path: R) -> SourceUnit
+/// The `ast` of a Foundry artifact, recording the compiler version of its `metadata`.
+pub fn cast_to_source_unit<R>(path: R) -> AstParserResult<SourceUnit>
 where
     R: std::io::Read,
 {
-    serde_json::from_reader::<_, FoundryWrapper>(path)
-        .unwrap()
-        .ast
+    ast_framework::FoundryOutput::from_reader(path).map(|output| output.ast)
 }
@@ -0,0 +1,263 @@
+//! Deserialization entry point shared by every loader.
+//!
+//! The fast path is a plain `serde_json` parse. Only when it fails is the
+//! document re-read as a `serde_json::Value` and walked top-down to find the
+//! deepest node whose own struct rejects it.
+
+use std::{fs::File, io::Read, path::Path};
+
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use crate::{
+    AstParserError, AstParserResult, ErrorKind,
+    ast_specs::{
+        inline_assembly::{
+            InlineAssembly,
+            yul_expression::{
+                yul_function_call::YulFunctionCall, yul_identifier::YulIdentifier,
+                yul_literal::YulLiteral,
+            },
+            yul_statements::{
+                yul_assignment::YulAssignment,
+                yul_block::YulBlock,
+                yul_break::YulBreak,
+                yul_continue::YulContinue,
+                yul_expression_statement::YulExpressionStatement,
+                yul_for_loop::YulForLoop,
+                yul_function_definition::YulFunctionDefinition,
+                yul_if::YulIf,
+                yul_leave::YulLeave,
+                yul_switch::{YulCase, YulSwitch},
+                yul_variable_declaration::YulVariableDeclaration,
+            },
+            yul_typed_name::YulTypedName,
+        },
+        is_lenient, *,
+    },
+};
+
+pub(crate) fn from_path<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> AstParserResult<T> {
+    let path = path.as_ref();
+    from_reader(File::open(path)?).map_err(|err| err.with_file(path))
+}
+
+pub(crate) fn from_reader<T: DeserializeOwned, R: Read>(mut reader: R) -> AstParserResult<T> {
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes)?;
+    from_slice(&bytes)
+}
+
+pub(crate) fn from_slice<T: DeserializeOwned>(bytes: &[u8]) -> AstParserResult<T> {
+    serde_json::from_slice(bytes).map_err(|err| locate(bytes, short_type_name::<T>(), err))
+}
+
+pub(crate) fn from_value<T: DeserializeOwned>(value: &Value) -> AstParserResult<T> {
+    T::deserialize(value).map_err(|err| {
+        let mut pointer = String::new();
+        find_rejected(value, &mut pointer, short_type_name::<T>())
+            .unwrap_or_else(|| AstParserError::json_parsing(short_type_name::<T>(), err))
+    })
+}
+
+/// Like [`from_path`], retrying through `normalize` when the document is rejected as is.
+pub(crate) fn from_path_normalized<T: DeserializeOwned, P: AsRef<Path>>(
+    path: P,
+    normalize: fn(&mut Value),
+) -> AstParserResult<T> {
+    let path = path.as_ref();
+    from_reader_normalized(File::open(path)?, normalize).map_err(|err| err.with_file(path))
+}
+
+pub(crate) fn from_reader_normalized<T: DeserializeOwned, R: Read>(
+    mut reader: R,
+    normalize: fn(&mut Value),
+) -> AstParserResult<T> {
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes)?;
+    from_slice_normalized(&bytes, normalize)
+}
+
+/// Current compilers take the plain path; the rejected location is reported
+/// against the normalized document.
+pub(crate) fn from_slice_normalized<T: DeserializeOwned>(
+    bytes: &[u8],
+    normalize: fn(&mut Value),
+) -> AstParserResult<T> {
+    serde_json::from_slice(bytes).or_else(|err| {
+        let Ok(mut value) = serde_json::from_slice::<Value>(bytes) else {
+            return Err(AstParserError::json_parsing(short_type_name::<T>(), err));
+        };
+        normalize(&mut value);
+        from_value(&value)
+    })
+}
+
+fn locate(bytes: &[u8], struct_name: &str, err: serde_json::Error) -> AstParserError {
+    // Syntax errors can't be attributed to a node
+    let Ok(value) = serde_json::from_slice::<Value>(bytes) else {
+        return AstParserError::json_parsing(struct_name, err);
+    };
+
+    let mut pointer = String::new();
+    find_rejected(&value, &mut pointer, struct_name)
+        .unwrap_or_else(|| AstParserError::json_parsing(struct_name, err))
+}
+
+fn find_rejected(value: &Value, pointer: &mut String, parent: &str) -> Option<AstParserError> {
+    let children: Box<dyn Iterator<Item = (String, &Value)>> = match value {
+        Value::Object(map) => Box::new(map.iter().map(|(key, child)| (escape(key), child))),
+        Value::Array(items) => Box::new(items.iter().enumerate().map(|(i, c)| (i.to_string(), c))),
+        _ => return None,
+    };
+
+    let node_type = value.get("nodeType").and_then(Value::as_str);
+    let check = node_type.map(|node_type| check_node(node_type, value));
+
+    match check {
+        Some(Some(Ok(()))) => return None,
+        // Accepted as `Unknown`, failures are elsewhere
+        Some(None) if is_lenient() => return None,
+        _ => {}
+    }
+
+    let struct_name = match (node_type, &check) {
+        (Some(node_type), Some(Some(_))) => node_type,
+        _ => parent,
+    };
+
+    for (key, child) in children {
+        let len = pointer.len();
+        pointer.push('/');
+        pointer.push_str(&key);
+        let rejected = find_rejected(child, pointer, struct_name);
+        pointer.truncate(len);
+
+        if rejected.is_some() {
+            return rejected;
+        }
+    }
+
+    let node_type = node_type?;
+    let inner = match check {
+        Some(Some(Err(err))) => err,
+        _ => format!("unknown nodeType `{node_type}`"),
+    };
+
+    Some(AstParserError::from(ErrorKind::JsonParsingError {
+        struct_name: Some(struct_name.to_owned()),
+        inner: Some(inner),
+        pointer: Some(pointer.clone()),
+        node_type: Some(node_type.to_owned()),
+        id: value
+            .get("id")
+            .and_then(Value::as_i64)
+            .map(|id| id as isize),
+        src: value.get("src").and_then(Value::as_str).map(str::to_owned),
+        file: None,
+    }))
+}
+
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+macro_rules! node_checks {
+    ($($node:ident),* $(,)?) => {
+        /// `None` if `node_type` is not known to this crate.
+        fn check_node(node_type: &str, value: &Value) -> Option<Result<(), String>> {
+            match node_type {
+                $(
+                    stringify!($node) => Some(
+                        $node::deserialize(value)
+                            .map(|_| ())
+                            .map_err(|err| err.to_string()),
+                    ),
+                )*
+                _ => None,
+            }
+        }
+    };
+}
+
+node_checks! {
+    ArrayTypeName,
+    Assignment,
+    BinaryOperation,
+    Block,
+    Break,
+    Conditional,
+    Continue,
+    ContractDefinition,
+    DoWhileStatement,
+    ElementaryTypeName,
+    ElementaryTypeNameExpression,
+    EmitStatement,
+    EnumDefinition,
+    EnumValue,
+    ErrorDefinition,
+    EventDefinition,
+    ExpressionStatement,
+    ForStatement,
+    FunctionCall,
+    FunctionCallOptions,
+    FunctionDefinition,
+    FunctionTypeName,
+    Identifier,
+    IdentifierPath,
+    IfStatement,
+    ImportDirective,
+    IndexAccess,
+    IndexRangeAccess,
+    InheritanceSpecifier,
+    InlineAssembly,
+    Literal,
+    Mapping,
+    MemberAccess,
+    ModifierDefinition,
+    ModifierInvocation,
+    NewExpression,
+    OverrideSpecifier,
+    ParameterList,
+    PlaceholderStatement,
+    PragmaDirective,
+    Return,
+    RevertStatement,
+    SourceUnit,
+    StructDefinition,
+    StructuredDocumentation,
+    Throw,
+    TryCatchClause,
+    TryStatement,
+    TupleExpression,
+    UnaryOperation,
+    UncheckedBlock,
+    UserDefinedTypeName,
+    UserDefinedValueTypeDefinition,
+    UsingForDirective,
+    VariableDeclaration,
+    VariableDeclarationStatement,
+    WhileStatement,
+    YulAssignment,
+    YulBlock,
+    YulBreak,
+    YulCase,
+    YulContinue,
+    YulExpressionStatement,
+    YulForLoop,
+    YulFunctionCall,
+    YulFunctionDefinition,
+    YulIdentifier,
+    YulIf,
+    YulLeave,
+    YulLiteral,
+    YulSwitch,
+    YulTypedName,
+    YulVariableDeclaration,
+}
@@ -1,2 +1,3 @@
 pub mod convert_enums;
+pub(crate) mod json_locator;
 pub mod ref_enums;
@@ -6,6 +6,7 @@ macro_rules! enum_refs {
         $(#[$additional:meta])?
         $v:vis enum $name:ident {
             $(
+                $(#[$variant_attr:meta])*
                 $variant:ident($variant_type:ty),
             )*
         }
@@ -14,6 +15,7 @@ macro_rules! enum_refs {
         $(#[$additional])?
         $v enum $name {
             $(
+                $(#[$variant_attr])*
                 $variant($variant_type),
             )*
         }
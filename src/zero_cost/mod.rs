@@ -15,7 +15,10 @@ pub use simd_json::base::{
     ValueAsArray, ValueAsMutArray, ValueAsMutObject, ValueAsObject, ValueAsScalar,
 };
 
-use crate::zero_cost::types::{abstraction::ZcSourceUnit, wrappers::FromBorrowedValue};
+use crate::{
+    AstParserError, AstParserResult, ErrorKind,
+    zero_cost::types::{abstraction::ZcSourceUnit, wrappers::FromBorrowedValue},
+};
 
 pub trait BorrowedValueVisitor<'a> {
     fn filter_by_id(&'a self, id: isize) -> Option<&'a BorrowedValue<'a>>;
@@ -490,24 +493,25 @@ pub struct SourceUnitBuilder<'a> {
 }
 
 impl SourceUnitBuilder<'static> {
-    pub fn new<P>(path: P) -> Self
+    pub fn new<P>(path: P) -> AstParserResult<Self>
     where
         P: AsRef<Path>,
     {
-        let file = File::options().read(true).open(path).unwrap();
-        let mmap = unsafe { MmapOptions::new().map_copy(&file).unwrap() };
+        let path = path.as_ref();
+        let file = File::options().read(true).open(path)?;
+        let mmap = unsafe { MmapOptions::new().map_copy(&file)? };
 
         let mut this = Self {
             map: mmap,
             root: None,
         };
 
-        this.get_root();
+        this.get_root().map_err(|err| err.with_file(path))?;
 
-        this
+        Ok(this)
     }
 
-    pub fn get_root(&mut self) {
+    pub fn get_root(&mut self) -> AstParserResult<()> {
         let slice: &mut [u8] = &mut self.map;
 
         // SAFETY: BorrowedValue only borrows from map, which lives as long as self.
@@ -515,35 +519,35 @@ impl SourceUnitBuilder<'static> {
             Ok(root) => unsafe {
                 std::mem::transmute::<BorrowedValue<'_>, BorrowedValue<'static>>(root)
             },
-            Err(err) => {
-                unimplemented!("Unhandled error occurs: {}", err)
-            }
+            Err(err) => return Err(AstParserError::json_parsing("SourceUnit", err)),
         };
 
         self.root = Some(root);
+
+        Ok(())
     }
 
-    pub fn source_unit<const N: usize>(&'_ self, key: Option<[&str; N]>) -> ZcSourceUnit<'_> {
-        ZcSourceUnit {
+    pub fn source_unit<const N: usize>(
+        &'_ self,
+        key: Option<[&str; N]>,
+    ) -> AstParserResult<ZcSourceUnit<'_>> {
+        let root = self.root.as_ref().expect("get_root() must be called first");
+
+        Ok(ZcSourceUnit {
             inner: match key {
-                Some(key) => self
-                    .root
-                    .as_ref()
-                    .expect("get_root() must be called first")
-                    .get_chain(key)
-                    .unwrap(),
-                None => self.root.as_ref().unwrap(),
+                Some(key) => root.get_chain(key).ok_or_else(|| missing_key(&key))?,
+                None => root,
             },
-        }
+        })
     }
 
     /// Technically safe, since file always persist in the system if not modified, which is UBs
     pub fn source_unit_const<const N: usize>(
         &self,
         key: Option<[&str; N]>,
-    ) -> ZcSourceUnit<'static> {
-        let su = self.source_unit(key);
-        unsafe { std::mem::transmute(su) }
+    ) -> AstParserResult<ZcSourceUnit<'static>> {
+        let su = self.source_unit(key)?;
+        Ok(unsafe { std::mem::transmute::<ZcSourceUnit<'_>, ZcSourceUnit<'static>>(su) })
     }
 
     /// # Safety:
@@ -570,17 +574,17 @@ pub struct NodeBuilder {
 }
 
 impl NodeBuilder {
-    pub fn new<P>(path: P, start: u32, length: u32) -> Self
+    pub fn new<P>(path: P, start: u32, length: u32) -> AstParserResult<Self>
     where
         P: AsRef<Path>,
     {
-        let file = File::options().read(true).open(path).unwrap();
+        let path = path.as_ref();
+        let file = File::options().read(true).open(path)?;
         let mmap = unsafe {
             MmapOptions::new()
                 .offset(start as u64)
                 .len(length as usize)
-                .map_copy(&file)
-                .unwrap()
+                .map_copy(&file)?
         };
 
         let mut this = Self {
@@ -588,22 +592,25 @@ impl NodeBuilder {
             node: None,
         };
 
-        this.get_node();
+        this.get_node().map_err(|err| err.with_file(path))?;
 
-        this
+        Ok(this)
     }
 
-    pub fn get_node(&mut self) {
+    pub fn get_node(&mut self) -> AstParserResult<()> {
         let slice: &mut [u8] = &mut self.map;
 
         // SAFETY: BorrowedValue only borrows from map, which lives as long as self.
         let root: BorrowedValue<'static> = unsafe {
             std::mem::transmute::<BorrowedValue<'_>, BorrowedValue<'static>>(
-                to_borrowed_value(slice).expect("invalid JSON"),
+                to_borrowed_value(slice)
+                    .map_err(|err| AstParserError::json_parsing("BorrowedValue", err))?,
             )
         };
 
         self.node = Some(root);
+
+        Ok(())
     }
 
     pub fn strong_node<T>(&self) -> T
@@ -615,3 +622,21 @@ impl NodeBuilder {
         T::from_borrowed_value(node)
     }
 }
+
+fn missing_key(chain: &[&str]) -> AstParserError {
+    let pointer = chain
+        .iter()
+        .map(|key| format!("/{}", key.replace('~', "~0").replace('/', "~1")))
+        .collect();
+
+    ErrorKind::JsonParsingError {
+        struct_name: Some("SourceUnit".to_owned()),
+        inner: Some("missing key".to_owned()),
+        pointer: Some(pointer),
+        node_type: None,
+        id: None,
+        src: None,
+        file: None,
+    }
+    .into()
+}
@@ -0,0 +1,129 @@
+//! Borrowed queries over a compact solc AST.
+
+use ast_parser::{
+    ast_framework::Workspace,
+    ast_specs::{NodeType, NodeTypeInternalRef, SourceUnit},
+    ast_visitor::{AstRefVisitor, AstVisitor},
+};
+
+/// solc 0.8 compact AST of `fixtures/C.sol`.
+const AST: &str = include_str!("fixtures/C.ast.json");
+
+fn unit() -> SourceUnit {
+    serde_json::from_str(AST).expect("The fixture is a valid compact AST")
+}
+
+fn name(node: NodeTypeInternalRef<'_>) -> String {
+    match node {
+        NodeTypeInternalRef::ContractDefinition(contract) => contract.name().to_string(),
+        NodeTypeInternalRef::FunctionDefinition(function) => function.name().to_string(),
+        NodeTypeInternalRef::VariableDeclaration(variable) => variable.name().to_string(),
+        NodeTypeInternalRef::Identifier(identifier) => identifier.name().to_string(),
+        other => panic!("no name on {:?}", NodeType::from(other)),
+    }
+}
+
+#[test]
+fn refs_by_node_type_in_source_order() {
+    let unit = unit();
+    let variables = unit.refs_by_node_type(NodeType::VariableDeclaration);
+    let names: Vec<_> = variables.into_iter().map(name).collect();
+    assert_eq!(names, ["x", "a", ""]);
+
+    let definitions =
+        unit.refs_by_node_types([NodeType::ContractDefinition, NodeType::FunctionDefinition]);
+    let names: Vec<_> = definitions.into_iter().map(name).collect();
+    assert_eq!(names, ["C", "f"]);
+}
+
+#[test]
+fn ref_by_id_finds_nested_nodes() {
+    let unit = unit();
+    let function = unit.ref_by_id(13).expect("f is in the fixture");
+    assert_eq!(NodeType::from(function), NodeType::FunctionDefinition);
+    assert_eq!(name(function), "f");
+    assert_eq!(function.try_src(), Some("90:83:0"));
+
+    assert!(unit.ref_by_id(30).is_some_and(|node| node.is_source_unit()));
+    assert!(unit.ref_by_id(1_000).is_none());
+}
+
+#[test]
+fn refs_by_reference_id_finds_uses() {
+    let unit = unit();
+    let uses = unit.refs_by_reference_id(3);
+    assert_eq!(uses.len(), 1);
+    assert_eq!(uses[0].id(), Some(9));
+    assert_eq!(name(uses[0]), "x");
+}
+
+#[test]
+fn parent_ref_steps_up_one_node() {
+    let unit = unit();
+    let x = unit.ref_by_id(9).expect("the use of x is in the fixture");
+    let sum = unit.parent_ref(x).expect("x is an operand");
+    assert_eq!(NodeType::from(sum), NodeType::BinaryOperation);
+    let statement = unit.parent_ref(sum).expect("the sum is returned");
+    assert_eq!(NodeType::from(statement), NodeType::Return);
+
+    let contract = unit.ref_by_id(20).expect("C is in the fixture");
+    assert_eq!(
+        unit.parent_ref(contract).and_then(|node| node.id()),
+        Some(30)
+    );
+    let root = unit.ref_by_id(30).expect("the unit itself");
+    assert!(unit.parent_ref(root).is_none());
+}
+
+#[test]
+fn ancestor_ref_skips_to_the_node_type() {
+    let unit = unit();
+    let x = unit.ref_by_id(9).expect("the use of x is in the fixture");
+    let function = unit
+        .ancestor_ref(x, NodeType::FunctionDefinition)
+        .expect("x is used in f");
+    assert_eq!(function.id(), Some(13));
+}
+
+#[test]
+fn legacy_queries_match_the_borrowed_ones() {
+    let unit = unit();
+    assert_eq!(
+        unit.filter_ref_by_node_type(NodeType::Identifier),
+        unit.refs_by_node_type(NodeType::Identifier)
+    );
+    assert_eq!(
+        unit.filter_ref_by_reference_id(3),
+        unit.refs_by_reference_id(3)
+    );
+    assert_eq!(unit.filter_ref_by_id(5), unit.ref_by_id(5));
+}
+
+#[test]
+fn workspace_queries_borrow_from_the_workspace() {
+    let workspace: Workspace = [unit()].into_iter().collect();
+    let function = workspace.ref_by_id(13).expect("f is in the workspace");
+    assert_eq!(name(function), "f");
+    assert_eq!(workspace.filter_ref_by_id(13), Some(function));
+    assert_eq!(
+        workspace.filter_ref_by_node_type(NodeType::VariableDeclaration),
+        workspace.refs_by_node_type(NodeType::VariableDeclaration)
+    );
+    assert_eq!(workspace.filter_ref_by_reference_id(3).len(), 1);
+}
+
+#[test]
+fn parents_of_equal_nodes_are_their_own() {
+    let units = vec![unit(), unit()];
+    let function = units[1].ref_by_id(13).expect("f is in the unit");
+    let contract = units[1].ref_by_id(20).expect("C is in the unit");
+    assert_eq!(units[0].ref_by_id(13), Some(function));
+
+    let parent = units.parent_ref(function).expect("f is in a contract");
+    assert!(parent.ptr_eq(contract));
+    assert!(!parent.ptr_eq(units[0].ref_by_id(20).expect("C is in the unit")));
+    let root = units
+        .ancestor_ref(function, NodeType::SourceUnit)
+        .expect("f is in a unit");
+    assert!(root.ptr_eq(NodeTypeInternalRef::SourceUnit(&units[1])));
+}
@@ -0,0 +1,79 @@
+//! Printing with the comments of the parsed source.
+
+use ast_parser::{
+    ast_parse::{comments, parse},
+    ast_serialize::{AstSerializer, PrinterConfig},
+};
+
+const SOURCE: &str = include_str!("fixtures/Comments.sol");
+
+fn print(source: &str) -> String {
+    parse(source)
+        .expect("The source parses")
+        .to_sol_string_with_comments(&PrinterConfig::default(), source)
+        .expect("The comments are lexed")
+}
+
+#[test]
+fn printing_with_comments_is_a_fixpoint() {
+    let printed = print(SOURCE);
+    assert_eq!(print(&printed), printed);
+}
+
+#[test]
+fn every_comment_is_printed_once_in_order() {
+    let printed = print(SOURCE);
+    let texts = |source: &str| -> Vec<String> {
+        comments(source)
+            .unwrap()
+            .iter()
+            .map(|comment| comment.text(source).to_owned())
+            .collect()
+    };
+    assert_eq!(texts(&printed), texts(SOURCE));
+}
+
+#[test]
+fn comments_stay_next_to_their_code() {
+    let printed = print(SOURCE);
+    for line in [
+        "pragma solidity ^0.8.20; // compiler range",
+        "mapping(address => uint256) private balances; // by account",
+        "event Deposited(address indexed account /* payer */, uint256 amount);",
+        "Open, // accepting deposits",
+        "uint256 amount; // in wei",
+        "/* when */ uint256 at;",
+        "function deposit(uint256 amount /* in wei */, address to) public returns (bool) {",
+        "/* before the check */ require(amount > 0, \"empty\");",
+        "balances[to] += amount; // credit",
+        "return true; // done",
+        "uint256 amount, // what to take",
+        "address to // where to send it",
+        "/* skip three */ continue;",
+        "/* pay */ return;",
+        "value := sload(slot) // raw",
+    ] {
+        assert!(
+            printed.lines().any(|printed| printed.trim() == line),
+            "{line:?} not printed as is in:\n{printed}"
+        );
+    }
+}
+
+#[test]
+fn comments_on_their_own_line_stay_there() {
+    let printed = print(SOURCE);
+    for comment in [
+        "// Who deposited what",
+        "// announce it",
+        "// nothing else",
+        "// nothing to do yet",
+        "// read the slot",
+        "// end of file",
+    ] {
+        assert!(
+            printed.lines().any(|line| line.trim() == comment),
+            "{comment:?} not on a line of its own in:\n{printed}"
+        );
+    }
+}
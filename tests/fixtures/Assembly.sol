@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+contract Assembly {
+    function safe(uint256 slot) external view returns (uint256 value) {
+        assembly ("memory-safe") {
+            value := sload(slot)
+        }
+    }
+
+    function flow(uint256 n) external pure returns (uint256 total) {
+        assembly {
+            function double(x) -> y {
+                y := mul(x, 2)
+            }
+            for { let i := 0 } lt(i, n) { i := add(i, 1) } {
+                switch mod(i, 3)
+                case 0 {
+                    continue
+                }
+                case 1 {
+                    total := add(total, double(i))
+                }
+                default {
+                    if gt(total, 100) {
+                        break
+                    }
+                    total := add(total, i)
+                }
+            }
+        }
+    }
+
+    function literals() external pure returns (bytes32 a, uint256 b, bool c) {
+        assembly {
+            a := hex"deadbeef"
+            b := 0xff
+            c := true
+            let s := "text"
+            pop(s)
+        }
+    }
+}
@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract C {
+    uint256 x;
+
+    function f(uint256 a) external view returns (uint256) {
+        return a + x;
+    }
+}
@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20; // compiler range
+
+/* Imports go first */
+import {IERC20} from "./IERC20.sol";
+
+/// @title Commented vault
+/// @notice Keeps deposits
+contract Vault {
+    // Who deposited what
+    mapping(address => uint256) private balances; // by account
+
+    /**
+     * @dev Emitted on every deposit
+     */
+    event Deposited(address indexed account /* payer */, uint256 amount);
+
+    enum State {
+        Open, // accepting deposits
+        Closed
+    }
+
+    struct Entry {
+        uint256 amount; // in wei
+        /* when */ uint256 at;
+    }
+
+    function deposit(uint256 amount /* in wei */, address to) public returns (bool) {
+        /* before the check */ require(amount > 0, "empty");
+        balances[to] += amount; // credit
+        // announce it
+        emit Deposited(to, amount);
+        return true; // done
+    }
+
+    function withdraw(
+        uint256 amount, // what to take
+        address to // where to send it
+    ) public {
+        for (uint256 i = 0; i < amount; i++) {
+            if (i == 3) {
+                /* skip three */ continue;
+            }
+            // nothing else
+        }
+        /* pay */ return;
+    }
+
+    function empty() public {
+        // nothing to do yet
+    }
+
+    function load(uint256 slot) public view returns (uint256 value) {
+        assembly {
+            // read the slot
+            value := sload(slot) // raw
+        }
+    }
+}
+
+// end of file
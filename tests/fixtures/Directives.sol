@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: MIT
+pragma solidity >=0.8.19 <0.9.0;
+pragma abicoder v2;
+pragma experimental ABIEncoderV2;
+
+type Price is uint256;
+
+function add(Price a, Price b) pure returns (Price) {
+    return Price.wrap(Price.unwrap(a) + Price.unwrap(b));
+}
+
+function neg(Price a) pure returns (Price) {
+    return a;
+}
+
+using {add as +, neg} for Price global;
+
+library L {
+    function twice(uint256 a) internal pure returns (uint256) {
+        return a * 2;
+    }
+}
+
+contract B {
+    constructor(uint256 b) {}
+}
+
+contract C is B(1) {
+    using L for *;
+
+    event Logged(uint256 indexed a) anonymous;
+    event Kept(uint256 a);
+}
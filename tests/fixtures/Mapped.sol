@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+contract Mapped {
+    uint256 private total;
+
+    function sum(uint256[] memory values) public returns (uint256) {
+        for (uint256 i = 0; i < values.length; i++) {
+            if (values[i] == 0) {
+                continue;
+            }
+            total += values[i];
+        }
+        return total;
+    }
+
+    function store(uint256 value) public {
+        assembly {
+            mstore(0x40, value)
+        }
+    }
+}
@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import "./IERC20.sol";
+import {Ownable as Own, Math} from "./Ownable.sol";
+import * as Lib from "./Lib.sol";
+
+uint256 constant MAX = 2**256 - 1;
+
+error Unauthorized(address caller);
+
+type Price is uint128;
+
+/// @title A token
+/// @notice Simple ERC20
+contract Token is Own, IERC20 {
+    using Math for uint256;
+
+    struct Account {
+        uint256 balance;
+        mapping(address spender => uint256) allowances;
+    }
+
+    enum State { Active, Paused }
+
+    event Transfer(address indexed from, address indexed to, uint256 value);
+
+    mapping(address => Account) internal accounts;
+    uint256 public totalSupply;
+    address public immutable deployer;
+    State private state = State.Active;
+    string public constant NAME = "Tok\"en";
+    bytes public constant RAW = hex"00ff";
+    uint[] public list;
+
+    modifier whenActive() {
+        require(state == State.Active, "paused");
+        _;
+    }
+
+    constructor(uint256 supply) Own(msg.sender) {
+        deployer = msg.sender;
+        accounts[msg.sender].balance = supply * 1 ether;
+        totalSupply = supply;
+    }
+
+    /**
+     * @dev Moves tokens.
+     * @param to receiver
+     */
+    function transfer(address to, uint256 amount) external virtual override whenActive returns (bool) {
+        Account storage from = accounts[msg.sender];
+        if (from.balance < amount) {
+            revert Unauthorized(msg.sender);
+        } else if (amount == 0) {
+            return false;
+        }
+        unchecked {
+            from.balance -= amount;
+        }
+        accounts[to].balance += amount;
+        emit Transfer(msg.sender, to, amount);
+        return true;
+    }
+
+    function loops(uint256 n) public pure returns (uint256 total, bool ok) {
+        for (uint256 i = 0; i < n; i++) {
+            if (i % 2 == 0) continue;
+            total += i;
+        }
+        uint256 j;
+        while (j < n) {
+            j++;
+        }
+        do {
+            j--;
+        } while (j > 0);
+        (total, ok) = (total * 2, true);
+        (uint256 a, , bool b) = (1, 2, false);
+        ok = b ? a > 0 : !ok;
+        delete j;
+        total = -int256(total) < 0 ? uint256(int256(-1)) : total >> 2;
+    }
+
+    function calls(address payable target, bytes calldata data) external payable {
+        (bool success, bytes memory ret) = target.call{value: msg.value, gas: 5000}(data);
+        require(success);
+        bytes memory slice = data[4:];
+        uint256[] memory arr = new uint256[](3);
+        arr[0] = abi.decode(ret, (uint256));
+        Token t = new Token{salt: bytes32(0)}(1);
+        payable(address(t)).transfer(1 wei);
+        uint256[3] memory fixedArr = [uint256(1), 2, 3];
+        this.loops({n: 1});
+        try t.transfer(target, 1) returns (bool res) {
+            ok(res);
+        } catch Error(string memory reason) {
+            revert(reason);
+        } catch (bytes memory) {
+            revert();
+        }
+    }
+
+    function ok(bool b) internal {}
+
+    receive() external payable {}
+    fallback(bytes calldata input) external returns (bytes memory) { return input; }
+
+
+}
+
+interface IThing {
+    function thing() external view returns (uint);
+}
+
+function free(uint a) pure returns (uint) { return a + 1; }
+
+library Arith {
+    function apply(function(uint256) pure returns (uint256) op, uint256 a) internal pure returns (uint256) {
+        return op(a);
+    }
+
+    function load(uint256 slot) internal view returns (uint256 value) {
+        assembly ("memory-safe") {
+            value := sload(slot)
+        }
+    }
+}
@@ -0,0 +1,10 @@
+pragma solidity ^0.4.26;
+
+contract C {
+    uint256 x;
+
+    function f(uint256 a) public view returns (uint256) {
+        if (a == 0) throw;
+        return a + x;
+    }
+}
@@ -0,0 +1,10 @@
+pragma solidity ^0.5.0;
+
+contract C {
+    uint256 x;
+
+    function f(uint256 a) public view returns (uint256) {
+        require(a != 0, "zero");
+        return a + x;
+    }
+}
@@ -0,0 +1,86 @@
+//! Rewriting trees with `AstFold`, checked on the printed source.
+
+use ast_parser::{
+    ast_parse::parse,
+    ast_serialize::AstSerializer,
+    ast_specs::{Statement, common::Block, expressions::Identifier},
+    ast_visitor::{AstFold, Fold},
+};
+
+const SOURCE: &str = r#"contract C {
+    uint256 x;
+
+    function f(uint256 a) external view returns (uint256) {
+        if (a > x) return a;
+        return x;
+    }
+}
+"#;
+
+/// Wraps every `return` statement in a block of its own. The bodies of `if` are `Body` nodes,
+/// left to `fold_body`.
+struct BlockReturns;
+
+impl AstFold for BlockReturns {
+    fn fold_statement(&mut self, node: Statement) -> Statement {
+        match node.fold_children(self) {
+            Statement::Return(node) => {
+                Statement::Block(Block::artificial_new(vec![Statement::Return(node)]))
+            }
+            other => other,
+        }
+    }
+}
+
+/// Renames `x` to `y`.
+struct Rename;
+
+impl AstFold for Rename {
+    fn fold_identifier(&mut self, node: Identifier) -> Identifier {
+        if node.name() == "x" {
+            Identifier::artificial_new("y".to_owned())
+        } else {
+            node
+        }
+    }
+}
+
+#[test]
+fn statements_are_replaced_by_another_variant() {
+    let unit = parse(SOURCE).expect("The source parses");
+    let folded = unit.fold_with(&mut BlockReturns);
+    assert_eq!(
+        folded.to_sol_string(),
+        r#"// SPDX-License-Identifier: UNLICENSED
+contract C {
+    uint256 internal x;
+
+    function f(uint256 a) external view returns (uint256) {
+        if (a > x) return a;
+        {
+            return x;
+        }
+    }
+}
+"#
+    );
+}
+
+#[test]
+fn nodes_are_replaced_inside_every_parent() {
+    let unit = parse(SOURCE).expect("The source parses");
+    let printed = unit.fold_with(&mut Rename).to_sol_string();
+    assert!(printed.contains("if (a > y) return a;"));
+    assert!(printed.contains("return y;"));
+    // declarations are no identifiers
+    assert!(printed.contains("uint256 internal x;"));
+}
+
+#[test]
+fn the_default_fold_keeps_the_tree() {
+    struct Keep;
+    impl AstFold for Keep {}
+
+    let unit = parse(SOURCE).expect("The source parses");
+    assert_eq!(unit.clone().fold_with(&mut Keep), unit);
+}
@@ -0,0 +1,85 @@
+//! Loading Hardhat `artifacts/build-info` files.
+
+use ast_parser::{
+    ast_framework::{HardhatBuildInfo, Workspace},
+    ast_source::SourceProvider,
+    ast_visitor::AstRefVisitor,
+};
+
+const BUILD_INFO: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/hardhat/build-info"
+);
+
+/// Build-info of `fixtures/C.sol`, as `contracts/C.sol`, compiled with solc 0.8.24.
+const FILE: &str =
+    include_str!("fixtures/hardhat/build-info/5e0c1b6f2b3d9e8a7c4f1a2b3c4d5e6f.json");
+
+const SOURCE: &str = include_str!("fixtures/C.sol");
+
+const VERSION: &str = "0.8.24+commit.e11b9ed9";
+
+#[test]
+fn build_info_keeps_the_compiler_input_and_output() {
+    let build_info = HardhatBuildInfo::from_reader(FILE.as_bytes()).expect("The fixture is valid");
+    assert_eq!(build_info.id(), "5e0c1b6f2b3d9e8a7c4f1a2b3c4d5e6f");
+    assert_eq!(build_info.format(), "hh-sol-build-info-1");
+    assert_eq!(build_info.solc_version(), "0.8.24");
+    assert_eq!(build_info.solc_long_version(), VERSION);
+
+    assert_eq!(build_info.input().language(), "Solidity");
+    assert_eq!(build_info.input().settings().evm_version(), Some("paris"));
+    assert_eq!(build_info.source_content("contracts/C.sol"), Some(SOURCE));
+    assert_eq!(build_info.source_content("contracts/D.sol"), None);
+
+    assert!(!build_info.output().has_errors());
+    assert_eq!(build_info.output().version(), Some(VERSION));
+    assert!(
+        build_info
+            .output()
+            .contract("contracts/C.sol", "C")
+            .is_some()
+    );
+}
+
+#[test]
+fn source_units_carry_the_compiler_version_and_source() {
+    let build_info = HardhatBuildInfo::from_reader(FILE.as_bytes()).expect("The fixture is valid");
+    let units = build_info.source_units();
+    assert_eq!(
+        units.keys().copied().collect::<Vec<_>>(),
+        ["contracts/C.sol"]
+    );
+    assert_eq!(units["contracts/C.sol"].compiler_version(), Some(VERSION));
+    assert_eq!(units["contracts/C.sol"].source(), None);
+
+    let units = build_info.into_source_units();
+    let unit = &units["contracts/C.sol"];
+    assert_eq!(unit.source(), Some(SOURCE));
+    assert_eq!(unit.compiler_version(), Some(VERSION));
+    let function = unit.ref_by_id(13).expect("f is in the unit");
+    assert_eq!(function.src(), "90:83:0");
+}
+
+#[test]
+fn build_info_directories_load_into_a_workspace() {
+    let build_infos = HardhatBuildInfo::from_dir(BUILD_INFO).expect("The directory is readable");
+    assert_eq!(build_infos.len(), 1);
+
+    let workspace = Workspace::from_hardhat_build_info(BUILD_INFO).expect("The fixture is valid");
+    assert_eq!(workspace.len(), 1);
+    let unit = workspace
+        .unit("contracts/C.sol")
+        .expect("The unit is compiled");
+    assert_eq!(unit.compiler_version(), Some(VERSION));
+    assert_eq!(workspace.source(0), Some(SOURCE));
+    assert_eq!(
+        workspace.unit_of(13).map(|unit| unit.absolute_path()),
+        Some("contracts/C.sol")
+    );
+}
+
+#[test]
+fn missing_directories_are_io_errors() {
+    assert!(HardhatBuildInfo::from_dir(format!("{BUILD_INFO}/missing")).is_err());
+}
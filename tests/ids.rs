@@ -0,0 +1,71 @@
+//! Node ids, and the synthetic ids of nodes without one.
+
+use std::collections::HashSet;
+
+use ast_parser::{
+    ast_parse::parse,
+    ast_specs::{NodeType, NodeTypeInternalRef, SourceUnit},
+    ast_visitor::{AstPath, VisitControl, VisitPath, walk_with_path},
+};
+
+/// Yul blocks, import aliases and most of the syntax of Solidity 0.8.
+const TOKEN: &str = include_str!("fixtures/Token.sol");
+
+/// `(node type, own id, id or synthetic id)` of every node of `unit`, in walk order.
+fn ids(unit: &SourceUnit) -> Vec<(NodeType, Option<isize>, isize)> {
+    struct Collector(Vec<(NodeType, Option<isize>, isize)>);
+
+    impl<'ast> VisitPath<'ast> for Collector {
+        fn visit_node(
+            &mut self,
+            node: NodeTypeInternalRef<'ast>,
+            path: &AstPath<'ast>,
+        ) -> VisitControl {
+            self.0
+                .push((NodeType::from(node), node.id(), path.node_id(node)));
+            VisitControl::Continue
+        }
+    }
+
+    let mut collector = Collector(Vec::new());
+    let _ = walk_with_path(unit, &mut collector);
+    collector.0
+}
+
+#[test]
+fn nodes_without_an_id_have_none() {
+    let unit = parse(TOKEN).expect("The fixture parses");
+    let ids = ids(&unit);
+    let without: Vec<_> = ids
+        .iter()
+        .filter(|(_, id, _)| id.is_none())
+        .map(|(node_type, _, _)| *node_type)
+        .collect();
+    assert!(without.contains(&NodeType::YulBlock));
+    assert!(without.contains(&NodeType::SymbolAliases));
+    assert!(
+        ids.iter()
+            .filter(|(_, id, _)| id.is_some())
+            .all(|(_, id, node_id)| *id == Some(*node_id))
+    );
+}
+
+#[test]
+fn synthetic_ids_are_unique_within_a_unit() {
+    let unit = parse(TOKEN).expect("The fixture parses");
+    let ids = ids(&unit);
+    let unique: HashSet<_> = ids.iter().map(|(_, _, node_id)| node_id).collect();
+    assert_eq!(unique.len(), ids.len());
+    assert!(
+        ids.iter()
+            .filter(|(_, id, _)| id.is_none())
+            .all(|(_, _, node_id)| *node_id < -isize::MAX / 2)
+    );
+}
+
+#[test]
+fn synthetic_ids_are_stable_across_runs() {
+    let first = ids(&parse(TOKEN).expect("The fixture parses"));
+    let second = ids(&parse(TOKEN).expect("The fixture parses"));
+    assert_eq!(first, second);
+}
@@ -0,0 +1,127 @@
+//! Lookups of an index over the units of separate compilations.
+
+use ast_parser::{
+    ast_framework::Workspace,
+    ast_specs::{NodeType, NodeTypeInternalRef, SourceUnit},
+    ast_visitor::AstIndex,
+};
+use serde_json::Value;
+
+/// solc 0.8 compact AST of `fixtures/C.sol`.
+const AST: &str = include_str!("fixtures/C.ast.json");
+
+/// Ids of `f` and the nodes in it.
+const FUNCTION: [i64; 12] = [13, 12, 11, 10, 8, 9, 6, 7, 5, 105, 4, 104];
+
+/// The fixture, as compiled from `path` into a unit with `id`, with `offset` added to the ids
+/// of `f` and everything in it.
+fn unit(path: &str, id: i64, offset: i64) -> SourceUnit {
+    fn shift(value: &mut Value, offset: i64) {
+        match value {
+            Value::Object(object) => {
+                for key in ["id", "referencedDeclaration"] {
+                    if let Some(id) = object.get_mut(key).and_then(|id| id.as_i64())
+                        && FUNCTION.contains(&id)
+                    {
+                        object[key] = (id + offset).into();
+                    }
+                }
+                object.values_mut().for_each(|value| shift(value, offset));
+            }
+            Value::Array(array) => array.iter_mut().for_each(|value| shift(value, offset)),
+            _ => {}
+        }
+    }
+
+    let mut ast: Value = serde_json::from_str(AST).expect("The fixture is valid JSON");
+    shift(&mut ast, offset);
+    ast["absolutePath"] = path.into();
+    ast["id"] = id.into();
+    serde_json::from_value(ast).expect("The fixture is a valid compact AST")
+}
+
+/// `C.sol` and `D.sol` of separate compilations, with the same contract and state variable ids
+/// but `f` renumbered from 1013.
+fn workspace() -> Workspace {
+    [unit("C.sol", 30, 0), unit("D.sol", 31, 1000)]
+        .into_iter()
+        .collect()
+}
+
+fn path_of(index: &AstIndex<'_>, id: isize) -> Option<String> {
+    match index.ancestor(id, NodeType::SourceUnit)? {
+        NodeTypeInternalRef::SourceUnit(unit) => Some(unit.absolute_path().to_owned()),
+        _ => None,
+    }
+}
+
+#[test]
+fn ids_of_one_unit_resolve_to_their_node() {
+    let unit = unit("C.sol", 30, 0);
+    let index = AstIndex::new(&unit);
+    assert_eq!(index.conflicts().count(), 0);
+    assert!(matches!(
+        index.node(13),
+        Some(NodeTypeInternalRef::FunctionDefinition(_))
+    ));
+    assert_eq!(index.parent(12).and_then(|node| node.id()), Some(13));
+    assert_eq!(
+        index
+            .ancestor(8, NodeType::ContractDefinition)
+            .and_then(|node| node.id()),
+        Some(20)
+    );
+    assert_eq!(index.enclosing_contract(13).map(|c| c.id()), Some(20));
+    assert_eq!(index.enclosing_function(8).map(|f| f.id()), Some(13));
+    let references: Vec<_> = index.references(3).iter().map(|node| node.id()).collect();
+    assert_eq!(references, [Some(9)]);
+}
+
+#[test]
+fn ids_shared_across_compilations_resolve_to_no_node() {
+    let workspace = workspace();
+    let index = AstIndex::new(&workspace);
+
+    let conflicts: Vec<_> = index.conflicts().collect();
+    assert_eq!(conflicts, [1, 3, 20, 103]);
+    for id in conflicts {
+        assert!(index.node(id).is_none());
+        assert!(index.parent(id).is_none());
+        assert!(index.ancestor(id, NodeType::SourceUnit).is_none());
+        assert!(index.enclosing_contract(id).is_none());
+    }
+    assert!(index.references(3).is_empty());
+}
+
+#[test]
+fn nodes_around_shared_ids_keep_their_own_unit() {
+    let workspace = workspace();
+    let index = AstIndex::new(&workspace);
+
+    assert_eq!(path_of(&index, 13).as_deref(), Some("C.sol"));
+    assert_eq!(path_of(&index, 1013).as_deref(), Some("D.sol"));
+    assert_eq!(path_of(&index, 1008).as_deref(), Some("D.sol"));
+
+    // the contracts share id 20, each function still finds its own
+    let contract = |id| {
+        index
+            .enclosing_contract(id)
+            .expect("f is declared in a contract")
+    };
+    assert!(!std::ptr::eq(contract(13), contract(1013)));
+    assert!(std::ptr::eq(contract(1013), contract(1008)));
+    assert_eq!(index.parent(1013).and_then(|node| node.id()), Some(20));
+
+    assert_eq!(index.enclosing_function(8).map(|f| f.id()), Some(13));
+    assert_eq!(index.enclosing_function(1008).map(|f| f.id()), Some(1013));
+
+    let references = |id| {
+        index
+            .references(id)
+            .iter()
+            .map(|node| node.id())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(references(5), [Some(8)]);
+    assert_eq!(references(1005), [Some(1008)]);
+}
@@ -0,0 +1,148 @@
+//! Pre- and post-order iteration, checked against the order of a walk.
+
+use ast_parser::{
+    ast_framework::Workspace,
+    ast_parse::parse,
+    ast_specs::{NodeType, NodeTypeInternalRef, SourceUnit},
+    ast_visitor::{AstIter, AstVisitor, TraversalOrder, Visit, VisitControl, Walk},
+};
+
+/// solc 0.8 compact AST of `fixtures/C.sol`.
+const AST: &str = include_str!("fixtures/C.ast.json");
+
+/// Yul blocks, import aliases and most of the syntax of Solidity 0.8.
+const TOKEN: &str = include_str!("fixtures/Token.sol");
+
+fn unit() -> SourceUnit {
+    serde_json::from_str(AST).expect("The fixture is a valid compact AST")
+}
+
+/// Nodes in the order the walk enters them, and in the order it leaves them.
+#[derive(Default)]
+struct Orders<'ast> {
+    pre: Vec<NodeTypeInternalRef<'ast>>,
+    post: Vec<NodeTypeInternalRef<'ast>>,
+}
+
+impl<'ast> Visit<'ast> for Orders<'ast> {
+    fn visit_node(&mut self, node: NodeTypeInternalRef<'ast>) -> VisitControl {
+        self.pre.push(node);
+        VisitControl::Continue
+    }
+
+    fn leave_node(&mut self, node: NodeTypeInternalRef<'ast>) -> VisitControl {
+        self.post.push(node);
+        VisitControl::Continue
+    }
+}
+
+fn orders<'ast, T: Walk<'ast> + ?Sized>(root: &'ast T) -> Orders<'ast> {
+    let mut orders = Orders::default();
+    let _ = root.walk(&mut orders);
+    orders
+}
+
+fn assert_walk_order<'ast, T: Walk<'ast> + ?Sized>(root: &'ast T) {
+    let orders = orders(root);
+    assert!(!orders.pre.is_empty());
+    assert_eq!(root.iter_nodes().collect::<Vec<_>>(), orders.pre);
+    assert_eq!(
+        root.iter_nodes_in(TraversalOrder::PreOrder)
+            .collect::<Vec<_>>(),
+        orders.pre
+    );
+    assert_eq!(
+        root.iter_nodes_in(TraversalOrder::PostOrder)
+            .collect::<Vec<_>>(),
+        orders.post
+    );
+}
+
+#[test]
+fn iteration_follows_the_walk() {
+    assert_walk_order(&unit());
+    assert_walk_order(&parse(TOKEN).expect("The fixture parses"));
+}
+
+#[test]
+fn workspaces_iterate_unit_after_unit() {
+    let token = parse(TOKEN).expect("The fixture parses");
+    let workspace: Workspace = [unit(), token].into_iter().collect();
+    assert_walk_order(&workspace);
+
+    let units: Vec<_> = workspace.iter_by_node_type(NodeType::SourceUnit).collect();
+    assert_eq!(units.len(), 2);
+}
+
+#[test]
+fn post_order_is_the_order_of_filter_by_node_type() {
+    let unit = parse(TOKEN).expect("The fixture parses");
+    for node_type in [
+        NodeType::Identifier,
+        NodeType::FunctionDefinition,
+        NodeType::YulIdentifier,
+    ] {
+        let filtered = unit.filter_ref_by_node_type(node_type);
+        assert!(!filtered.is_empty(), "no {node_type:?} in the fixture");
+        let iterated: Vec<_> = unit
+            .iter_nodes_in(TraversalOrder::PostOrder)
+            .filter(|node| NodeType::from(*node) == node_type)
+            .collect();
+        assert_eq!(iterated, filtered);
+    }
+}
+
+#[test]
+fn descendants_are_the_walk_below_a_node() {
+    let unit = unit();
+    let function = unit
+        .iter_by_node_type(NodeType::FunctionDefinition)
+        .next()
+        .expect("f is a function");
+    let orders = orders(&unit);
+    let start = orders
+        .pre
+        .iter()
+        .position(|node| *node == function)
+        .expect("The walk reaches f");
+    let end = orders
+        .post
+        .iter()
+        .position(|node| *node == function)
+        .expect("The walk reaches f");
+
+    let count = function.descendants().count();
+    assert_eq!(
+        function.descendants().collect::<Vec<_>>(),
+        orders.pre[start + 1..start + 1 + count]
+    );
+    assert_eq!(
+        function
+            .descendants_in(TraversalOrder::PostOrder)
+            .collect::<Vec<_>>(),
+        orders.post[end - count..end]
+    );
+
+    let children: Vec<_> = function
+        .children()
+        .into_iter()
+        .map(|child| child.id())
+        .collect();
+    assert_eq!(children, [Some(12), Some(6), Some(7)]);
+}
+
+#[test]
+fn dropping_the_iterator_early_yields_a_prefix() {
+    let unit = unit();
+    let orders = orders(&unit);
+    assert_eq!(
+        unit.iter_nodes().take(4).collect::<Vec<_>>(),
+        orders.pre[..4]
+    );
+    assert_eq!(
+        unit.iter_nodes_in(TraversalOrder::PostOrder)
+            .take(4)
+            .collect::<Vec<_>>(),
+        orders.post[..4]
+    );
+}
@@ -0,0 +1,64 @@
+//! Legacy `children`/`attributes` ASTs of solc 0.4 and 0.5, and compiler versions of artifacts.
+
+use ast_parser::{
+    ast_normalize::{is_legacy, source_unit_from_value},
+    ast_serialize::AstSerializer,
+    ast_specs::{NodeType, SourceUnit},
+    ast_visitor::AstIter,
+    cast_to_source_unit,
+};
+use serde_json::{Value, json};
+
+/// solc 0.4.26 `--ast-json` of `fixtures/legacy/C04.sol`.
+const LEGACY_04: &str = include_str!("fixtures/legacy/C04.legacy.json");
+/// solc 0.5.17 `--ast-json` of `fixtures/legacy/C05.sol`.
+const LEGACY_05: &str = include_str!("fixtures/legacy/C05.legacy.json");
+
+fn load(json: &str, version: &str) -> SourceUnit {
+    let ast: Value = serde_json::from_str(json).expect("The fixture is valid JSON");
+    assert!(is_legacy(&ast));
+    source_unit_from_value(ast, Some(version)).expect("The legacy AST deserializes")
+}
+
+fn body_of_f(unit: &SourceUnit) -> String {
+    let printed = unit.to_sol_string();
+    let start = printed.find("function f").expect("f is printed");
+    printed[start..].to_owned()
+}
+
+#[test]
+fn solc_04_throw_statements_are_kept() {
+    let unit = load(LEGACY_04, "0.4.26+commit.4563c3fc");
+    assert_eq!(unit.compiler_version(), Some("0.4.26+commit.4563c3fc"));
+    let throws: Vec<_> = unit.iter_by_node_type(NodeType::Throw).collect();
+    assert_eq!(throws.len(), 1);
+    assert_eq!(throws[0].id(), Some(10));
+
+    let f = body_of_f(&unit);
+    assert!(f.contains("if (a == 0) throw;"), "{f}");
+    assert!(f.contains("return a + x;"), "{f}");
+}
+
+#[test]
+fn solc_05_legacy_units_deserialize() {
+    let unit = load(LEGACY_05, "0.5.17+commit.d19bba13");
+    assert_eq!(unit.absolute_path(), "C05.sol");
+    let f = body_of_f(&unit);
+    assert!(
+        f.starts_with("function f(uint256 a) public view returns (uint256) {"),
+        "{f}"
+    );
+    assert!(f.contains("require(a != 0, \"zero\");"), "{f}");
+}
+
+#[test]
+fn foundry_artifacts_record_their_compiler_version() {
+    let ast: Value = serde_json::from_str(include_str!("fixtures/C.ast.json")).unwrap();
+    let artifact = json!({
+        "ast": ast,
+        "metadata": { "compiler": { "version": "0.8.26+commit.8a97fa7a" } },
+    });
+    let unit =
+        cast_to_source_unit(artifact.to_string().as_bytes()).expect("The artifact deserializes");
+    assert_eq!(unit.compiler_version(), Some("0.8.26+commit.8a97fa7a"));
+}
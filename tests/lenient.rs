@@ -0,0 +1,48 @@
+//! Nodes of an unknown `nodeType` in lenient mode.
+
+use std::panic;
+
+use ast_parser::ast_specs::{Body, Directive, FalseBody, is_lenient, lenient};
+use serde_json::{Value, json};
+
+fn future_node() -> Value {
+    json!({ "id": 7, "nodeType": "FutureNode", "src": "0:1:0" })
+}
+
+#[test]
+fn unknown_directive_and_bodies_need_lenient_mode() {
+    assert!(serde_json::from_value::<Directive>(future_node()).is_err());
+    assert!(serde_json::from_value::<Body>(future_node()).is_err());
+    assert!(serde_json::from_value::<FalseBody>(future_node()).is_err());
+
+    lenient(|| {
+        let directive: Directive = serde_json::from_value(future_node()).unwrap();
+        assert!(
+            matches!(directive, Directive::Unknown(ref unknown) if unknown.node_type() == "FutureNode")
+        );
+        let body: Body = serde_json::from_value(future_node()).unwrap();
+        assert!(matches!(body, Body::Unknown(ref unknown) if unknown.id() == 7));
+        let false_body: FalseBody = serde_json::from_value(future_node()).unwrap();
+        assert!(matches!(false_body, FalseBody::Unknown(_)));
+    });
+}
+
+#[test]
+fn known_nodes_keep_their_variant() {
+    let body = json!({ "id": 1, "nodeType": "Break", "src": "0:6:0" });
+    let body: Body = lenient(|| serde_json::from_value(body)).unwrap();
+    assert!(matches!(body, Body::Break(_)));
+}
+
+#[test]
+fn a_panic_restores_the_previous_mode() {
+    let unwound = panic::catch_unwind(|| lenient(|| panic!("loader failed")));
+    assert!(unwound.is_err());
+    assert!(!is_lenient());
+
+    lenient(|| {
+        let _ = panic::catch_unwind(|| lenient(|| panic!("nested loader failed")));
+        assert!(is_lenient());
+    });
+    assert!(!is_lenient());
+}
@@ -0,0 +1,102 @@
+//! Errors of ASTs that fail to load point at the rejected node.
+
+use ast_parser::{ErrorKind, ast_framework::FoundryOutput, cast_to_source_unit};
+use serde_json::Value;
+
+const FIXTURE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/malformed/C.json"
+);
+
+/// Foundry artifact of `fixtures/C.sol` whose `a + x` has a number for its operator.
+const ARTIFACT: &str = include_str!("fixtures/malformed/C.json");
+
+/// `(struct_name, pointer, node_type, id, src, file)` of a JSON error.
+type Located = (
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<isize>,
+    Option<String>,
+    Option<String>,
+);
+
+fn located(kind: &ErrorKind) -> Located {
+    match kind {
+        ErrorKind::JsonParsingError {
+            struct_name,
+            inner,
+            pointer,
+            node_type,
+            id,
+            src,
+            file,
+        } => {
+            assert!(inner.is_some(), "no reason in {kind:?}");
+            (
+                struct_name.clone(),
+                pointer.clone(),
+                node_type.clone(),
+                *id,
+                src.clone(),
+                file.clone(),
+            )
+        }
+        kind => panic!("Not a JSON error: {kind:?}"),
+    }
+}
+
+fn expected(file: Option<&str>) -> Located {
+    (
+        Some("BinaryOperation".to_owned()),
+        Some("/ast/nodes/1/nodes/1/body/statements/0/expression".to_owned()),
+        Some("BinaryOperation".to_owned()),
+        Some(10),
+        Some("161:5:0".to_owned()),
+        file.map(str::to_owned),
+    )
+}
+
+#[test]
+fn errors_name_the_rejected_node() {
+    let error =
+        FoundryOutput::from_reader(ARTIFACT.as_bytes()).expect_err("The fixture is invalid");
+    assert_eq!(located(error.kind()), expected(None));
+
+    let error = cast_to_source_unit(ARTIFACT.as_bytes()).expect_err("The fixture is invalid");
+    assert_eq!(located(error.kind()), expected(None));
+}
+
+#[test]
+fn errors_of_files_name_the_file() {
+    let error = FoundryOutput::from_path(FIXTURE).expect_err("The fixture is invalid");
+    assert_eq!(located(error.kind()), expected(Some(FIXTURE)));
+    let message = error.to_string();
+    for part in [FIXTURE, "BinaryOperation", "161:5:0"] {
+        assert!(message.contains(part), "{part:?} missing from {message:?}");
+    }
+}
+
+#[test]
+fn errors_point_at_the_innermost_rejected_node() {
+    let mut artifact: Value = serde_json::from_str(ARTIFACT).expect("The fixture is valid JSON");
+    let operation = artifact
+        .pointer_mut("/ast/nodes/1/nodes/1/body/statements/0/expression")
+        .expect("The fixture has the operation");
+    operation["operator"] = "+".into();
+    operation["leftExpression"]["name"] = Value::Null;
+
+    let error = FoundryOutput::from_reader(artifact.to_string().as_bytes())
+        .expect_err("The identifier is invalid");
+    assert_eq!(
+        located(error.kind()),
+        (
+            Some("Identifier".to_owned()),
+            Some("/ast/nodes/1/nodes/1/body/statements/0/expression/leftExpression".to_owned()),
+            Some("Identifier".to_owned()),
+            Some(8),
+            Some("161:1:0".to_owned()),
+            None,
+        )
+    );
+}
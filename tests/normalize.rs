@@ -0,0 +1,80 @@
+//! Ids of the nodes normalization synthesizes for older ASTs.
+
+use std::collections::HashSet;
+
+use ast_parser::ast_normalize::normalize;
+use serde_json::{Value, json};
+
+/// Pre-0.7 compact unit importing `aliases` symbols by id, with a plain string contract doc.
+fn unit(import_id: i64, aliases: usize) -> Value {
+    let symbol_aliases: Vec<_> = (0..aliases)
+        .map(|i| json!({ "foreign": i, "local": null }))
+        .collect();
+    json!({
+        "absolutePath": "A.sol",
+        "id": 1,
+        "nodeType": "SourceUnit",
+        "src": "0:0:0",
+        "nodes": [
+            {
+                "absolutePath": "B.sol",
+                "file": "B.sol",
+                "id": import_id,
+                "nodeType": "ImportDirective",
+                "scope": 1,
+                "sourceUnit": 2,
+                "src": "0:0:0",
+                "symbolAliases": symbol_aliases,
+            },
+            {
+                "documentation": "A contract",
+                "id": import_id + 1,
+                "name": "A",
+                "nodeType": "ContractDefinition",
+                "scope": 1,
+                "src": "0:0:0",
+            },
+        ],
+    })
+}
+
+fn synthetic_ids(unit: &Value) -> Vec<i64> {
+    let import = &unit["nodes"][0];
+    let mut ids: Vec<_> = import["symbolAliases"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|alias| alias["foreign"]["id"].as_i64().unwrap())
+        .collect();
+    ids.push(unit["nodes"][1]["documentation"]["id"].as_i64().unwrap());
+    ids
+}
+
+#[test]
+fn synthetic_ids_are_unique_past_256_slots() {
+    let mut unit = unit(3, 600);
+    normalize(&mut unit);
+    let ids = synthetic_ids(&unit);
+
+    assert_eq!(ids.len(), 601);
+    assert!(ids.iter().all(|&id| id < -1));
+    assert_eq!(ids.iter().collect::<HashSet<_>>().len(), ids.len());
+}
+
+#[test]
+fn large_parent_ids_do_not_overflow() {
+    let mut unit = unit(i64::MAX - 1, 3);
+    normalize(&mut unit);
+    let ids = synthetic_ids(&unit);
+    assert!(ids.iter().all(|&id| id < -1));
+    assert_eq!(ids.iter().collect::<HashSet<_>>().len(), ids.len());
+}
+
+#[test]
+fn synthetic_ids_are_stable_across_normalizations() {
+    let mut first = unit(3, 4);
+    let mut second = unit(3, 4);
+    normalize(&mut first);
+    normalize(&mut second);
+    assert_eq!(synthetic_ids(&first), synthetic_ids(&second));
+}
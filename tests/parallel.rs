@@ -0,0 +1,121 @@
+//! Parallel queries return what the sequential ones do, in the same order.
+#![cfg(feature = "rayon")]
+
+use ast_parser::{
+    ast_framework::Workspace,
+    ast_parse::parse,
+    ast_specs::{NodeType, SourceUnit},
+    ast_visitor::{AstIter, AstRefVisitor, ParAstRefVisitor, Pattern, PatternMatch},
+};
+use serde_json::Value;
+
+/// solc 0.8 compact AST of `fixtures/C.sol`.
+const AST: &str = include_str!("fixtures/C.ast.json");
+
+/// Yul blocks, import aliases and most of the syntax of Solidity 0.8.
+const TOKEN: &str = include_str!("fixtures/Token.sol");
+
+const COMMENTS: &str = include_str!("fixtures/Comments.sol");
+
+/// Node types of every shape: nested in their own kind, spread over contracts, or file level.
+const NODE_TYPES: [NodeType; 6] = [
+    NodeType::Identifier,
+    NodeType::FunctionDefinition,
+    NodeType::Block,
+    NodeType::YulIdentifier,
+    NodeType::ImportDirective,
+    NodeType::SourceUnit,
+];
+
+const SELECTORS: [&str; 3] = [
+    "ContractDefinition FunctionDefinition[visibility=external] Identifier",
+    "Block > ExpressionStatement, YulBlock",
+    "*[name]",
+];
+
+fn unit(path: &str, id: i64) -> SourceUnit {
+    let mut ast: Value = serde_json::from_str(AST).expect("The fixture is valid JSON");
+    ast["absolutePath"] = path.into();
+    ast["id"] = id.into();
+    serde_json::from_value(ast).expect("The fixture is a valid compact AST")
+}
+
+fn workspace() -> Workspace {
+    let token = parse(TOKEN).expect("The fixture parses");
+    let comments = parse(COMMENTS).expect("The fixture parses");
+    [unit("C.sol", 30), token, comments, unit("D.sol", 31)]
+        .into_iter()
+        .collect()
+}
+
+#[test]
+fn node_types_come_out_in_walk_order() {
+    let workspace = workspace();
+    for node_type in NODE_TYPES {
+        let sequential: Vec<_> = workspace.iter_by_node_type(node_type).collect();
+        assert!(!sequential.is_empty(), "no {node_type:?} in the fixtures");
+        assert_eq!(
+            workspace.par_refs_by_node_type(node_type),
+            sequential,
+            "{node_type:?}"
+        );
+        assert_eq!(
+            workspace.par_located_by_node_type(node_type),
+            workspace.located_by_node_type(node_type),
+            "{node_type:?}"
+        );
+    }
+}
+
+#[test]
+fn selectors_and_references_match_the_sequential_queries() {
+    let workspace = workspace();
+    for selector in SELECTORS {
+        assert_eq!(
+            workspace.par_refs_by_selector(selector).unwrap(),
+            workspace.refs_by_selector(selector).unwrap(),
+            "{selector}"
+        );
+        assert_eq!(
+            workspace.par_located_by_selector(selector).unwrap(),
+            workspace.located_by_selector(selector).unwrap(),
+            "{selector}"
+        );
+    }
+    assert!(workspace.par_refs_by_selector("Nope").is_err());
+
+    let references = workspace.par_refs_by_reference_id(3);
+    assert_eq!(references.len(), 2);
+    let sequential: Vec<_> = workspace
+        .iter_nodes()
+        .filter(|node| node.reference_id() == Some(3))
+        .collect();
+    assert_eq!(references, sequential);
+}
+
+#[test]
+fn patterns_match_the_sequential_find() {
+    let workspace = workspace();
+    for pattern in ["$X + $Y", "return $X;", "$S;"] {
+        let pattern = Pattern::parse(pattern).expect("The pattern is valid");
+        let located = |matches: Vec<_>| -> Vec<_> {
+            matches
+                .into_iter()
+                .map(|found: PatternMatch<'_>| (found.node(), found.path().clone()))
+                .collect()
+        };
+        let parallel = located(workspace.par_find_pattern(&pattern));
+        assert!(!parallel.is_empty());
+        assert_eq!(parallel, located(pattern.find(&workspace)));
+    }
+}
+
+#[test]
+fn results_are_the_same_on_every_run() {
+    let workspace = workspace();
+    let first = workspace.par_located_by_selector("*").unwrap();
+    for _ in 0..16 {
+        assert_eq!(workspace.par_located_by_selector("*").unwrap(), first);
+    }
+    assert_eq!(first.len(), workspace.iter_nodes().count());
+}
@@ -0,0 +1,94 @@
+//! Parsing Solidity source, and printing the parsed tree back.
+
+use ast_parser::{
+    ErrorKind,
+    ast_parse::{SolidityParser, parse},
+    ast_serialize::AstSerializer,
+    ast_specs::{NodeType, SourceUnit},
+    ast_visitor::AstIter,
+};
+
+/// Most of the syntax of Solidity 0.8.
+const TOKEN: &str = include_str!("fixtures/Token.sol");
+
+fn node_types(unit: &SourceUnit) -> Vec<NodeType> {
+    unit.iter_nodes().map(NodeType::from).collect()
+}
+
+#[test]
+fn printed_sources_parse_back_to_the_same_tree() {
+    let unit = parse(TOKEN).expect("The fixture parses");
+    let printed = unit.to_sol_string();
+    let reparsed = parse(&printed).expect("The printed source parses");
+    assert_eq!(node_types(&reparsed), node_types(&unit));
+    assert_eq!(reparsed.to_sol_string(), printed);
+}
+
+/// Position of the syntax error `source` is rejected with.
+fn error_at(source: &str) -> (usize, usize, usize) {
+    let error = parse(source).expect_err("The source is invalid");
+    match error.kind() {
+        ErrorKind::SyntaxError {
+            offset,
+            line,
+            column,
+            ..
+        } => (*offset, *line, *column),
+        kind => panic!("Not a syntax error: {kind:?}"),
+    }
+}
+
+#[test]
+fn syntax_errors_point_at_the_offending_token() {
+    let source = "contract C {\n    function f() public {\n        uint256 x = ;\n    }\n}\n";
+    let offset = source.find("= ;").unwrap() + 2;
+    assert_eq!(error_at(source), (offset, 3, 21));
+}
+
+#[test]
+fn lexer_errors_point_at_the_offending_character() {
+    let source = "contract C {\n    string s = \"open;\n}\n";
+    let offset = source.find('"').unwrap();
+    assert_eq!(error_at(source), (offset, 2, 16));
+}
+
+#[test]
+fn unexpected_end_of_input_points_past_the_last_token() {
+    let source = "contract C {\n    uint256 x;\n";
+    assert_eq!(error_at(source), (source.len(), 3, 1));
+}
+
+#[test]
+fn columns_count_bytes_after_multi_byte_characters() {
+    let source = "contract C {\n    string s = \"é\"; uint256 x = ; }\n";
+    let offset = source.find("= ;").unwrap() + 2;
+    // 32 characters precede the `;`, `é` taking two bytes
+    assert_eq!(error_at(source), (offset, 2, 34));
+}
+
+#[test]
+fn syntax_errors_name_the_file_and_position() {
+    let error = SolidityParser::new()
+        .with_absolute_path("src/C.sol")
+        .parse_source_unit("contract C {\n    uint256 x = ;\n}\n")
+        .expect_err("The source is invalid");
+    let message = error.to_string();
+    assert!(
+        message.starts_with("syntax error in src/C.sol at 2:17: "),
+        "{message}"
+    );
+}
+
+#[test]
+fn declarations_end_before_their_semicolon() {
+    let source = "uint256 constant A = 1;\n\ncontract C {\n    uint256 x;\n\n    struct S {\n        bool b;\n    }\n}\n";
+    let unit = parse(source).unwrap();
+    let texts: Vec<_> = unit
+        .iter_by_node_type(NodeType::VariableDeclaration)
+        .map(|node| {
+            let src = node.src_location().unwrap();
+            &source[src.range()]
+        })
+        .collect();
+    assert_eq!(texts, ["uint256 constant A = 1", "uint256 x", "bool b"]);
+}
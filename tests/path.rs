@@ -0,0 +1,146 @@
+//! Where a node is: the enclosing definitions, loops and `unchecked` blocks of its path.
+
+use ast_parser::{
+    ast_parse::parse,
+    ast_specs::{NodeType, NodeTypeInternalRef, SourceUnit},
+    ast_visitor::{Located, Selector},
+};
+
+const VAULT: &str = r#"uint256 constant LIMIT = 10;
+
+function helper(uint256 a) pure returns (uint256) {
+    return a;
+}
+
+contract Vault {
+    mapping(address => uint256) balances;
+
+    modifier onlyOwner() {
+        require(msg.sender != address(0));
+        _;
+    }
+
+    constructor() {
+        balances[msg.sender] = LIMIT;
+    }
+
+    function withdraw(uint256[] memory amounts) external onlyOwner {
+        for (uint256 i = 0; i < amounts.length; i++) {
+            unchecked {
+                balances[msg.sender] -= amounts[i];
+            }
+        }
+        uint256 j;
+        while (j < 3) {
+            j++;
+        }
+        do {
+            j--;
+        } while (j > 0);
+    }
+}
+"#;
+
+fn unit() -> SourceUnit {
+    parse(VAULT).expect("The source parses")
+}
+
+fn located<'root>(unit: &'root SourceUnit, selector: &str) -> Vec<Located<'root>> {
+    Selector::parse(selector)
+        .expect("The selector is valid")
+        .select_located(unit)
+}
+
+/// The one node `selector` selects.
+fn one<'root>(unit: &'root SourceUnit, selector: &str) -> Located<'root> {
+    let mut found = located(unit, selector);
+    assert_eq!(found.len(), 1, "{selector}");
+    found.remove(0)
+}
+
+#[test]
+fn paths_read_like_a_location() {
+    let unit = unit();
+    let cases = [
+        ("Literal[value=10]", "at file level"),
+        ("Return", "in `helper`"),
+        ("VariableDeclaration[name=balances]", "in `Vault`"),
+        (
+            "ModifierDefinition ExpressionStatement > FunctionCall",
+            "in `Vault.onlyOwner`",
+        ),
+        ("Assignment[operator='=']", "in `Vault.constructor`"),
+        (
+            "Assignment[operator='-=']",
+            "in `Vault.withdraw`, inside `for` loop, inside `unchecked` block",
+        ),
+        (
+            "WhileStatement UnaryOperation",
+            "in `Vault.withdraw`, inside `while` loop",
+        ),
+        (
+            "UnaryOperation[operator='--']",
+            "in `Vault.withdraw`, inside `do while` loop",
+        ),
+    ];
+    for (selector, location) in cases {
+        assert_eq!(
+            one(&unit, selector).path().to_string(),
+            location,
+            "{selector}"
+        );
+    }
+}
+
+#[test]
+fn closest_finds_the_innermost_ancestor() {
+    let unit = unit();
+    let assignment = one(&unit, "Assignment[operator='-=']");
+    let path = assignment.path();
+
+    assert!(matches!(
+        path.closest(NodeType::UncheckedBlock),
+        Some(NodeTypeInternalRef::UncheckedBlock(_))
+    ));
+    // the body of the `for` loop, not the body of the function
+    let block = path.closest(NodeType::Block).expect("The loop has a body");
+    let function = path.function().expect("withdraw is a function");
+    assert_ne!(block.id(), function.body().as_ref().map(|body| body.id()));
+    assert_eq!(
+        path.closest(NodeType::FunctionDefinition)
+            .and_then(|node| node.id()),
+        Some(function.id())
+    );
+    assert!(path.closest(NodeType::ModifierDefinition).is_none());
+    assert!(matches!(
+        path.closest(NodeType::SourceUnit),
+        Some(NodeTypeInternalRef::SourceUnit(_))
+    ));
+}
+
+#[test]
+fn loops_and_unchecked_blocks_enclose_only_their_body() {
+    let unit = unit();
+
+    let decrement = one(&unit, "Assignment[operator='-=']");
+    assert!(matches!(
+        decrement.path().enclosing_loop(),
+        Some(NodeTypeInternalRef::ForStatement(_))
+    ));
+    assert!(decrement.path().unchecked().is_some());
+
+    let increment = one(&unit, "WhileStatement UnaryOperation");
+    assert!(matches!(
+        increment.path().enclosing_loop(),
+        Some(NodeTypeInternalRef::WhileStatement(_))
+    ));
+    assert!(increment.path().unchecked().is_none());
+
+    let counter = one(&unit, "VariableDeclaration[name=j]");
+    assert!(counter.path().enclosing_loop().is_none());
+    assert!(counter.path().unchecked().is_none());
+
+    // a loop is not inside itself
+    let for_loop = one(&unit, "ForStatement");
+    assert!(for_loop.path().enclosing_loop().is_none());
+}
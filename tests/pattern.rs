@@ -0,0 +1,107 @@
+//! Matching code patterns with metavariables.
+
+use ast_parser::{
+    ast_parse::SolidityParser,
+    ast_specs::{Block, Expression, Identifier, NodeTypeInternalRef, SourceUnit, Statement},
+    ast_visitor::Pattern,
+};
+
+/// solc 0.8 compact AST of `fixtures/C.sol`.
+const AST: &str = include_str!("fixtures/C.ast.json");
+
+fn unit() -> SourceUnit {
+    serde_json::from_str(AST).expect("The fixture is a valid compact AST")
+}
+
+fn metavariable(name: &str) -> Expression {
+    Expression::Identifier(Identifier::artificial_new(name.to_owned()))
+}
+
+fn expression(source: &str) -> Expression {
+    SolidityParser::new()
+        .parse_expression(source)
+        .expect("The expression parses")
+}
+
+#[test]
+fn expression_metavariables_bind_expressions_only() {
+    let pattern = Pattern::from(metavariable("$X"));
+    let owner = Identifier::artificial_new("owner".to_owned());
+    let bindings = pattern
+        .matches(NodeTypeInternalRef::Identifier(&owner))
+        .expect("$X matches an identifier");
+    assert_eq!(
+        bindings.get("$X"),
+        Some(&NodeTypeInternalRef::Identifier(&owner))
+    );
+
+    let block = Block::artificial_new(vec![]);
+    assert!(
+        pattern
+            .matches(NodeTypeInternalRef::Block(&block))
+            .is_none()
+    );
+}
+
+#[test]
+fn statement_metavariables_bind_statements_only() {
+    let pattern = Pattern::parse("$S;").unwrap();
+    let block = Block::artificial_new(vec![Statement::Block(Block::artificial_new(vec![]))]);
+    let bindings = pattern
+        .matches(NodeTypeInternalRef::Block(&block))
+        .expect("$S; matches a block");
+    assert_eq!(bindings.len(), 1);
+    assert!(bindings["$S"].is_block());
+
+    let owner = Identifier::artificial_new("owner".to_owned());
+    assert!(
+        pattern
+            .matches(NodeTypeInternalRef::Identifier(&owner))
+            .is_none()
+    );
+}
+
+#[test]
+fn repeated_metavariables_bind_equal_subtrees() {
+    let pattern = Pattern::parse("$A == $A").unwrap();
+    let same = expression("balances[to] == balances[to]");
+    let bindings = pattern
+        .matches((&same).into())
+        .expect("Both sides are equal");
+    assert!(bindings["$A"].is_index_access());
+
+    let different = expression("balances[to] == balances[from]");
+    assert!(pattern.matches((&different).into()).is_none());
+}
+
+#[test]
+fn parsed_patterns_match_compiled_code() {
+    let unit = unit();
+
+    let found = Pattern::parse("$A + $B").unwrap().find(&unit);
+    assert_eq!(found.len(), 1);
+    let names = ["$A", "$B"].map(|name| {
+        found[0]
+            .binding(name)
+            .and_then(|node| node.cast_identifier())
+            .map(|identifier| identifier.name().to_owned())
+    });
+    assert_eq!(names, [Some("a".to_owned()), Some("x".to_owned())]);
+    assert_eq!(found[0].path().function().map(|f| f.id()), Some(13));
+
+    assert!(Pattern::parse("$A + $A").unwrap().find(&unit).is_empty());
+
+    let returns = Pattern::parse("return $X;").unwrap().find(&unit);
+    assert_eq!(
+        returns.iter().map(|m| m.node().id()).collect::<Vec<_>>(),
+        [Some(11)]
+    );
+}
+
+#[test]
+fn statement_metavariables_find_every_statement() {
+    let unit = unit();
+    let found = Pattern::parse("$S;").unwrap().find(&unit);
+    let ids: Vec<_> = found.iter().map(|m| m.node().id()).collect();
+    assert_eq!(ids, [Some(12), Some(11)]);
+}
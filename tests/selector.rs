@@ -0,0 +1,93 @@
+//! Selector syntax errors, and the child and descendant combinators.
+
+use ast_parser::{ErrorKind, ast_specs::SourceUnit, ast_visitor::Selector};
+
+/// solc 0.8 compact AST of `fixtures/C.sol`.
+const AST: &str = include_str!("fixtures/C.ast.json");
+
+fn unit() -> SourceUnit {
+    serde_json::from_str(AST).expect("The fixture is a valid compact AST")
+}
+
+/// Ids of the nodes `selector` selects in the fixture.
+fn select(selector: &str) -> Vec<isize> {
+    Selector::parse(selector)
+        .expect("The selector is valid")
+        .select(&unit())
+        .into_iter()
+        .map(|node| node.id().expect("The node has an id"))
+        .collect()
+}
+
+/// Offset and message of the error `selector` is rejected with.
+fn error_at(selector: &str) -> (usize, String) {
+    let error = Selector::parse(selector).expect_err("The selector is invalid");
+    match error.kind() {
+        ErrorKind::InvalidSelector {
+            selector: rejected,
+            offset,
+            message,
+        } => {
+            assert_eq!(rejected, selector);
+            (*offset, message.clone())
+        }
+        kind => panic!("Not a selector error: {kind:?}"),
+    }
+}
+
+#[test]
+fn errors_point_at_the_offending_character() {
+    let cases = [
+        ("Nope", 0, "unknown node type `Nope`"),
+        ("Block, Nope", 7, "unknown node type `Nope`"),
+        ("FunctionDefinition >", 20, "expected a node type or `*`"),
+        ("FunctionDefinition > ", 21, "expected a node type or `*`"),
+        ("Identifier)", 10, "expected `,` or end of selector"),
+        ("Identifier[", 11, "expected an attribute name"),
+        ("Identifier[name", 15, "expected `]`"),
+        ("Identifier[name=", 16, "expected a value"),
+        ("Identifier[name='x]", 16, "unterminated string"),
+        ("Identifier[name=x y]", 18, "expected `]`"),
+    ];
+    for (selector, offset, message) in cases {
+        assert_eq!(
+            error_at(selector),
+            (offset, message.to_owned()),
+            "{selector}"
+        );
+    }
+}
+
+#[test]
+fn child_combinators_match_direct_children_only() {
+    assert_eq!(select("BinaryOperation > Identifier"), [8, 9]);
+    assert!(select("Return > Identifier").is_empty());
+    assert!(select("ContractDefinition > Identifier").is_empty());
+    assert_eq!(select("FunctionDefinition > Block"), [12]);
+    assert_eq!(
+        select("ContractDefinition > FunctionDefinition > ParameterList > VariableDeclaration"),
+        [5, 4]
+    );
+}
+
+#[test]
+fn descendant_combinators_match_at_any_depth() {
+    assert_eq!(select("Return Identifier"), [8, 9]);
+    assert_eq!(select("ContractDefinition Identifier"), [8, 9]);
+    assert_eq!(select("SourceUnit VariableDeclaration"), [3, 5, 4]);
+    assert_eq!(select("FunctionDefinition ElementaryTypeName"), [105, 104]);
+    assert!(select("Block ParameterList").is_empty());
+}
+
+#[test]
+fn combinators_mix() {
+    // `*` directly below the contract, then anything below it
+    assert_eq!(select("ContractDefinition > * Identifier"), [8, 9]);
+    assert!(select("ContractDefinition > * > Identifier").is_empty());
+    assert_eq!(
+        select("SourceUnit ParameterList > VariableDeclaration[name=a]"),
+        [5]
+    );
+    // alternatives come out in walk order
+    assert_eq!(select("Return, PragmaDirective"), [1, 11]);
+}
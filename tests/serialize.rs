@@ -0,0 +1,141 @@
+//! Printing parsed sources back to Solidity.
+
+use ast_parser::{
+    ast_parse::parse,
+    ast_serialize::AstSerializer,
+    ast_specs::{
+        NodeType, NodeTypeInternalRef, inline_assembly::yul_expression::yul_literal::YulLiteral,
+    },
+    ast_visitor::AstIter,
+};
+
+/// Directives and specifiers whose parts the printer used to drop.
+const DIRECTIVES: &str = include_str!("fixtures/Directives.sol");
+
+/// `memory-safe` assembly, Yul functions, `for`, `switch` and literals of every kind.
+const ASSEMBLY: &str = include_str!("fixtures/Assembly.sol");
+
+fn print(source: &str) -> String {
+    parse(source).expect("The source parses").to_sol_string()
+}
+
+#[test]
+fn printing_a_reparsed_print_is_a_fixpoint() {
+    let printed = print(DIRECTIVES);
+    assert_eq!(print(&printed), printed);
+}
+
+#[test]
+fn directives_keep_all_their_parts() {
+    let printed = print(DIRECTIVES);
+    for expected in [
+        "pragma solidity >=0.8.19 <0.9.0;",
+        "pragma abicoder v2;",
+        "pragma experimental ABIEncoderV2;",
+        "using {add as +, neg} for Price global;",
+        "using L for *;",
+        "event Logged(uint256 indexed a) anonymous;",
+        "event Kept(uint256 a);",
+        "contract C is B(1) {",
+    ] {
+        assert!(
+            printed.contains(expected),
+            "{expected:?} missing from:\n{printed}"
+        );
+    }
+}
+
+#[test]
+fn pragma_version_constraints_are_spaced() {
+    let printed = print("pragma solidity ^0.7.0 || ^0.8.0;\npragma solidity 0.8.0 - 0.8.20;\n");
+    assert!(
+        printed.contains("pragma solidity ^0.7.0 || ^0.8.0;"),
+        "{printed}"
+    );
+    assert!(
+        printed.contains("pragma solidity 0.8.0 - 0.8.20;"),
+        "{printed}"
+    );
+}
+
+/// `f(f(f(1, 2), 2), 2)` nested `depth` deep, too wide for any of the calls to stay on one line.
+fn nested_calls(depth: usize) -> String {
+    format!(
+        "contract C {{\n    function f(uint256 a, uint256 b) internal pure returns (uint256) {{\n        return a + b;\n    }}\n\n    function g() internal pure returns (uint256) {{\n        return {}1{};\n    }}\n}}\n",
+        "f(".repeat(depth),
+        ", 2)".repeat(depth),
+    )
+}
+
+#[test]
+fn deeply_nested_lists_print_in_reasonable_time() {
+    // Parsing and deserializing recurse once per call, past the default test stack in debug
+    let printed = std::thread::Builder::new()
+        .stack_size(64 << 20)
+        .spawn(|| {
+            // Rendering every list twice per level of nesting never finishes at this depth
+            let printed = print(&nested_calls(64));
+            assert_eq!(print(&printed), printed);
+            printed
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+    assert_eq!(printed.matches("f(").count(), 65);
+}
+
+#[test]
+fn yul_prints_back_to_its_source() {
+    let unit = parse(ASSEMBLY).expect("The fixture parses");
+    assert_eq!(unit.to_sol_string(), ASSEMBLY);
+
+    let flags: Vec<_> = unit
+        .iter_by_node_type(NodeType::InlineAssembly)
+        .filter_map(NodeTypeInternalRef::cast_inline_assembly)
+        .map(|assembly| assembly.flags.clone().unwrap_or_default())
+        .collect();
+    assert_eq!(flags, [vec!["memory-safe".to_owned()], vec![], vec![]]);
+    for node_type in [
+        NodeType::YulFunctionDefinition,
+        NodeType::YulForLoop,
+        NodeType::YulSwitch,
+        NodeType::YulContinue,
+        NodeType::YulBreak,
+    ] {
+        assert_eq!(
+            unit.iter_by_node_type(node_type).count(),
+            1,
+            "{node_type:?}"
+        );
+    }
+}
+
+/// Yul literal of solc's compact AST, printed.
+fn print_literal(json: &str) -> String {
+    serde_json::from_str::<YulLiteral>(json)
+        .expect("The literal is a valid compact AST")
+        .to_sol_string()
+}
+
+#[test]
+fn yul_hex_literals_print_as_solc_wrote_them() {
+    // bytes that are no UTF-8 come without a `value`
+    assert_eq!(
+        print_literal(
+            r#"{"hexValue":"deadbeef","kind":"string","nativeSrc":"0:11:0","nodeType":"YulLiteral","src":"0:11:0","type":""}"#
+        ),
+        r#"hex"deadbeef""#
+    );
+    assert_eq!(
+        print_literal(
+            r#"{"hexValue":"616263","kind":"string","nativeSrc":"0:11:0","nodeType":"YulLiteral","src":"0:11:0","type":"","value":"abc"}"#
+        ),
+        r#""abc""#
+    );
+    assert_eq!(
+        print_literal(
+            r#"{"kind":"number","nativeSrc":"0:4:0","nodeType":"YulLiteral","src":"0:4:0","type":"","value":"0xff"}"#
+        ),
+        "0xff"
+    );
+}
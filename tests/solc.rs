@@ -0,0 +1,129 @@
+//! `compile` against stub `solc` scripts that print a canned standard-JSON output.
+#![cfg(all(unix, not(miri)))]
+
+use std::{
+    fs,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+use ast_parser::{
+    ErrorKind,
+    ast_framework::{SolcConfig, SolcOutput, compile},
+    ast_visitor::AstRefVisitor,
+};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+/// Scratch directory holding a `solc` script with `body`, which also logs every run to `runs`.
+struct Stub {
+    dir: PathBuf,
+}
+
+impl Stub {
+    fn new(name: &str, body: &str) -> Self {
+        let dir =
+            std::env::temp_dir().join(format!("ast_parser_solc_{name}_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let solc = dir.join("solc");
+        let runs = dir.join("runs");
+        fs::write(
+            &solc,
+            format!("#!/bin/sh\necho \"$@\" >> '{}'\n{body}\n", runs.display()),
+        )
+        .unwrap();
+        fs::set_permissions(&solc, fs::Permissions::from_mode(0o755)).unwrap();
+        Self { dir }
+    }
+
+    /// Stub printing the fixture at `path`, relative to the fixtures directory.
+    fn printing(name: &str, path: &str) -> Self {
+        Self::new(name, &format!("cat > /dev/null\ncat '{FIXTURES}/{path}'"))
+    }
+
+    fn config(&self) -> SolcConfig {
+        SolcConfig::new(self.dir.join("solc"))
+    }
+
+    /// Arguments of every run, one line per run.
+    fn runs(&self) -> Vec<String> {
+        fs::read_to_string(self.dir.join("runs"))
+            .unwrap_or_default()
+            .lines()
+            .map(str::to_owned)
+            .collect()
+    }
+}
+
+impl Drop for Stub {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn source() -> PathBuf {
+    Path::new(FIXTURES).join("C.sol")
+}
+
+#[test]
+fn output_is_parsed_from_a_single_run() {
+    let stub = Stub::printing("ok", "solc/ok.json");
+    let output = compile([source()], &stub.config()).expect("the stub compiles");
+
+    assert_eq!(stub.runs(), ["--standard-json"]);
+    assert!(!output.has_errors());
+    assert_eq!(output.errors().len(), 1);
+    assert_eq!(output.version(), Some("0.8.24+commit.e11b9ed9"));
+
+    let unit = output.source_unit("C.sol").expect("C.sol is compiled");
+    assert_eq!(unit.compiler_version(), Some("0.8.24+commit.e11b9ed9"));
+    assert!(unit.ref_by_id(13).is_some());
+    assert_eq!(output.ast(), Some(unit));
+    let contract = output.contract("C.sol", "C").expect("C is compiled");
+    assert_eq!(contract.abi()[0].name(), Some("f"));
+}
+
+#[test]
+fn output_without_sources_has_no_ast() {
+    let output = SolcOutput::from_reader(r#"{"sources": {}}"#.as_bytes()).expect("valid output");
+    assert!(output.ast().is_none());
+}
+
+#[test]
+fn error_entries_are_returned_as_diagnostics() {
+    let stub = Stub::printing("errors", "solc/errors.json");
+    let err = compile([source()], &stub.config()).expect_err("the stub reports an error");
+
+    let ErrorKind::Compilation(diagnostics) = err.kind() else {
+        panic!("expected a compilation error, got {err}");
+    };
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics[0].is_error());
+    assert_eq!(diagnostics[0].kind(), "ParserError");
+    assert_eq!(diagnostics[0].message(), "Expected ';' but got '}'");
+    assert!(!diagnostics[1].is_error());
+    assert_eq!(stub.runs().len(), 1);
+}
+
+#[test]
+fn failing_solc_reports_its_status_and_stderr() {
+    let stub = Stub::new("exit", "cat > /dev/null\necho boom >&2\nexit 3");
+    let err = compile([source()], &stub.config()).expect_err("the stub fails");
+
+    let ErrorKind::Solc { status, stderr } = err.kind() else {
+        panic!("expected a solc error, got {err}");
+    };
+    assert_eq!(*status, Some(3));
+    assert_eq!(stderr.trim(), "boom");
+}
+
+#[test]
+fn garbage_output_is_a_parsing_error() {
+    let stub = Stub::new("garbage", "cat > /dev/null\necho 'not json'");
+    let err = compile([source()], &stub.config()).expect_err("the output is not JSON");
+
+    assert!(
+        matches!(err.kind(), ErrorKind::JsonParsingError { .. }),
+        "expected a parsing error, got {err}"
+    );
+}
@@ -0,0 +1,185 @@
+//! Source locations, line/column conversion and source providers.
+
+use ast_parser::{
+    ast_parse::{SolidityParser, parse},
+    ast_source::{ColumnEncoding, LineColumn, LineIndex, SourceProvider, SrcLocation},
+    ast_specs::NodeType,
+    ast_visitor::AstIter,
+};
+
+#[test]
+fn src_locations_parse_and_print_back() {
+    let location: SrcLocation = "90:83:2".parse().unwrap();
+    assert_eq!(location, SrcLocation::new(90, 83, Some(2)));
+    assert_eq!(location.end(), 173);
+    assert_eq!(location.to_string(), "90:83:2");
+
+    let generated: SrcLocation = "0:0:-1".parse().unwrap();
+    assert_eq!(generated.file_index(), None);
+    assert_eq!(generated.to_string(), "0:0:-1");
+
+    for invalid in ["", "1:2", "1:2:3:4", "a:2:0", "1:-2:0", "1:2:-3"] {
+        assert!(
+            invalid.parse::<SrcLocation>().is_err(),
+            "{invalid:?} parsed"
+        );
+    }
+}
+
+#[test]
+fn containment_and_overlap_stay_within_one_file() {
+    let outer = SrcLocation::new(10, 20, Some(0));
+    assert!(outer.contains(&SrcLocation::new(10, 20, Some(0))));
+    assert!(outer.contains(&SrcLocation::new(15, 5, Some(0))));
+    assert!(!outer.contains(&SrcLocation::new(25, 10, Some(0))));
+    assert!(!outer.contains(&SrcLocation::new(15, 5, Some(1))));
+
+    assert!(outer.overlaps(&SrcLocation::new(25, 10, Some(0))));
+    assert!(!outer.overlaps(&SrcLocation::new(30, 10, Some(0))));
+    assert!(!outer.overlaps(&SrcLocation::new(15, 5, Some(1))));
+
+    assert!(outer.contains_offset(10));
+    assert!(!outer.contains_offset(30));
+}
+
+#[test]
+fn line_columns_round_trip_in_both_encodings() {
+    // `é` is two bytes and one UTF-16 unit, `𝔸` four bytes and two units
+    let source = "a = \"é𝔸\";\nb\r\n𝔸c";
+    let index = LineIndex::new(source);
+    assert_eq!(index.line_count(), 3);
+
+    let c = source.find('c').unwrap();
+    assert_eq!(
+        index.line_column(c, ColumnEncoding::Utf8),
+        Some(LineColumn::new(3, 5))
+    );
+    assert_eq!(
+        index.line_column(c, ColumnEncoding::Utf16),
+        Some(LineColumn::new(3, 3))
+    );
+
+    for encoding in [ColumnEncoding::Utf8, ColumnEncoding::Utf16] {
+        for (offset, _) in source.char_indices().chain([(source.len(), ' ')]) {
+            let position = index.line_column(offset, encoding).unwrap();
+            assert_eq!(
+                index.offset(position, encoding),
+                Some(offset),
+                "{position:?}"
+            );
+        }
+    }
+
+    // Inside a character
+    assert_eq!(index.line_column(6, ColumnEncoding::Utf8), None);
+    assert_eq!(
+        index.offset(LineColumn::new(3, 2), ColumnEncoding::Utf16),
+        None
+    );
+}
+
+#[test]
+fn lines_are_returned_without_their_line_break() {
+    let index = LineIndex::new("first\r\nsecond\r\nthird");
+    assert_eq!(index.line_text(1), Some("first"));
+    assert_eq!(index.line_text(3), Some("third"));
+    assert_eq!(index.lines_text(1..=2), Some("first\r\nsecond"));
+    assert_eq!(index.lines_text(0..=9), Some("first\r\nsecond\r\nthird"));
+    assert_eq!(index.line_text(4), None);
+}
+
+#[test]
+fn providers_only_hand_out_their_own_file() {
+    let source = "contract C {\n    uint256 x;\n}\n";
+    let unit = SolidityParser::new()
+        .with_file_index(1)
+        .parse_source_unit(source)
+        .unwrap();
+    assert_eq!(SourceProvider::source(&unit, 1), Some(source));
+    assert_eq!(SourceProvider::source(&unit, 0), None);
+    assert_eq!((1, source).source(1), Some(source));
+    assert_eq!((1, source).source(0), None);
+
+    let variable = unit
+        .iter_by_node_type(NodeType::VariableDeclaration)
+        .next()
+        .unwrap();
+    assert_eq!(variable.text(&unit), Some("uint256 x"));
+    assert_eq!(variable.text(&(1, source.to_owned())), Some("uint256 x"));
+    assert_eq!(variable.text(&(0, source)), None);
+    assert_eq!(variable.line_range(&unit), Some(2..=2));
+}
+
+#[test]
+fn unit_line_indexes_follow_their_source() {
+    let mut unit = parse("contract C {\n    uint256 x;\n}\n").unwrap();
+    assert_eq!(unit.line_index(0).unwrap().line_count(), 4);
+    assert_eq!(
+        unit.line_index(0).unwrap().line_text(2),
+        Some("    uint256 x;")
+    );
+
+    unit.set_source("contract C { uint256 x; }".to_owned());
+    let index = unit.line_index(0).unwrap();
+    assert_eq!(index.line_count(), 1);
+    assert_eq!(index.line_text(1), Some("contract C { uint256 x; }"));
+}
+
+/// A CRLF file with multi-byte characters on the lines of `f`.
+const CRLF: &str = "contract C {\r\n    string s = \"é\";\r\n\r\n    function f() public {\r\n        s = \"ü𝔸\";\r\n    }\r\n}\r\n";
+
+#[test]
+fn snippets_of_crlf_files_have_no_carriage_returns() {
+    let unit = parse(CRLF).unwrap();
+    let assignment = unit.iter_by_node_type(NodeType::Assignment).next().unwrap();
+
+    assert_eq!(assignment.text(&unit), Some("s = \"ü𝔸\""));
+    assert_eq!(assignment.line_range(&unit), Some(5..=5));
+    assert_eq!(assignment.context(&unit, 0), Some("        s = \"ü𝔸\";"));
+    assert_eq!(
+        assignment.context(&unit, 1),
+        Some("    function f() public {\r\n        s = \"ü𝔸\";\r\n    }")
+    );
+    assert_eq!(
+        assignment.context(&unit, 2),
+        Some("\r\n    function f() public {\r\n        s = \"ü𝔸\";\r\n    }\r\n}")
+    );
+}
+
+#[test]
+fn snippet_columns_count_multi_byte_characters() {
+    let unit = parse(CRLF).unwrap();
+    let assignment = unit.iter_by_node_type(NodeType::Assignment).next().unwrap();
+    let index = assignment.line_index(&unit).unwrap();
+    let location = assignment.src_location().unwrap();
+
+    let (start, end) = index
+        .location_range(location, ColumnEncoding::Utf8)
+        .unwrap();
+    assert_eq!(
+        (start, end),
+        (LineColumn::new(5, 9), LineColumn::new(5, 21))
+    );
+    let (start, end) = index
+        .location_range(location, ColumnEncoding::Utf16)
+        .unwrap();
+    assert_eq!(
+        (start, end),
+        (LineColumn::new(5, 9), LineColumn::new(5, 18))
+    );
+}
+
+#[test]
+fn snippets_need_the_file_of_the_node() {
+    let unit = SolidityParser::new()
+        .with_file_index(3)
+        .parse_source_unit(CRLF)
+        .unwrap();
+    let assignment = unit.iter_by_node_type(NodeType::Assignment).next().unwrap();
+    assert_eq!(
+        assignment.context(&(3, CRLF), 0),
+        Some("        s = \"ü𝔸\";")
+    );
+    assert_eq!(assignment.context(&(0, CRLF), 0), None);
+    assert_eq!(assignment.line_range(&(0, CRLF)), None);
+}
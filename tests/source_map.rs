@@ -0,0 +1,85 @@
+//! Source maps recorded while printing, from output offsets back to the parsed nodes.
+
+use ast_parser::{
+    ast_parse::parse,
+    ast_serialize::{AstSerializer, PrinterConfig, SourceMap},
+    ast_specs::NodeType,
+    ast_visitor::AstRefVisitor,
+};
+
+const SOURCE: &str = include_str!("fixtures/Mapped.sol");
+
+fn printed() -> (String, SourceMap) {
+    let unit = parse(SOURCE).expect("The fixture parses");
+    unit.to_sol_string_with_source_map(&PrinterConfig::default())
+}
+
+/// Original text of the node printed innermost at the first `needle` of the output.
+fn source_at<'a>(printed: &str, map: &SourceMap, needle: &str) -> &'a str {
+    let offset = printed.find(needle).expect("The needle is printed");
+    let mapping = map.at(offset).expect("The needle is mapped");
+    &SOURCE[mapping.src().range()]
+}
+
+#[test]
+fn statements_map_back_to_their_source() {
+    let (printed, map) = printed();
+    assert_eq!(source_at(&printed, &map, "return total"), "return total;");
+    assert_eq!(source_at(&printed, &map, "continue"), "continue;");
+    assert_eq!(source_at(&printed, &map, "+="), "total += values[i]");
+}
+
+#[test]
+fn yul_nodes_are_mapped_without_an_id() {
+    let (printed, map) = printed();
+    let offset = printed
+        .find("0x40, value")
+        .expect("The assembly is printed")
+        + "0x40, ".len();
+    let mapping = map.at(offset).expect("The Yul identifier is mapped");
+    assert_eq!(mapping.id(), None);
+    assert_eq!(&SOURCE[mapping.src().range()], "value");
+
+    let call = printed.find("mstore(0x40, value)").unwrap();
+    let statement = map
+        .mappings()
+        .iter()
+        .find(|mapping| mapping.start() == call && printed[mapping.range()].ends_with(')'))
+        .expect("The Yul call is mapped");
+    assert_eq!(&SOURCE[statement.src().range()], "mstore(0x40, value)");
+}
+
+#[test]
+fn by_id_finds_every_printing_of_a_node() {
+    let unit = parse(SOURCE).unwrap();
+    let (printed, map) = unit.to_sol_string_with_source_map(&PrinterConfig::default());
+
+    for node_type in [
+        NodeType::Return,
+        NodeType::Continue,
+        NodeType::FunctionDefinition,
+    ] {
+        for node in unit.refs_by_node_type(node_type) {
+            let id = node.id().expect("The node has an id");
+            let mappings: Vec<_> = map.by_id(id).collect();
+            assert_eq!(mappings.len(), 1, "{node_type:?} {id} is printed once");
+            assert_eq!(mappings[0].src().to_string(), node.src());
+            assert!(!printed[mappings[0].range()].is_empty());
+        }
+    }
+    assert_eq!(map.by_id(1_000_000).count(), 0);
+}
+
+#[test]
+fn mappings_nest_in_pre_order() {
+    let (printed, map) = printed();
+    let mappings = map.mappings();
+    assert!(!mappings.is_empty());
+    for pair in mappings.windows(2) {
+        assert!(pair[0].start() <= pair[1].start());
+    }
+    for mapping in mappings {
+        assert!(mapping.end() <= printed.len());
+    }
+    assert!(map.at(printed.len()).is_none());
+}
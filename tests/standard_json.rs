@@ -0,0 +1,23 @@
+//! Compiler input in the solc standard-JSON format.
+
+use ast_parser::ast_framework::{Settings, StandardJsonInput};
+use serde_json::{Value, json};
+
+#[test]
+fn default_settings_select_the_ast() {
+    let settings = Settings::default();
+    assert_eq!(settings, Settings::new());
+    let json = serde_json::to_value(&settings).unwrap();
+    assert_eq!(json["outputSelection"]["*"][""], json!(["ast"]));
+}
+
+#[test]
+fn settings_without_a_selection_serialize_without_one() {
+    let settings: Settings = serde_json::from_value(json!({ "viaIR": true })).unwrap();
+    let json = serde_json::to_value(&settings).unwrap();
+    assert_eq!(json, json!({ "viaIR": true }));
+
+    let input = StandardJsonInput::new(Default::default(), Settings::default());
+    let json = serde_json::to_value(&input).unwrap();
+    assert_ne!(json["settings"]["outputSelection"], Value::Null);
+}
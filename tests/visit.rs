@@ -0,0 +1,210 @@
+//! Hook order and walk control of `Visit` and `VisitMut`.
+
+use std::ops::ControlFlow;
+
+use ast_parser::{
+    ast_parse::parse,
+    ast_specs::{
+        NodeType, NodeTypeInternalRef, SourceUnit, directives::FunctionDefinition,
+        expressions::Identifier,
+    },
+    ast_visitor::{AstVisitor, Visit, VisitControl, VisitMut, Walk, WalkMut},
+};
+
+/// solc 0.8 compact AST of `fixtures/C.sol`.
+const AST: &str = include_str!("fixtures/C.ast.json");
+
+fn unit() -> SourceUnit {
+    serde_json::from_str(AST).expect("The fixture is a valid compact AST")
+}
+
+/// Hooks as they run, `node` and `leave` for the generic ones.
+#[derive(Default)]
+struct Recorder {
+    hooks: Vec<String>,
+    /// Control returned by the typed hooks of functions.
+    function: VisitControl,
+    /// Control returned by the typed hooks of identifiers.
+    identifier: VisitControl,
+}
+
+impl Recorder {
+    fn node(&mut self, hook: &str, node: NodeTypeInternalRef<'_>) -> VisitControl {
+        let id = node.id().map(|id| id.to_string()).unwrap_or_default();
+        self.hooks.push(
+            format!("{hook} {:?} {id}", NodeType::from(node))
+                .trim_end()
+                .to_owned(),
+        );
+        VisitControl::Continue
+    }
+}
+
+impl<'ast> Visit<'ast> for Recorder {
+    fn visit_node(&mut self, node: NodeTypeInternalRef<'ast>) -> VisitControl {
+        self.node("node", node)
+    }
+
+    fn leave_node(&mut self, node: NodeTypeInternalRef<'ast>) -> VisitControl {
+        self.node("leave", node)
+    }
+
+    fn visit_function_definition(&mut self, _node: &'ast FunctionDefinition) -> VisitControl {
+        self.hooks.push("visit_function_definition".to_owned());
+        self.function
+    }
+
+    fn leave_function_definition(&mut self, _node: &'ast FunctionDefinition) -> VisitControl {
+        self.hooks.push("leave_function_definition".to_owned());
+        VisitControl::Continue
+    }
+
+    fn visit_identifier(&mut self, _node: &'ast Identifier) -> VisitControl {
+        self.identifier
+    }
+}
+
+impl VisitMut for Recorder {
+    fn visit_node(&mut self, node: NodeTypeInternalRef<'_>) -> VisitControl {
+        self.node("node", node)
+    }
+
+    fn leave_node(&mut self, node: NodeTypeInternalRef<'_>) -> VisitControl {
+        self.node("leave", node)
+    }
+
+    fn visit_function_definition(&mut self, _node: &mut FunctionDefinition) -> VisitControl {
+        self.hooks.push("visit_function_definition".to_owned());
+        self.function
+    }
+
+    fn leave_function_definition(&mut self, _node: &mut FunctionDefinition) -> VisitControl {
+        self.hooks.push("leave_function_definition".to_owned());
+        VisitControl::Continue
+    }
+
+    fn visit_identifier(&mut self, _node: &mut Identifier) -> VisitControl {
+        self.identifier
+    }
+}
+
+fn walk(mut recorder: Recorder) -> (ControlFlow<()>, Vec<String>) {
+    let flow = unit().walk(&mut recorder);
+    (flow, recorder.hooks)
+}
+
+fn walk_mut(mut recorder: Recorder) -> (ControlFlow<()>, Vec<String>) {
+    let flow = unit().walk_mut(&mut recorder);
+    (flow, recorder.hooks)
+}
+
+#[test]
+fn hooks_run_in_walk_order() {
+    let expected = [
+        "node SourceUnit 30",
+        "node PragmaDirective 1",
+        "leave PragmaDirective 1",
+        "node ContractDefinition 20",
+        "node VariableDeclaration 3",
+        "node ElementaryTypeName 103",
+        "leave ElementaryTypeName 103",
+        "leave VariableDeclaration 3",
+        "node FunctionDefinition 13",
+        "visit_function_definition",
+        "node Block 12",
+        "node Return 11",
+        "node BinaryOperation 10",
+        "node Identifier 8",
+        "leave Identifier 8",
+        "node Identifier 9",
+        "leave Identifier 9",
+        "leave BinaryOperation 10",
+        "leave Return 11",
+        "leave Block 12",
+        "node ParameterList 6",
+        "node VariableDeclaration 5",
+        "node ElementaryTypeName 105",
+        "leave ElementaryTypeName 105",
+        "leave VariableDeclaration 5",
+        "leave ParameterList 6",
+        "node ParameterList 7",
+        "node VariableDeclaration 4",
+        "node ElementaryTypeName 104",
+        "leave ElementaryTypeName 104",
+        "leave VariableDeclaration 4",
+        "leave ParameterList 7",
+        "leave_function_definition",
+        "leave FunctionDefinition 13",
+        "leave ContractDefinition 20",
+        "leave SourceUnit 30",
+    ];
+    assert_eq!(
+        walk(Recorder::default()),
+        (
+            ControlFlow::Continue(()),
+            expected.map(String::from).to_vec()
+        )
+    );
+    assert_eq!(walk_mut(Recorder::default()), walk(Recorder::default()));
+}
+
+#[test]
+fn skipped_children_still_leave() {
+    let recorder = || Recorder {
+        function: VisitControl::SkipChildren,
+        ..Recorder::default()
+    };
+    let (flow, hooks) = walk(recorder());
+    assert_eq!(flow, ControlFlow::Continue(()));
+    let start = hooks
+        .iter()
+        .position(|hook| hook == "visit_function_definition")
+        .expect("f is visited");
+    assert_eq!(
+        hooks[start + 1..],
+        [
+            "leave_function_definition",
+            "leave FunctionDefinition 13",
+            "leave ContractDefinition 20",
+            "leave SourceUnit 30",
+        ]
+    );
+    assert_eq!(walk_mut(recorder()), (flow, hooks));
+}
+
+#[test]
+fn stop_ends_the_walk() {
+    let recorder = || Recorder {
+        identifier: VisitControl::Stop,
+        ..Recorder::default()
+    };
+    let (flow, hooks) = walk(recorder());
+    assert_eq!(flow, ControlFlow::Break(()));
+    assert_eq!(hooks.last().map(String::as_str), Some("node Identifier 8"));
+    assert!(!hooks.iter().any(|hook| hook == "leave_function_definition"));
+    assert_eq!(walk_mut(recorder()), (flow, hooks));
+}
+
+#[test]
+fn nodes_outside_the_child_tables_run_the_generic_hooks() {
+    let unit = parse("contract A {}\ncontract B is A {}\n").expect("The source parses");
+    let contract = unit
+        .filter_ref_by_node_type(NodeType::ContractDefinition)
+        .into_iter()
+        .filter_map(NodeTypeInternalRef::cast_contract_definition)
+        .find(|contract| contract.name() == "B")
+        .expect("B is a contract");
+    let base = &contract.base_contracts()[0];
+
+    let mut recorder = Recorder::default();
+    let flow = NodeTypeInternalRef::InheritanceSpecifier(base).walk(&mut recorder);
+    assert_eq!(flow, ControlFlow::Continue(()));
+    let id = base.id();
+    assert_eq!(
+        recorder.hooks,
+        [
+            format!("node InheritanceSpecifier {id}"),
+            format!("leave InheritanceSpecifier {id}")
+        ]
+    );
+}
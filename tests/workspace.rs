@@ -0,0 +1,73 @@
+//! Node ids shared by units of separate compilations.
+
+use ast_parser::{ast_framework::Workspace, ast_source::SourceProvider, ast_specs::SourceUnit};
+use serde_json::Value;
+
+/// solc 0.8 compact AST of `fixtures/C.sol`.
+const AST: &str = include_str!("fixtures/C.ast.json");
+
+/// The fixture, as compiled from `path` into a unit with `id`.
+fn unit(path: &str, id: i64) -> SourceUnit {
+    let mut ast: Value = serde_json::from_str(AST).expect("The fixture is valid JSON");
+    ast["absolutePath"] = path.into();
+    ast["id"] = id.into();
+    serde_json::from_value(ast).expect("The fixture is a valid compact AST")
+}
+
+#[test]
+fn ids_of_one_compilation_resolve_to_their_unit() {
+    let workspace: Workspace = [unit("C.sol", 30)].into_iter().collect();
+    assert_eq!(workspace.conflicts().count(), 0);
+    let unit = workspace.unit_of(13).expect("f is in C.sol");
+    assert_eq!(unit.absolute_path(), "C.sol");
+    assert_eq!(workspace.units_of(13).count(), 1);
+}
+
+#[test]
+fn duplicate_units_are_not_conflicts() {
+    let mut workspace = Workspace::new();
+    assert!(workspace.add_unit(unit("C.sol", 30)));
+    assert!(!workspace.add_unit(unit("C.sol", 30)));
+    assert_eq!(workspace.conflicts().count(), 0);
+    assert!(workspace.unit_of(13).is_some());
+}
+
+#[test]
+fn ids_reused_across_compilations_are_reported() {
+    let workspace: Workspace = [unit("C.sol", 30), unit("D.sol", 31)].into_iter().collect();
+    assert_eq!(workspace.len(), 2);
+
+    let conflicts: Vec<_> = workspace.conflicts().collect();
+    assert!(conflicts.contains(&13));
+    assert!(!conflicts.contains(&30) && !conflicts.contains(&31));
+
+    assert!(workspace.unit_of(13).is_none());
+    let paths: Vec<_> = workspace
+        .units_of(13)
+        .map(SourceUnit::absolute_path)
+        .collect();
+    assert_eq!(paths, ["C.sol", "D.sol"]);
+    assert_eq!(
+        workspace.unit_of(31).map(SourceUnit::absolute_path),
+        Some("D.sol")
+    );
+}
+
+#[test]
+fn sources_are_handed_out_for_files_only_one_compilation_has() {
+    let source = include_str!("fixtures/C.sol");
+    let mut workspace: Workspace = [unit("C.sol", 30)].into_iter().collect();
+    assert_eq!(workspace.source(0), None);
+    workspace.set_source("C.sol", source.to_owned());
+    assert_eq!(workspace.source(0), Some(source));
+    assert_eq!(
+        workspace.line_index(0).map(|index| index.line_count()),
+        Some(11)
+    );
+
+    // D.sol of another compilation is file 0 too
+    workspace.add_unit(unit("D.sol", 31));
+    workspace.set_source("D.sol", source.to_owned());
+    assert_eq!(workspace.source(0), None);
+    assert!(workspace.line_index(0).is_none());
+}